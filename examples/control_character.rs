@@ -0,0 +1,57 @@
+//! Test control characters in strings and comments.
+//! 文字列とコメントの中の制御文字のテスト。
+//!
+//! `cargo run --example control_character`
+//!
+//! The rejected lines are written to the log file.
+//! 拒否した行はログ・ファイルに書かれます。
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::Toml;
+
+fn main() {
+    // Accepted. Tab is not a forbidden control character.
+    // 受け付けます。タブは禁止された制御文字ではありません。
+    let doc = Toml::from_string(
+        "a = \"x\ty\" # tab\there
+b = 'x\ty'
+c = \"\"\"
+x\ty
+z\"\"\"
+d = '''
+x\ty\r
+z'''
+e = \"\\u0000\\u001F\\u007F\"
+",
+    );
+    assert_eq!(doc.get("a"), Ok("x\ty".to_string()));
+    assert_eq!(doc.get("b"), Ok("x\ty".to_string()));
+    assert_eq!(doc.get("c"), Ok("x\ty\nz".to_string()));
+    assert_eq!(doc.get("d"), Ok("x\ty\r\nz".to_string()));
+    // Escaped, they are fine.
+    // エスケープすれば構いません。
+    assert_eq!(doc.get("e"), Ok("\u{0}\u{1f}\u{7f}".to_string()));
+
+    // Rejected. The line is not in the document, the next line is.
+    // 拒否します。その行はドキュメントに入らず、次の行は入ります。
+    for c in ['\u{0}', '\u{1}', '\u{8}', '\u{1f}', '\u{7f}'] {
+        for text in [
+            format!("bad = \"x{}\"", c),
+            format!("bad = 'x{}'", c),
+            format!("bad = \"\"\"x{}\"\"\"", c),
+            format!("bad = '''x{}'''", c),
+            format!("bad = 1 # x{}", c),
+        ] {
+            let doc = Toml::from_string(&format!("{}\nok = 1\n", text));
+            assert!(!doc.contains_key("bad"), "{:?}", text);
+            assert_eq!(doc.get::<i64>("ok"), Ok(1), "{:?}", text);
+            assert!(!doc.to_string().contains(c), "{:?}", text);
+        }
+    }
+
+    // A line with only a comment.
+    // コメントだけの行。
+    let doc = Toml::from_string("# x\u{7f}\nok = 1\n");
+    assert_eq!(doc.to_string(), "ok = 1\n");
+}
@@ -3,7 +3,11 @@
 
 extern crate tomboy_toml_dom;
 
-use tomboy_toml_dom::Toml;
+use chrono::NaiveDate;
+use tomboy_toml_dom::{
+    model::{layer220::ItemValue, layer225::Val, layer230::Expression},
+    Toml,
+};
 
 fn main() {
     // Read a toml.
@@ -12,7 +16,6 @@ fn main() {
 
     // Read a array.
     // 配列読取。
-    /* TODO
     assert_eq!(
         doc.get_string_array_by_key("int_array"),
         Ok(Some(vec![
@@ -29,7 +32,14 @@ fn main() {
         doc.get_int_array_by_key("int_array"),
         Ok(Some(vec![-1, 0, 1]))
     );
-    */
+    assert_eq!(
+        doc.get_string_array_by_key("float_array"),
+        Ok(Some(vec![
+            "0.0".to_string(),
+            "0.5".to_string(),
+            "1.0".to_string()
+        ]))
+    );
     assert_eq!(
         doc.get_string_array_by_key("string_array"),
         Ok(Some(vec![
@@ -38,4 +48,116 @@ fn main() {
             "\"c\"".to_string()
         ]))
     );
+    assert_eq!(
+        doc.get_string_array_by_key("mix_type_array"),
+        Ok(Some(vec![
+            "1".to_string(),
+            "2.5".to_string(),
+            "true".to_string(),
+            "a".to_string(),
+            "1979-05-27".to_string()
+        ]))
+    );
+
+    // Typed arrays.
+    // 型付きの配列。
+    assert_eq!(
+        doc.get_float_array_by_key("float_array"),
+        Ok(Some(vec![0.0, 0.5, 1.0]))
+    );
+    assert_eq!(
+        doc.get_bool_array_by_key("bool_array"),
+        Ok(Some(vec![true, false]))
+    );
+    assert_eq!(
+        doc.get_datetime_array_by_key("date_array"),
+        Ok(Some(vec![
+            NaiveDate::from_ymd(1979, 5, 27),
+            NaiveDate::from_ymd(2021, 1, 1)
+        ]))
+    );
+    assert_eq!(
+        doc.get_int_array_by_key("hex_array"),
+        Ok(Some(vec![255, 1000]))
+    );
+    assert_eq!(
+        doc.get_int_array_by_key::<u8>("hex_array")
+            .unwrap_err()
+            .to_string(),
+        "hex_array[1] = 1_000 does not fit in u8."
+    );
+    assert_eq!(
+        doc.get_nested_array_by_key("nested_int_array", |m| m.to_int_vector::<i64>()),
+        Ok(Some(vec![vec![1, 2], vec![3]]))
+    );
+    assert_eq!(
+        doc.get_nested_array_by_key("mix_array", |m| m.to_int_vector::<i64>())
+            .unwrap_err()
+            .to_string(),
+        "mix_array[1][0] is a string, not an integer."
+    );
+    assert_eq!(
+        doc.get_int_array_by_key::<i64>("mix_type_array")
+            .unwrap_err()
+            .to_string(),
+        "mix_type_array[1] is a float, not an integer."
+    );
+    assert_eq!(
+        doc.get_bool_array_by_key("string_array")
+            .unwrap_err()
+            .to_string(),
+        "string_array[0] is a string, not a boolean."
+    );
+    match doc.get_inline_table_array_by_key("points") {
+        Ok(Some(points)) => {
+            assert_eq!(points.len(), 2);
+            assert_eq!(points[0]["y"].to_string(), "2");
+        }
+        _ => panic!("points is not a array of inline tables."),
+    }
+    assert_eq!(
+        doc.get_inline_table_array_by_key("int_array")
+            .unwrap_err()
+            .to_string(),
+        "int_array[0] is an integer, not an inline table."
+    );
+
+    // Display writes the source text as is.
+    // Display はソース・テキストをそのまま書きます。
+    assert_eq!(
+        doc.get_val_by_key("points").unwrap().to_string(),
+        "points = [ { x = 1, y = 2 }, { x = 7, y = 8 } ]\n"
+    );
+    assert_eq!(
+        doc.get_val_by_key("mix_array").unwrap().to_string(),
+        "mix_array = [[1, 2, 3], [\"a\", 'b', '\"c\"'], []]\n"
+    );
+
+    // Walk nested data.
+    // 入れ子のデータを辿る。
+    if let Some(Expression::Keyval(_ws1, keyval, _ws2, _comment, _newline)) =
+        doc.get_val_by_key("points")
+    {
+        if let Val::Array(points) = &*keyval.val {
+            assert_eq!(points.len(), 2);
+            assert_eq!(points.iter().count(), 2);
+            if let Some(ItemValue::InlineTable(point)) = points.get(1) {
+                assert_eq!(point.len(), 2);
+                assert_eq!(
+                    point.get("x").map(|val| val.to_string()),
+                    Some("7".to_string())
+                );
+                assert!(point.get("z").is_none());
+                let keys: Vec<String> = point.iter().map(|(key, _val)| key.to_string()).collect();
+                assert_eq!(keys, vec!["x".to_string(), "y".to_string()]);
+            } else {
+                panic!("points[1] is not a inline table.");
+            }
+            assert!(points.get(2).is_none());
+        } else {
+            panic!("points is not a array.");
+        }
+    } else {
+        panic!("points is not found.");
+    }
 }
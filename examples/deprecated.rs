@@ -132,8 +132,7 @@ world!!"
     assert_eq!(
         doc.get_string_by_key("multiline_basic_string_escape_double_quotation"),
         Some(
-            "
-\\
+            "\\
 "
             .to_string()
         )
@@ -148,8 +147,7 @@ world!!"
     assert_eq!(
         doc.get_string_by_key("multiline_basic_string_tab"),
         Some(
-            "
-a\tb
+            "a\tb
 "
             .to_string()
         )
@@ -391,8 +389,7 @@ world!!"
     assert_eq!(
         doc.get_string_by_key("multiline_basic_string_escape_double_quotation"),
         Some(
-            "
-\\
+            "\\
 "
             .to_string()
         )
@@ -407,8 +404,7 @@ world!!"
     assert_eq!(
         doc.get_string_by_key("multiline_basic_string_tab"),
         Some(
-            "
-a\tb
+            "a\tb
 "
             .to_string()
         )
@@ -0,0 +1,58 @@
+//! Test escape sequences.
+//! エスケープ・シーケンスのテスト。
+//!
+//! `cargo run --example escape`
+//!
+//! The rejected lines are written to the log file.
+//! 拒否した行はログ・ファイルに書かれます。
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::Toml;
+
+fn main() {
+    // Accepted.
+    // 受け付けます。
+    let doc = Toml::from_string(
+        "a = \"\\b\\f\\U0001F600\"
+b = \"\\t\\n\\r\\\"\\\\\"
+c = \"\\u00E9\\U0010FFFF\\u0000\"
+d = \"\"\"\\b\\f\\U0001F600\"\"\"
+",
+    );
+    assert_eq!(doc.get("a"), Ok("\u{8}\u{c}\u{1F600}".to_string()));
+    assert_eq!(doc.get("b"), Ok("\t\n\r\"\\".to_string()));
+    assert_eq!(doc.get("c"), Ok("\u{E9}\u{10FFFF}\u{0}".to_string()));
+    assert_eq!(doc.get("d"), Ok("\u{8}\u{c}\u{1F600}".to_string()));
+    // The source text is kept.
+    // ソース・テキストは残ります。
+    assert!(doc.to_string().starts_with("a = \"\\b\\f\\U0001F600\"\n"));
+
+    // Rejected. The line is not in the document, the next line is.
+    // 拒否します。その行はドキュメントに入らず、次の行は入ります。
+    for text in [
+        // Surrogates.
+        // サロゲート。
+        "bad = \"\\uD800\"",
+        "bad = \"\\uDFFF\"",
+        "bad = \"\\U0000D800\"",
+        // Above U+10FFFF.
+        // U+10FFFF より上。
+        "bad = \"\\U00110000\"",
+        "bad = \"\\UFFFFFFFF\"",
+        // Unknown escapes.
+        // 知らないエスケープ。
+        "bad = \"\\q\"",
+        "bad = \"\\x41\"",
+        "bad = \"\"\"\\q\"\"\"",
+        // Short hex runs.
+        // 短い16進数の並び。
+        "bad = \"\\u12\"",
+        "bad = \"\\u12G4\"",
+        "bad = \"\\U0001F60\"",
+    ] {
+        let doc = Toml::from_string(&format!("{}\nok = 1\n", text));
+        assert!(!doc.contains_key("bad"), "{}", text);
+        assert_eq!(doc.get::<i64>("ok"), Ok(1), "{}", text);
+    }
+}
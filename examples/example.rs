@@ -130,8 +130,7 @@ world!!"
     assert_eq!(
         doc.get_string_by_key("multiline_basic_string_escape_double_quotation"),
         Some(
-            "
-\\
+            "\\
 "
             .to_string()
         )
@@ -146,8 +145,7 @@ world!!"
     assert_eq!(
         doc.get_string_by_key("multiline_basic_string_tab"),
         Some(
-            "
-a\tb
+            "a\tb
 "
             .to_string()
         )
@@ -0,0 +1,243 @@
+//! Test typed get.
+//! 型付きゲットのテスト。
+//!
+//! `cargo run --example from_toml`
+
+extern crate tomboy_toml_dom;
+
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use std::collections::HashMap;
+use tomboy_toml_dom::{
+    model::layer225::{FromToml, GetError, Val},
+    Toml,
+};
+
+/// Your own enum.
+/// 自分の列挙型。
+#[derive(Debug, PartialEq)]
+enum Level {
+    Info,
+    Warn,
+}
+impl FromToml for Level {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        match String::from_toml(val)?.as_str() {
+            "info" => Ok(Level::Info),
+            "warn" => Ok(Level::Warn),
+            s => Err(GetError::invalid_format(&format!(
+                "is `{}`, not a level.",
+                s
+            ))),
+        }
+    }
+}
+
+/// Your own newtype.
+/// 自分のニュータイプ。
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+impl FromToml for Port {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        Ok(Port(u16::from_toml(val)?))
+    }
+}
+
+fn main() {
+    // Read a Toml file.
+    let doc = Toml::from_file("./resource/from-toml.toml");
+
+    // Scalars.
+    // スカラー。
+    assert_eq!(doc.get("title"), Ok("TOML Example".to_string()));
+    assert_eq!(doc.get::<u8>("limits.cpu"), Ok(2));
+    assert_eq!(doc.get::<i64>("site.\"google.com\".rank"), Ok(1));
+    assert_eq!(doc.get::<i64>("site . 'google.com' . rank"), Ok(1));
+    assert_eq!(doc.get("database.enabled"), Ok(true));
+    assert_eq!(doc.get("database.temp_targets.cpu"), Ok(79.5_f32));
+    assert_eq!(
+        doc.get::<DateTime<FixedOffset>>("owner.dob"),
+        Ok(DateTime::parse_from_rfc3339("1979-05-27T07:32:00-08:00").unwrap())
+    );
+
+    // Containers.
+    // コンテナ。
+    assert_eq!(doc.get("ports"), Ok(vec![8000_u16, 8001, 8002]));
+    assert_eq!(doc.get("matrix"), Ok(vec![vec![1_i32, 2], vec![3]]));
+    let mut limits = HashMap::new();
+    limits.insert("cpu".to_string(), 2_i64);
+    limits.insert("memory".to_string(), 512);
+    assert_eq!(doc.get("limits"), Ok(limits));
+    assert_eq!(
+        doc.get::<Option<String>>("owner.name"),
+        Ok(Some("Tom Preston-Werner".to_string()))
+    );
+    assert_eq!(doc.get::<Option<String>>("owner.email"), Ok(None));
+
+    // Your own types.
+    // 自分の型。
+    assert_eq!(doc.get("level"), Ok(Level::Warn));
+    assert_ne!(doc.get("level"), Ok(Level::Info));
+    assert_eq!(
+        doc.get("ports"),
+        Ok(vec![Port(8000), Port(8001), Port(8002)])
+    );
+
+    // Errors name the path and the place.
+    // エラーはパスと場所を示します。
+    let err = |r: Result<(), GetError>| r.unwrap_err().to_string();
+    assert_eq!(
+        err(doc.get::<i64>("title").map(|_| ())),
+        "line 1, column 9: title is a string, not an integer."
+    );
+    assert_eq!(
+        err(doc.get::<Vec<String>>("ports").map(|_| ())),
+        "line 3, column 10: ports[0] is an integer, not a string."
+    );
+    assert_eq!(
+        err(doc.get::<Vec<Vec<bool>>>("matrix").map(|_| ())),
+        "line 4, column 12: matrix[0][0] is an integer, not a boolean."
+    );
+    assert_eq!(
+        err(doc.get::<HashMap<String, NaiveDate>>("limits").map(|_| ())),
+        "line 5, column 18: limits.cpu is an integer, not a date-time."
+    );
+    assert_eq!(
+        err(doc.get::<Vec<i8>>("ports").map(|_| ())),
+        "line 3, column 10: ports[0] = 8000 does not fit in i8."
+    );
+    assert_eq!(
+        err(doc.get::<Level>("title").map(|_| ())),
+        "line 1, column 9: title is `TOML Example`, not a level."
+    );
+    assert_eq!(
+        err(doc.get::<i64>("database.temp_targets.case").map(|_| ())),
+        "line 14, column 37: database.temp_targets.case is a float, not an integer."
+    );
+    assert_eq!(
+        err(doc.get::<String>("owner.email").map(|_| ())),
+        "owner.email is not found."
+    );
+    // An array of tables needs an index.
+    // テーブルの配列には添え字が要ります。
+    assert_eq!(
+        err(doc.get::<String>("products.name").map(|_| ())),
+        "products.name is not found."
+    );
+    assert_eq!(
+        err(doc.get::<String>("owner..name").map(|_| ())),
+        "`owner..name` is not a path."
+    );
+
+    // Match on the kind.
+    // 種類で分岐します。
+    assert_eq!(
+        doc.get::<String>("owner.email"),
+        Err(GetError::NotFound {
+            path: "owner.email".to_string()
+        })
+    );
+    match doc.get::<bool>("owner.dob") {
+        Err(GetError::TypeMismatch {
+            path,
+            expected,
+            found,
+            span: Some(span),
+        }) => {
+            assert_eq!(path, "owner.dob");
+            assert_eq!(expected, "boolean");
+            assert_eq!(found, "date-time");
+            assert_eq!((span.line, span.column), (10, 7));
+            assert_eq!(
+                &doc.to_string()[span.start..span.end],
+                "1979-05-27 07:32:00-08:00"
+            );
+        }
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(
+        doc.get_span_by_path("ports[2]").map(|m| (m.line, m.column)),
+        Some((3, 22))
+    );
+    assert!(matches!(
+        doc.get::<u8>("limits.memory"),
+        Err(GetError::OutOfRange { .. })
+    ));
+
+    // Map keys are the names without quotes. Dotted keys are tables, same as `to_value`.
+    // マップのキーは引用符を外した名前です。ドット区切りのキーは `to_value` と同じくテーブルです。
+    let doc = Toml::from_string(
+        "m = { \"x y\" = 1, 'lit' = 3, a.b = 2, a.c = 4 }
+n = { \"x y\" = 1, 'lit' = 3 }
+o = { a.b = 2, a.c = 4 }
+
+[srv]
+port = 80
+tls.port = 443
+
+[srv.limits]
+cpu = 2
+",
+    );
+    let mut n = HashMap::new();
+    n.insert("x y".to_string(), 1_i64);
+    n.insert("lit".to_string(), 3);
+    assert_eq!(doc.get("n"), Ok(n));
+    let o: HashMap<String, HashMap<String, i64>> = doc.get("o").unwrap();
+    assert_eq!(o["a"]["b"], 2);
+    assert_eq!(o["a"]["c"], 4);
+    assert_eq!(
+        err(doc.get::<HashMap<String, i64>>("m").map(|_| ())),
+        "m.a is an inline table, not an integer."
+    );
+    // A table header.
+    // テーブル・ヘッダー。
+    let mut tls = HashMap::new();
+    tls.insert("port".to_string(), 443_i64);
+    assert_eq!(doc.get("srv.tls"), Ok(tls));
+    let mut limits = HashMap::new();
+    limits.insert("cpu".to_string(), 2_i64);
+    assert_eq!(doc.get("srv.limits"), Ok(limits));
+    assert_eq!(doc.get::<i64>("srv.tls.port"), Ok(443));
+    assert_eq!(
+        err(doc.get::<HashMap<String, i64>>("srv").map(|_| ())),
+        "srv.tls is an inline table, not an integer."
+    );
+    assert!(matches!(
+        doc.get::<HashMap<String, i64>>("nothing"),
+        Err(GetError::NotFound { .. })
+    ));
+
+    // Arrays of tables and indexes.
+    // テーブルの配列と添え字。
+    let doc = Toml::from_string(
+        "[[products]]
+name = \"Hammer\"
+
+[[products]]
+name = \"Nail\"
+color = \"gray\"
+",
+    );
+    let products: Vec<HashMap<String, String>> = doc.get("products").unwrap();
+    assert_eq!(products.len(), 2);
+    assert_eq!(products[0]["name"], "Hammer");
+    assert_eq!(products[1]["color"], "gray");
+    assert_eq!(doc.get("products[1].name"), Ok("Nail".to_string()));
+    assert_eq!(doc.get::<Option<String>>("products[0].color"), Ok(None));
+    assert_eq!(
+        err(doc.get::<String>("products[2].name").map(|_| ())),
+        "products[2].name is not found."
+    );
+    assert_eq!(
+        err(doc.get::<i64>("products[1].name").map(|_| ())),
+        "line 5, column 8: products[1].name is a string, not an integer."
+    );
+    let doc = Toml::from_string("[[a]]\n[[a.b]]\nc = 1\n[[a.b]]\nc = 2\n");
+    assert_eq!(doc.get::<i64>("a[0].b[1].c"), Ok(2));
+    let b: Vec<HashMap<String, i64>> = doc.get("a[0].b").unwrap();
+    assert_eq!(b.len(), 2);
+    assert_eq!(b[0]["c"], 1);
+    let doc = Toml::from_file("./resource/path.toml");
+    assert_eq!(doc.get("a.\"b.c\"[1].name"), Ok("y".to_string()));
+    assert_eq!(doc.get("servers.alpha.ports[1]"), Ok(8001_u16));
+}
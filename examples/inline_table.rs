@@ -5,17 +5,150 @@
 
 extern crate tomboy_toml_dom;
 
-// use tomboy_toml_dom::Toml;
+use tomboy_toml_dom::Toml;
 
 fn main() {
     // Read a Toml file.
-    // let doc = Toml::from_file("./resource/inline-table.toml");
+    let doc = Toml::from_file("./resource/inline-table.toml");
 
-    // TODO
-    /*
+    // Nested inline tables and dotted keys.
+    // 入れ子のインライン・テーブルと、ドット付きキー。
+    assert!(doc.get_val_by_key("inline_table_5").is_some());
+    assert!(doc.get_val_by_key("inline_table_6").is_some());
+
+    // Typed getters on an inline table, same as on the document.
+    // インライン・テーブルの型付きゲッター。ドキュメントと同じです。
+    let table = doc
+        .get_inline_table_by_key("inline_table_4")
+        .unwrap()
+        .unwrap();
+    assert_eq!(table.len(), 3);
+    assert_eq!(table.get_string_by_key("name"), Some("c".to_string()));
+    assert_eq!(table.get_f64_by_key_v2("weight"), Ok(Some(80.0)));
+    assert_eq!(
+        table.get_string_by_key("directory"),
+        Some("c:\\temp".to_string())
+    );
+    assert_eq!(table.get_f64_by_key_v2("height"), Ok(None));
+    assert!(doc
+        .get_inline_table_by_key("inline_table_1")
+        .unwrap()
+        .unwrap()
+        .is_empty());
+    assert!(doc
+        .get_inline_table_by_key("inline_table_7")
+        .unwrap()
+        .is_none());
+
+    // Nested inline tables read the same at every level.
+    // 入れ子のインライン・テーブルも、どの階層でも同じように読めます。
+    let a = doc
+        .get_inline_table_by_key("inline_table_5")
+        .unwrap()
+        .unwrap();
+    let b = a.get_inline_table_by_key("a").unwrap().unwrap();
+    let c = b.get_inline_table_by_key("b").unwrap().unwrap();
+    assert_eq!(c.len(), 1);
+    assert_eq!(
+        c.get_array_by_key("c")
+            .unwrap()
+            .unwrap()
+            .get(0)
+            .unwrap()
+            .to_string(),
+        "1"
+    );
+    assert_eq!(
+        b.get_int_array_by_key::<i64>("b").unwrap_err().to_string(),
+        "b is an inline table, not an array."
+    );
+
+    // Keys are the names without quotes.
+    // キーは引用符を外した名前です。
+    let quoted = Toml::from_string(
+        "t = { \"a b\" = 1, 'c' = 2, type.name = \"pug\" }\nv = [ { \"a b\" = 1, x.y = 2 } ]\n",
+    );
+    let t = quoted.get_inline_table_by_key("t").unwrap().unwrap();
+    assert_eq!(t.get_int_by_key_v2::<i64>("a b"), Ok(Some(1)));
+    assert_eq!(t.get_int_by_key_v2::<i64>("c"), Ok(Some(2)));
+    assert!(t.contains_key("type.name"));
+    assert!(!t.contains_key("\"a b\""));
+    let v = quoted.get_inline_table_array_by_key("v").unwrap().unwrap();
+    assert_eq!(v[0]["a b"].to_string(), "1");
+    assert_eq!(v[0]["x"].to_string(), "{y=2}");
+    assert!(!v[0].contains_key("\"a b\""));
+
+    // Display writes the source text as is.
+    // Display はソース・テキストをそのまま書きます。
+    assert_eq!(
+        doc.get_val_by_key("inline_table_3").unwrap().to_string(),
+        "inline_table_3 = { name = \"b\", weight = 93.5 }\n"
+    );
+    assert_eq!(
+        doc.get_val_by_key("inline_table_1").unwrap().to_string(),
+        "inline_table_1 = {}\n"
+    );
+    assert_eq!(
+        doc.get_val_by_key("inline_table_6").unwrap().to_string(),
+        "inline_table_6 = { type.name = \"pug\", type.\"age\" = 3 }\n"
+    );
+
+    // An inline table is closed. The keys under a rejected header are rejected too.
+    // インライン・テーブルは閉じています。拒否したヘッダーの下のキーも拒否します。
+    let doc = Toml::from_string("a = { b = 1 }\n[a]\nc = 2\n[d]\ne = 3\n");
+    assert_eq!(doc.to_string(), "a = { b = 1 }\n[d]\ne = 3\n");
+    assert!(!doc.contains_key("c"));
+    let doc = Toml::from_string("\"x.y\" = { b = 1 }\n[\"x.y\".c]\nd = 2\n");
+    assert_eq!(doc.to_string(), "\"x.y\" = { b = 1 }\n");
+
+    // Configuration file.
+    // 設定ファイル。
+    let doc = Toml::from_file("./resource/edit-1.type.toml");
+    let file_name = doc.get_inline_table_by_key("file_name").unwrap().unwrap();
+    assert_eq!(
+        file_name.get_string_by_key("type"),
+        Some("string".to_string())
+    );
+    assert_eq!(file_name.get_bool_by_key("regex"), Some(true));
+    assert_eq!(
+        file_name.get_string_by_key("value"),
+        Some("^[a-zA-Z0-9_-]+$".to_string())
+    );
+    let file_ext = doc.get_inline_table_by_key("file_ext").unwrap().unwrap();
+    assert_eq!(
+        file_ext.get_string_array_by_key("value"),
+        Ok(Some(vec![".log.toml".to_string(), ".log".to_string()]))
+    );
+    let retention_days = doc
+        .get_inline_table_by_key("retention_days")
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        retention_days.get_int_by_key_v2::<i16>("default"),
+        Ok(Some(7))
+    );
+    assert_eq!(
+        retention_days.get_i128_by_key_v2("min"),
+        Ok(Some(-100000000))
+    );
+    assert_eq!(
+        retention_days
+            .get_int_by_key_v2::<i16>("min")
+            .unwrap_err()
+            .to_string(),
+        "min = -100000000 does not fit in i16."
+    );
+    assert_eq!(
+        retention_days
+            .get_float_by_key_v2::<f64>("default")
+            .unwrap_err()
+            .to_string(),
+        "default is an integer, not a float."
+    );
     assert_eq!(
-        &format!("{:?}", doc.get_val_by_key("inline_table_3")),
-        "{ name = \"b\", weight = 93.5 }"
+        doc.get_inline_table_by_key("punch")
+            .unwrap_err()
+            .to_string(),
+        "punch is a boolean, not an inline table."
     );
-    */
 }
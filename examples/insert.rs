@@ -0,0 +1,129 @@
+//! Test inserting keys.
+//! キーの挿入のテスト。
+//!
+//! `cargo run --example insert`
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::Toml;
+
+fn main() {
+    let mut doc = Toml::from_string(
+        "title = \"Logger\"
+
+[log]
+  level  =  \"warn\" # Level.
+  retention_days  =  7
+
+[server]
+ip = \"10.0.0.1\"
+site.name = \"alpha\"
+
+[[sinks]]
+name = \"stdout\"
+",
+    );
+
+    // At the end of the table, in its style.
+    // テーブルの最後に、その書き方で。
+    doc.insert("log.retention_size", 100).unwrap();
+    doc.insert("version", "1.0").unwrap();
+    // A dotted key, where the table is written so.
+    // テーブルがドット付きキーで書かれていれば、ドット付きキーで。
+    doc.insert("server.site.port", 8080).unwrap();
+    // Next to a sibling.
+    // 兄弟の隣に。
+    doc.insert_before("log.level", "color", true).unwrap();
+    doc.insert_after("server.site.name", "owner", "tom")
+        .unwrap();
+    // A control character in a key is escaped as TOML.
+    // キーの中の制御文字は TOML としてエスケープします。
+    doc.insert("server.\"a\u{7f}\"", 1).unwrap();
+    assert!(doc.to_string().contains("\"a\\u007F\" = 1\n"));
+    assert_eq!(doc.get::<i64>("server.\"a\u{7f}\""), Ok(1));
+    doc.remove("server.\"a\u{7f}\"").unwrap();
+    // A new table at the end.
+    // 新しいテーブルを最後に。
+    doc.insert("database.pool.\"max size\"", 5).unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "title = \"Logger\"
+version = \"1.0\"
+
+[log]
+  color  =  true
+  level  =  \"warn\" # Level.
+  retention_days  =  7
+  retention_size  =  100
+
+[server]
+ip = \"10.0.0.1\"
+site.name = \"alpha\"
+site.owner = \"tom\"
+site.port = 8080
+
+[[sinks]]
+name = \"stdout\"
+
+[database.pool]
+\"max size\" = 5
+"
+    );
+    assert_eq!(doc.get::<i64>("log.retention_size"), Ok(100));
+    assert_eq!(
+        doc.get::<String>("server.site.owner"),
+        Ok("tom".to_string())
+    );
+    assert_eq!(doc.get::<i64>("database.pool.\"max size\""), Ok(5));
+    assert_eq!(doc["sinks"][0]["name"].as_str(), Some("stdout"));
+
+    // The last line gets a line ending.
+    // 最終行には改行が付きます。
+    let mut doc = Toml::from_string("a = 1");
+    doc.insert("b", [2, 3].to_vec()).unwrap();
+    assert_eq!(doc.to_string(), "a = 1\nb = [2, 3]\n");
+    let mut doc = Toml::from_string("a = 1\r\n[t]");
+    doc.insert("t.b", 2).unwrap();
+    assert_eq!(doc.to_string(), "a = 1\r\n[t]\r\nb = 2\r\n");
+    let mut doc = Toml::from_string("");
+    doc.insert("a.b", 1).unwrap();
+    assert_eq!(doc.to_string(), "[a]\nb = 1\n");
+
+    // Nothing is overwritten.
+    // 何も上書きしません。
+    let mut doc = Toml::from_string(
+        "a = 1
+b = { c = 2 }
+[t]
+d = 3
+[[u]]
+e = 4
+",
+    );
+    assert_eq!(
+        doc.insert("a", 2),
+        Err("a is defined already. Try `doc.set()`.".to_string())
+    );
+    assert_eq!(
+        doc.insert("t", 2),
+        Err("t is defined already. Try `doc.set()`.".to_string())
+    );
+    assert_eq!(doc.insert("a.x", 2), Err("a is not a table.".to_string()));
+    assert_eq!(doc.insert("b.x", 2), Err("b is not a table.".to_string()));
+    assert_eq!(
+        doc.insert("u.x", 2),
+        Err("u is an array of tables.".to_string())
+    );
+    assert_eq!(
+        doc.insert_after("t.x", "y", 2),
+        Err("t.x is not found.".to_string())
+    );
+    assert_eq!(
+        doc.insert_after("t.d", "d", 2),
+        Err("t.d is defined already. Try `doc.set()`.".to_string())
+    );
+    assert_eq!(
+        doc.insert("u[0].x", 2),
+        Err("`u[0].x` has an index.".to_string())
+    );
+}
@@ -0,0 +1,122 @@
+//! Test the key index.
+//! キー索引のテスト。
+//!
+//! `cargo run --example key_index`
+
+extern crate tomboy_toml_dom;
+
+use std::time::Instant;
+use tomboy_toml_dom::{
+    model::{
+        layer110::{Token, TokenType},
+        layer210::Key,
+        layer310::VisitMut,
+    },
+    Toml,
+};
+
+/// Renames `a` to `z`.
+/// `a` を `z` に名前変更します。
+struct Rename;
+impl VisitMut for Rename {
+    fn visit_key_mut(&mut self, m: &mut Key) {
+        if m.to_string() == "a" {
+            *m = Key::from_token(&Token::new("z", TokenType::Key));
+        }
+    }
+}
+
+fn main() {
+    // The first one wins, same as before.
+    // 以前と同じく、最初のものが勝ちます。
+    let doc = Toml::from_string(
+        "a = 1
+b = { c = 2, d.e = 3 }
+[t]
+a = 4
+f.g = 5
+[[u]]
+h = 6
+",
+    );
+    assert_eq!(doc.get_i128_by_key("a"), Some(1));
+    assert_eq!(doc.get_i128_by_key("h"), Some(6));
+    assert_eq!(doc.get_i128_by_key("f.g"), Some(5));
+    assert!(doc.contains_key("f.g"));
+    assert!(!doc.contains_key("g"));
+    assert_eq!(doc.get::<i64>("a"), Ok(1));
+    assert_eq!(doc.get::<i64>("b.c"), Ok(2));
+    assert_eq!(doc.get::<i64>("b.d.e"), Ok(3));
+    assert_eq!(doc.get::<i64>("t.a"), Ok(4));
+    assert_eq!(doc.get::<i64>("t.f.g"), Ok(5));
+    // `t.f` is a table.
+    // `t.f` はテーブルです。
+    assert!(doc.get::<Option<i64>>("t.f").is_err());
+    assert_eq!(doc.get::<Option<i64>>("t.g"), Ok(None));
+    assert_eq!(doc.get::<Option<i64>>("u.h"), Ok(None));
+
+    // Edits drop the index.
+    // 編集は索引を捨てます。
+    let mut doc = doc;
+    assert!(doc.contains_key("a"));
+    Rename.visit_document_mut(&mut doc);
+    assert!(!doc.contains_key("a"));
+    assert_eq!(doc.get_i128_by_key("z"), Some(1));
+    assert_eq!(doc.get::<i64>("t.z"), Ok(4));
+    let more = Toml::from_string("a = 7\n");
+    doc.push_element(&more.elements()[0]);
+    assert_eq!(doc.get_i128_by_key("a"), Some(7));
+
+    // Edits through `elements_mut()` drop the index.
+    // `elements_mut()` を通した編集は索引を捨てます。
+    let mut doc = Toml::from_string("a = 1\nb = 2\n[t]\nc = 3\n");
+    assert_eq!(doc.get_int_by_key_v2::<i64>("b"), Ok(Some(2)));
+    doc.elements_mut().remove(0);
+    assert_eq!(doc.get_int_by_key_v2::<i64>("b"), Ok(Some(2)));
+    assert_eq!(doc.get_int_by_key_v2::<i64>("a"), Ok(None));
+    assert_eq!(doc.get::<i64>("t.c"), Ok(3));
+    doc.elements_mut().truncate(1);
+    assert_eq!(doc.get_int_by_key_v2::<i64>("c"), Ok(None));
+    assert_eq!(doc.get::<Option<i64>>("t.c"), Ok(None));
+    assert_eq!(doc.set("t.c", 4), Err("t.c is not found.".to_string()));
+    doc.elements_mut().push(more.elements()[0].clone());
+    assert_eq!(doc.get_int_by_key_v2::<i64>("a"), Ok(Some(7)));
+    doc.elements_mut()[0] = more.elements()[0].clone();
+    assert_eq!(doc.get_int_by_key_v2::<i64>("b"), Ok(None));
+    assert!(doc.contains_key("a"));
+    // The same length, with a new key.
+    // 同じ長さで、新しいキー。
+    let mut doc = Toml::from_string("a = 1\nb = 2\n");
+    assert_eq!(doc.get::<i64>("a"), Ok(1));
+    doc.elements_mut()[0] = Toml::from_string("c = 3\n").elements()[0].clone();
+    assert_eq!(doc.get::<i64>("c"), Ok(3));
+    assert!(doc.contains_key("c"));
+    assert!(!doc.contains_key("a"));
+    assert_eq!(doc["c"].as_integer(), Some(3));
+
+    // Many keys, each read once.
+    // たくさんのキーを、それぞれ一回読みます。
+    let count = 5000;
+    let mut text = String::new();
+    for i in 0..count {
+        text.push_str(&format!("key{} = {}\n", i, i));
+    }
+    let doc = Toml::from_string(&text);
+    let start = Instant::now();
+    for i in 0..count {
+        assert_eq!(doc.get_i128_by_key(&format!("key{}", i)), Some(i as i128));
+        assert_eq!(doc.get::<i64>(&format!("key{}", i)), Ok(i as i64));
+    }
+    println!("Read {} keys in {:?}.", count, start.elapsed());
+    // Lookups after a push use the index again.
+    // 追加の後の検索も、また索引を使います。
+    let mut doc = doc;
+    doc.elements_mut()
+        .push(Toml::from_string("more = 1\n").elements()[0].clone());
+    let start = Instant::now();
+    for i in 0..count {
+        assert_eq!(doc.get::<i64>(&format!("key{}", i)), Ok(i as i64));
+    }
+    assert_eq!(doc.get::<i64>("more"), Ok(1));
+    println!("Read {} keys after a push in {:?}.", count, start.elapsed());
+}
@@ -24,7 +24,7 @@ fn main() {
     let doc = Toml::from_file(toml_file);
     Log::info_toml_document(toml_file, &doc);
 
-    for elem in doc.elements {
+    for elem in doc.elements() {
         match elem {
             Expression::HeaderOfArrayOfTable(m) => {
                 Log::info_t(
@@ -32,7 +32,7 @@ fn main() {
                     Table::default().str("HeaderOfArrayOfTable", &format!("{}", m)),
                 );
             }
-            Expression::EmptyLine(ws, comment) => {
+            Expression::EmptyLine(ws, comment, _newline) => {
                 Log::info_t(
                     "Scan a Broad-line.",
                     Table::default()
@@ -48,12 +48,12 @@ fn main() {
                         ),
                 );
             }
-            Expression::Keyval(_ws1, keyval, _ws2, _comment) => {
+            Expression::Keyval(_ws1, keyval, _ws2, _comment, _newline) => {
                 Log::info_t(
                     "Scan a Broad-line.",
                     Table::default().str("Keyval", &format!("{}", keyval)),
                 );
-                match *keyval.val {
+                match &*keyval.val {
                     Val::Array(m) => Log::info(&format!("{}", m)),
                     Val::BasicString(m) => Log::info(&format!("{}", m)),
                     Val::InlineTable(m) => Log::info(&format!("{}", m)),
@@ -0,0 +1,48 @@
+//! Test quotation marks at the end of multi-line strings.
+//! 複数行文字列の終わりの引用符のテスト。
+//!
+//! `cargo run --example multi_line_string`
+//!
+//! The rejected lines are written to the log file.
+//! 拒否した行はログ・ファイルに書かれます。
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::Toml;
+
+fn main() {
+    // Up to two quotation marks before the closing ones.
+    // 終わりの引用符の前は２つまで。
+    let doc = Toml::from_string(
+        "a = \"\"\"a\"\"\"\"
+b = \"\"\"a\"\"\"\"\"
+c = '''a''''
+d = '''a'''''
+e = \"\"\"a\"\"b\"\"\"
+f = \"\"\"a\\\"\\\"\\\"\"\"\"\"\"
+",
+    );
+    assert_eq!(doc.get("a"), Ok("a\"".to_string()));
+    assert_eq!(doc.get("b"), Ok("a\"\"".to_string()));
+    assert_eq!(doc.get("c"), Ok("a'".to_string()));
+    assert_eq!(doc.get("d"), Ok("a''".to_string()));
+    assert_eq!(doc.get("e"), Ok("a\"\"b".to_string()));
+    // Escaped ones are not counted.
+    // エスケープしたものは数えません。
+    assert_eq!(doc.get("f"), Ok("a\"\"\"\"\"".to_string()));
+
+    // Rejected. More than five in a row.
+    // 拒否します。５つより多く続くもの。
+    for text in [
+        "bad = \"\"\"a\"\"\"\"\"\"",
+        "bad = \"\"\"a\"\"\"\"\"\"\"",
+        "bad = '''a''''''",
+        "bad = '''a'''''''",
+        "bad = \"\"\"a\n\"\"\"\"\"\"",
+        "bad = '''a\n''''''",
+    ] {
+        let doc = Toml::from_string(&format!("{}\nok = 1\n", text));
+        assert!(!doc.contains_key("bad"), "{:?}", text);
+        assert_eq!(doc.get::<i64>("ok"), Ok(1), "{:?}", text);
+    }
+}
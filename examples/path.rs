@@ -0,0 +1,104 @@
+//! Test paths and indexing.
+//! パスと添え字のテスト。
+//!
+//! `cargo run --example path`
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::{
+    model::layer310::{Path, PathSegment, Value},
+    Toml,
+};
+
+fn main() {
+    // Read a Toml file.
+    let doc = Toml::from_file("./resource/path.toml");
+
+    // Indexing.
+    // 添え字。
+    assert_eq!(doc["servers"]["alpha"]["ip"].as_str(), Some("10.0.0.1"));
+    assert_eq!(doc["servers"]["alpha"]["ports"][1].as_integer(), Some(8001));
+    assert_eq!(doc["products"][1]["name"].as_str(), Some("Nail"));
+    assert_eq!(doc["a"]["b.c"][0]["name"].as_str(), Some("x"));
+
+    // Nothing is there. No panic.
+    // 何も無い。パニックしません。
+    assert_eq!(doc["servers"]["gamma"]["ip"], Value::Absent);
+    assert!(doc["products"][2]["name"].is_absent());
+    assert!(doc["title"]["no"][0].is_absent());
+    assert_eq!(doc["title"][0].as_str(), None);
+
+    // The string form.
+    // 文字列の形式。
+    assert_eq!(doc.path("servers.alpha.ip").as_str(), Some("10.0.0.1"));
+    assert_eq!(
+        doc.path(" servers . 'beta' . ip ").as_str(),
+        Some("10.0.0.2")
+    );
+    assert_eq!(doc.path("products[0].sku").as_integer(), Some(738594937));
+    assert_eq!(doc.path("a.\"b.c\"[1].name").as_str(), Some("y"));
+    assert_eq!(doc.path("servers.alpha.ports[0]").as_integer(), Some(8000));
+    assert!(doc.path("").as_table().is_some());
+    assert!(doc.path("servers.alpha.ports[9]").is_absent());
+    assert!(doc.path("servers..alpha").is_absent());
+
+    // Path.
+    // パス。
+    let path = Path::parse("a.\"b.c\"[0].name").unwrap();
+    assert_eq!(
+        path.segments(),
+        &[
+            PathSegment::Key("a".to_string()),
+            PathSegment::Key("b.c".to_string()),
+            PathSegment::Index(0),
+            PathSegment::Key("name".to_string()),
+        ]
+    );
+    assert_eq!(path.to_string(), "a.\"b.c\"[0].name");
+    assert_eq!(
+        Path::parse("a[x]"),
+        Err("`a[x]` is not a path.".to_string())
+    );
+    assert_eq!(
+        Path::parse("a.'b"),
+        Err("`a.'b` has an unclosed quote.".to_string())
+    );
+    assert_eq!(
+        Path::parse("products[1"),
+        Err("`products[1` has an unclosed `[`.".to_string())
+    );
+
+    // Escapes are decoded, same as TOML. Displayed paths parse back.
+    // TOML と同じくエスケープを解きます。表示したパスは元に戻ります。
+    let path = Path::parse("\"tab\\there\".\"\\u0041\\U0001F600\\\"\\\\\"").unwrap();
+    assert_eq!(
+        path.segments(),
+        &[
+            PathSegment::Key("tab\there".to_string()),
+            PathSegment::Key("A\u{1F600}\"\\".to_string()),
+        ]
+    );
+    assert_eq!(Path::parse(&path.to_string()), Ok(path));
+    // Control characters are written as TOML escapes.
+    // 制御文字は TOML のエスケープで書きます。
+    let path = Path::parse("a.\"b\\u007F\"").unwrap();
+    assert_eq!(path.to_string(), "a.\"b\\u007F\"");
+    assert_eq!(Path::parse(&path.to_string()), Ok(path));
+    assert_eq!(
+        Path::parse("\"a\\q\""),
+        Err("`\"a\\q\"` has an invalid escape sequence `\\q`.".to_string())
+    );
+    assert_eq!(
+        Path::parse("\"a\\u12\""),
+        Err("`\"a\\u12\"` needs 4 hexadecimal digits after `\\u`.".to_string())
+    );
+    assert_eq!(
+        Path::parse("\"\\uD800\""),
+        Err("`\"\\uD800\"` has U+D800, which is not a Unicode scalar value.".to_string())
+    );
+
+    // The typed get takes the same paths.
+    // 型付きゲットも同じパスを受け取ります。
+    assert_eq!(doc.get("products[1].name"), Ok("Nail".to_string()));
+    assert_eq!(doc.get("products[0].sku"), Ok(738594937_i64));
+}
@@ -0,0 +1,111 @@
+//! Test queries.
+//! クエリーのテスト。
+//!
+//! `cargo run --example query`
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::{
+    model::layer310::{Path, Value},
+    Toml,
+};
+
+fn main() {
+    // Read a Toml file.
+    let doc = Toml::from_file("./resource/query.toml");
+
+    // `*` is every value in a table.
+    // `*` はテーブルの全ての値。
+    assert_eq!(
+        to_strings(doc.query("servers.*.ip").unwrap()),
+        vec![
+            "servers.alpha.ip = \"10.0.0.1\"",
+            "servers.beta.ip = \"10.0.0.2\""
+        ]
+    );
+
+    // `[*]` is every item in an array.
+    // `[*]` は配列の全ての項目。
+    assert_eq!(
+        to_strings(doc.query("products[*].sku").unwrap()),
+        vec![
+            "products[0].sku = 738594937",
+            "products[1].sku = 284758393",
+            "products[2].sku = 112233445"
+        ]
+    );
+
+    // `**` is any depth. Inline tables too.
+    // `**` は任意の深さ。インライン・テーブルも。
+    assert_eq!(
+        to_strings(doc.query("**.timeout_secs").unwrap()),
+        vec![
+            "timeout_secs = 30",
+            "servers.alpha.timeout_secs = 5",
+            "database.connection.timeout_secs = 10"
+        ]
+    );
+
+    // Filters. Integers and floats compare as numbers.
+    // フィルター。整数と浮動小数点数は数として比べます。
+    assert_eq!(
+        to_strings(doc.query("products[?price > 10].name").unwrap()),
+        vec![
+            "products[0].name = \"Hammer\"",
+            "products[2].name = \"Saw\""
+        ]
+    );
+    assert_eq!(
+        to_strings(doc.query("products[?name == 'Nail'].price").unwrap()),
+        vec!["products[1].price = 1"]
+    );
+    assert_eq!(
+        to_strings(doc.query("products[?price<=12.5][0].name").unwrap()),
+        Vec::<String>::new()
+    );
+    assert_eq!(doc.query("products[?name > 10]").unwrap().len(), 0);
+    assert_eq!(doc.query("products[ ? price >= 1 ]").unwrap().len(), 3);
+
+    // Plain paths work too.
+    // ただのパスも使えます。
+    assert_eq!(
+        to_strings(doc.query("products[1].name").unwrap()),
+        vec!["products[1].name = \"Nail\""]
+    );
+    assert_eq!(doc.query("no.such").unwrap().len(), 0);
+    assert_eq!(doc.query("").unwrap().len(), 1);
+
+    // Errors.
+    // エラー。
+    assert_eq!(
+        doc.query("products[?price ~ 10]"),
+        Err("`products[?price ~ 10]` is not a query.".to_string())
+    );
+    assert_eq!(
+        doc.query("products[?price > ]"),
+        Err("`products[?price > ]` is not a query.".to_string())
+    );
+    assert_eq!(
+        Toml::from_string("a = 1\na = 2\n").query("a"),
+        Err("a is defined twice.".to_string())
+    );
+}
+
+/// `path = value`.
+fn to_strings(matches: Vec<(Path, &Value)>) -> Vec<String> {
+    matches
+        .iter()
+        .map(|(path, value)| {
+            format!(
+                "{} = {}",
+                path,
+                match value {
+                    Value::String(s) => format!("{:?}", s),
+                    Value::Integer(n) => n.to_string(),
+                    Value::Float(n) => n.to_string(),
+                    _ => value.type_name().to_string(),
+                }
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,167 @@
+//! Test removing.
+//! 削除のテスト。
+//!
+//! `cargo run --example remove`
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::Toml;
+
+fn main() {
+    let text = "# Logger.
+title = \"Logger\"
+version = 1 # One.
+
+# Log.
+[log]
+level = \"warn\"
+retention_days = 7
+rotation = { daily = true, size.max = 10, size.min = 1 }
+ports = [ 8000, 8001, 8002 ]
+
+# Database.
+[database]
+ip = \"10.0.0.1\"
+
+[log.file]
+path = \"./app.log\"
+
+[[products]]
+name = \"Hammer\"
+
+[[products]]
+name = \"Nail\"
+[products.spec]
+size = 3
+
+[[products]]
+name = \"Saw\"
+";
+
+    // A key-value. Its comment too.
+    // キー・バリュー。そのコメントも。
+    let mut doc = Toml::from_string(text);
+    doc.remove("version").unwrap();
+    doc.remove("log.retention_days").unwrap();
+    assert!(doc
+        .to_string()
+        .starts_with("# Logger.\ntitle = \"Logger\"\n\n# Log.\n[log]\nlevel = \"warn\"\nrotation"));
+    assert_eq!(doc.get::<Option<i64>>("log.retention_days"), Ok(None));
+
+    // Items in a value.
+    // 値の中の項目。
+    doc.remove("log.ports[1]").unwrap();
+    doc.remove("log.rotation.size").unwrap();
+    assert!(doc
+        .to_string()
+        .contains("\nrotation = { daily = true }\nports = [ 8000, 8002 ]\n"));
+    doc.remove("log.ports[1]").unwrap();
+    doc.remove("log.ports[0]").unwrap();
+    assert!(doc.to_string().contains("\nports = []\n"));
+
+    // Comments after the comma go with the item.
+    // カンマの後のコメントは項目と一緒です。
+    let mut doc = Toml::from_string("a = [\n  1, # One.\n  2, # Two.\n  3,\n]\n");
+    doc.remove("a[0]").unwrap();
+    assert_eq!(doc.to_string(), "a = [\n  2, # Two.\n  3,\n]\n");
+    // The last item leaves the comment after the comma before it.
+    // 最後の項目は、その前のカンマの後のコメントを残します。
+    doc.remove("a[1]").unwrap();
+    assert_eq!(doc.to_string(), "a = [\n  2, # Two.\n]\n");
+    let mut doc = Toml::from_string("ports = [\n  80, # a\n  443 # b\n]\n");
+    doc.remove("ports[1]").unwrap();
+    assert_eq!(doc.to_string(), "ports = [\n  80, # a\n]\n");
+    let mut doc = Toml::from_string("a = [\n  1,\n  2\n]\n");
+    doc.remove("a[1]").unwrap();
+    assert_eq!(doc.to_string(), "a = [\n  1\n]\n");
+    let mut doc = Toml::from_string("a = [\n  1,\n  2,\n]\n");
+    doc.remove("a[1]").unwrap();
+    assert_eq!(doc.to_string(), "a = [\n  1,\n]\n");
+
+    // A table with its sub-tables. The comment of the next table stays.
+    // サブ・テーブルを含むテーブル。次のテーブルのコメントは残ります。
+    let mut doc = Toml::from_string(text);
+    doc.remove("log").unwrap();
+    assert!(doc.to_string().starts_with(
+        "# Logger.
+title = \"Logger\"
+version = 1 # One.
+
+# Log.
+
+# Database.
+[database]
+ip = \"10.0.0.1\"
+
+[[products]]"
+    ));
+    // With the comments right before it.
+    // 直前のコメントも。
+    let mut doc = Toml::from_string(text);
+    doc.remove_with_comments("log").unwrap();
+    assert!(doc.to_string().starts_with(
+        "# Logger.
+title = \"Logger\"
+version = 1 # One.
+
+# Database.
+[database]
+ip = \"10.0.0.1\"
+
+[[products]]"
+    ));
+    doc.remove_with_comments("database").unwrap();
+    assert!(doc
+        .to_string()
+        .starts_with("# Logger.\ntitle = \"Logger\"\nversion = 1 # One.\n\n[[products]]"));
+    doc.remove_with_comments("title").unwrap();
+    assert!(doc
+        .to_string()
+        .starts_with("version = 1 # One.\n\n[[products]]"));
+
+    // One blank line stays between the lines around it.
+    // 前後の行の間には空行が１つ残ります。
+    let mut doc = Toml::from_string(
+        "# top\n[log]\nlevel = \"warn\"\n\n# servers\n[servers.alpha]\nip = \"10.0.0.1\"\n",
+    );
+    doc.remove("log").unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "# top\n\n# servers\n[servers.alpha]\nip = \"10.0.0.1\"\n"
+    );
+
+    // An element of an array of tables, with its sub-tables.
+    // テーブルの配列の要素。そのサブ・テーブルも。
+    let mut doc = Toml::from_string(text);
+    doc.remove("products[1]").unwrap();
+    assert!(doc.to_string().ends_with(
+        "[[products]]
+name = \"Hammer\"
+
+[[products]]
+name = \"Saw\"
+"
+    ));
+    assert_eq!(doc["products"][1]["name"].as_str(), Some("Saw"));
+    doc.remove("products[1].name").unwrap();
+    assert!(doc
+        .to_string()
+        .ends_with("name = \"Hammer\"\n\n[[products]]\n"));
+    // The whole array. No blank line is left at the end.
+    // 配列全体。末尾に空行は残りません。
+    doc.remove("products").unwrap();
+    assert!(doc
+        .to_string()
+        .ends_with("[log.file]\npath = \"./app.log\"\n"));
+
+    // Nothing there.
+    // 何も無い。
+    assert_eq!(
+        doc.remove("log.retention"),
+        Err("log.retention is not found.".to_string())
+    );
+    assert_eq!(
+        doc.remove("products[0]"),
+        Err("products[0] is not found.".to_string())
+    );
+}
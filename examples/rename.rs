@@ -0,0 +1,156 @@
+//! Test renaming keys and moving tables.
+//! キーの名前変更と、テーブルの移動のテスト。
+//!
+//! `cargo run --example rename`
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::Toml;
+
+fn main() {
+    let text = "title = \"Logger\"
+site.name = \"alpha\"
+
+[server]
+timeout_secs  =  30 # Seconds.
+limits = { cpu = 2, mem.max = 512, mem.min = 64 }
+retry.count = 3
+
+# Log.
+[log]
+level = \"warn\"
+
+[log.file]
+path = \"./app.log\"
+
+[[products]]
+name = \"Hammer\"
+[products.spec]
+size = 3
+";
+
+    // A key. The value and the comment stay.
+    // キー。値とコメントはそのままです。
+    let mut doc = Toml::from_string(text);
+    doc.rename("server.timeout_secs", "timeout").unwrap();
+    assert!(doc
+        .to_string()
+        .contains("\n[server]\ntimeout  =  30 # Seconds.\n"));
+    assert_eq!(doc.get::<i64>("server.timeout"), Ok(30));
+    // Quoted if needed.
+    // 必要なら引用符で囲みます。
+    doc.rename("title", "site title").unwrap();
+    assert!(doc.to_string().starts_with("\"site title\" = \"Logger\"\n"));
+    // Written as TOML, not as Rust.
+    // Rust ではなく TOML として書きます。
+    doc.rename("server.timeout", "cafe\u{301}").unwrap();
+    assert!(doc
+        .to_string()
+        .contains("\n\"cafe\u{301}\"  =  30 # Seconds.\n"));
+    assert_eq!(doc.get::<i64>("server.\"cafe\u{301}\""), Ok(30));
+    doc.rename("server.\"cafe\u{301}\"", "timeout").unwrap();
+    // A part of dotted keys.
+    // ドット付きキーの一部。
+    doc.rename("site", "web").unwrap();
+    assert!(doc.to_string().contains("\nweb.name = \"alpha\"\n"));
+    // Keys in an inline table.
+    // インライン・テーブルの中のキー。
+    doc.rename("server.limits.mem", "memory").unwrap();
+    doc.rename("server.limits.cpu", "cores").unwrap();
+    assert!(doc
+        .to_string()
+        .contains("\nlimits = { cores = 2, memory.max = 512, memory.min = 64 }\n"));
+    // A table with its sub-tables. The comment stays.
+    // サブ・テーブルを含むテーブル。コメントはそのままです。
+    doc.rename("log", "logging").unwrap();
+    assert!(doc.to_string().contains(
+        "# Log.
+[logging]
+level = \"warn\"
+
+[logging.file]
+path = \"./app.log\"
+"
+    ));
+    // In an element of an array of tables.
+    // テーブルの配列の要素の中。
+    doc.rename("products[0].spec", "size chart").unwrap();
+    doc.rename("products[0].name", "title").unwrap();
+    assert!(doc.to_string().ends_with(
+        "[[products]]
+title = \"Hammer\"
+[products.\"size chart\"]
+size = 3
+"
+    ));
+    assert_eq!(
+        doc["products"][0]["size chart"]["size"].as_integer(),
+        Some(3)
+    );
+
+    // Move a table in place. Its sub-tables move too.
+    // テーブルをその場で移します。サブ・テーブルも移ります。
+    let mut doc = Toml::from_string(text);
+    doc.move_table("log", "logging.output").unwrap();
+    assert!(doc.to_string().contains(
+        "# Log.
+[logging.output]
+level = \"warn\"
+
+[logging.output.file]
+path = \"./app.log\"
+"
+    ));
+    assert_eq!(
+        doc.get::<String>("logging.output.file.path"),
+        Ok("./app.log".to_string())
+    );
+    // Dotted keys move in their table.
+    // ドット付きキーは、そのテーブルの中で移ります。
+    doc.move_table("site", "web.site").unwrap();
+    assert!(doc.to_string().contains("\nweb.site.name = \"alpha\"\n"));
+    doc.move_table("products", "shop.products").unwrap();
+    assert!(doc
+        .to_string()
+        .ends_with("[[shop.products]]\nname = \"Hammer\"\n[shop.products.spec]\nsize = 3\n"));
+
+    // Errors.
+    // エラー。
+    let mut doc = Toml::from_string(text);
+    assert_eq!(
+        doc.rename("server.timeout_secs", "limits"),
+        Err("server.limits is defined already.".to_string())
+    );
+    assert_eq!(
+        doc.rename("server.timeout", "x"),
+        Err("server.timeout is not found.".to_string())
+    );
+    assert_eq!(
+        doc.rename("products[0]", "x"),
+        Err("`products[0]` ends with an index.".to_string())
+    );
+    assert_eq!(
+        doc.move_table("server.retry", "retry"),
+        Err(
+            "server.retry.count is a dotted key in [server]. It can not move out of the table."
+                .to_string()
+        )
+    );
+    assert_eq!(
+        doc.move_table("server.limits", "limits"),
+        Err("server.limits is an inline table. Try `doc.set()`.".to_string())
+    );
+    assert_eq!(
+        doc.move_table("title", "name"),
+        Err("title is not a table.".to_string())
+    );
+    assert_eq!(
+        doc.move_table("log", "server"),
+        Err("server is defined already.".to_string())
+    );
+    assert_eq!(
+        doc.move_table("log", "title.log"),
+        Err("title is not a table.".to_string())
+    );
+    assert_eq!(doc.to_string(), text);
+}
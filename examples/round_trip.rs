@@ -0,0 +1,63 @@
+//! Test. Display writes the source text as is.
+//! テスト。 Display はソース・テキストをそのまま書きます。
+//!
+//! `cargo run --example round_trip`
+
+extern crate tomboy_toml_dom;
+
+use std::fs;
+use tomboy_toml_dom::Toml;
+
+fn main() {
+    // Every file in the resource directory. The official specification too.
+    // リソース・ディレクトリーの全てのファイル。公式の仕様書も。
+    let mut paths: Vec<_> = fs::read_dir("./resource")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    for path in paths {
+        let text = fs::read_to_string(&path).unwrap();
+        let doc = Toml::from_string(&text);
+        assert_eq!(doc.to_string(), text, "{}", path.display());
+    }
+
+    // Line endings, the end of file and whitespace.
+    // 改行、ファイルの終わり、空白。
+    for text in &[
+        "",
+        "   ",
+        "a = 1",
+        "a=1\r\nb =  2\r\n",
+        "  [x]  # Comment.\r\n  y = 1\n\n\t\n",
+        "[[ a . b ]]#Comment.\n[[a.b]]",
+        "s = \"\"\"\r\nx\r\n\"\"\"\r\n",
+        "array = [\r\n  1, # One.\r\n  2,\r\n]\r\n",
+        "t = {  a = 1 ,b={} }\n",
+        "odt = 1979-05-27 07:32:00Z # Space instead of `T`.\n",
+        "ld = 1979-05-27",
+    ] {
+        assert_eq!(&Toml::from_string(text).to_string(), text);
+    }
+
+    // Broken date-times are rejected. The line after them stays.
+    // 壊れた日時は拒否します。その次の行は残ります。
+    for text in &[
+        "a = 1979-05-27T\nb = 1\n",
+        "a = 12:\nb = 1\n",
+        "a = 07:32\nb = 1\n",
+        "a = 1979-13-45\nb = 1\n",
+        "a = 2001-02-29\nb = 1\n",
+        "a = 1979-05-27T24:00:00\nb = 1\n",
+        "a = 1979-05-27T07:32:00+25:00\nb = 1\n",
+    ] {
+        assert_eq!(Toml::from_string(text).to_string(), "b = 1\n", "{:?}", text);
+    }
+    for text in &[
+        "a = 2000-02-29\n",
+        "a = 23:59:60.5\n",
+        "a = 1979-05-27T07:32:00.999999-07:00\n",
+    ] {
+        assert_eq!(&Toml::from_string(text).to_string(), text);
+    }
+}
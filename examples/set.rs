@@ -0,0 +1,138 @@
+//! Test setting values.
+//! 値の設定のテスト。
+//!
+//! `cargo run --example set`
+
+extern crate tomboy_toml_dom;
+
+use std::convert::TryFrom;
+use tomboy_toml_dom::{
+    model::layer310::{Table, Value},
+    Toml,
+};
+
+fn main() {
+    let mut doc = Toml::from_string(
+        "title = 'Logger' # Name.
+[log]
+retention_days   =  7 # days
+mask = 0xFF
+big = 1_000_000
+note = '''
+Keep it.'''
+path = \"C:\\\\logs\"
+rate = 0.5
+ports = [ 8000, 8001 ] # Two.
+rotation = { daily = true, size = 10 }
+servers = [ { ip = \"10.0.0.1\" } ]
+",
+    );
+
+    // Only the value changes.
+    // 値だけが変わります。
+    doc.set("log.retention_days", 14).unwrap();
+    assert!(doc
+        .to_string()
+        .contains("\nretention_days   =  14 # days\n"));
+    assert_eq!(doc.get::<i64>("log.retention_days"), Ok(14));
+    assert_eq!(doc["log"]["retention_days"].as_integer(), Some(14));
+
+    // The style of the old value.
+    // 古い値の書き方。
+    doc.set("title", "Tom's logger").unwrap();
+    doc.set("log.mask", 255 + 256).unwrap();
+    doc.set("log.big", -2_500_000).unwrap();
+    doc.set("log.note", "Keep it.\nAnd this.").unwrap();
+    doc.set("log.path", "D:\\logs").unwrap();
+    doc.set("log.rate", 2.0).unwrap();
+    doc.set("log.ports[1]", 8081).unwrap();
+    doc.set("log.rotation.size", 20).unwrap();
+    doc.set("log.servers[0].ip", "10.0.0.2").unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "title = \"Tom's logger\" # Name.
+[log]
+retention_days   =  14 # days
+mask = 0x1FF
+big = -2_500_000
+note = '''
+Keep it.
+And this.'''
+path = \"D:\\\\logs\"
+rate = 2.0
+ports = [ 8000, 8081 ] # Two.
+rotation = { daily = true, size = 20 }
+servers = [ { ip = \"10.0.0.2\" } ]
+"
+    );
+
+    // Any value. Tables are inline.
+    // どんな値でも。テーブルはインラインです。
+    doc.set("log.ports", vec![1, 2, 3]).unwrap();
+    doc.set("log.rate", "fast").unwrap();
+    let mut table = Table::default();
+    table.insert("daily", Value::from(false));
+    table.insert("max size", Value::from(vec!["a", "b"]));
+    doc.set("log.rotation", table).unwrap();
+    assert!(doc.to_string().contains("\nports = [1, 2, 3] # Two.\n"));
+    assert!(doc.to_string().contains("\nrate = \"fast\"\n"));
+    assert!(doc
+        .to_string()
+        .contains("\nrotation = { daily = false, \"max size\" = [\"a\", \"b\"] }\n"));
+    assert_eq!(doc.get::<Vec<i32>>("log.ports"), Ok(vec![1, 2, 3]));
+    assert_eq!(doc["log"]["rotation"]["max size"][1].as_str(), Some("b"));
+
+    // Nothing is added.
+    // 何も追加しません。
+    assert_eq!(
+        doc.set("log.retention", 1),
+        Err("log.retention is not found.".to_string())
+    );
+    assert_eq!(
+        doc.set("log.ports[9]", 1),
+        Err("log.ports[9] is not found.".to_string())
+    );
+    assert_eq!(
+        doc.set("log.ports", vec![Value::Absent]),
+        Err("log.ports[0] has an absent value.".to_string())
+    );
+    assert_eq!(
+        Value::try_from(u64::MAX),
+        Err(
+            "is 18446744073709551615, out of the range of a TOML integer (64-bit signed)."
+                .to_string()
+        )
+    );
+    assert_eq!(Value::try_from(7_u64), Ok(Value::Integer(7)));
+    assert_eq!(doc.get::<Vec<i32>>("log.ports"), Ok(vec![1, 2, 3]));
+
+    // In an array of tables, same as `remove` and `rename`.
+    // `remove` や `rename` と同じく、テーブルの配列の中。
+    let mut doc = Toml::from_string(
+        "[[products]]\nname = \"A\"\n\n[[products]]\nname = \"B\" # Second.\nsizes = [1, 2]\n",
+    );
+    doc.set("products[1].name", "C").unwrap();
+    doc.set("products[1].sizes[0]", 3).unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "[[products]]\nname = \"A\"\n\n[[products]]\nname = \"C\" # Second.\nsizes = [3, 2]\n"
+    );
+    assert_eq!(doc.get("products[1].name"), Ok("C".to_string()));
+    assert_eq!(
+        doc.set("products[2].name", "D"),
+        Err("products[2].name is not found.".to_string())
+    );
+
+    // Both ends of TOML integers. `f32` is written as it reads.
+    // TOML の整数の両端。 `f32` は読める通りに書きます。
+    let mut doc = Toml::from_string("a = 0\nb = 0\nc = 0.5\n");
+    doc.set("a", i64::MAX).unwrap();
+    doc.set("b", i64::MIN).unwrap();
+    doc.set("c", 0.1_f32).unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "a = 9223372036854775807\nb = -9223372036854775808\nc = 0.1\n"
+    );
+    assert_eq!(Value::from(1.5_f32), Value::Float(1.5));
+    assert_eq!(Value::from(0.1_f32), Value::Float(0.1));
+}
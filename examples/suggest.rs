@@ -0,0 +1,61 @@
+//! Test "did you mean" suggestions.
+//! 「もしかして」の候補のテスト。
+//!
+//! `cargo run --example suggest`
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::{model::layer225::GetError, Toml};
+
+fn main() {
+    // Read a Toml file.
+    let doc = Toml::from_file("./resource/suggest.toml");
+
+    let suggest = |path: &str| {
+        doc.suggest_keys(path)
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<String>>()
+    };
+
+    // A typo in the same table.
+    // 同じテーブルの中のタイプミス。
+    assert_eq!(
+        suggest("log.retention_day"),
+        vec!["log.retention_days", "log.retention_size"]
+    );
+    assert_eq!(suggest("titel"), vec!["title"]);
+    assert_eq!(suggest("levle"), vec!["level"]);
+    // Tables and arrays of tables are keys too.
+    // テーブルとテーブルの配列もキーです。
+    assert_eq!(suggest("logs"), vec!["log"]);
+    assert_eq!(suggest("sink"), vec!["sinks"]);
+    assert_eq!(suggest("log.fille.path"), Vec::<String>::new());
+    assert_eq!(suggest("log.fil"), vec!["log.file"]);
+    assert_eq!(suggest("log.file.pth"), vec!["log.file.path"]);
+    // In an array of tables, with the index.
+    // テーブルの配列の中。添え字付き。
+    assert_eq!(suggest("sinks[0].nam"), vec!["sinks[0].name"]);
+    assert_eq!(suggest("sinks[1].nam"), Vec::<String>::new());
+    // Inline tables too.
+    // インライン・テーブルも。
+    assert_eq!(suggest("log.rotation.dayly"), vec!["log.rotation.daily"]);
+    // Nothing near.
+    // 近いものはありません。
+    assert_eq!(suggest("database"), Vec::<String>::new());
+    assert_eq!(suggest("log..x"), Vec::<String>::new());
+
+    // With the typed get.
+    // 型付きゲットと一緒に。
+    match doc.get::<u32>("log.retention_day") {
+        Err(GetError::NotFound { path }) => {
+            assert_eq!(path, "log.retention_day");
+            assert_eq!(
+                doc.suggest_keys(&path).first().map(|m| m.to_string()),
+                Some("log.retention_days".to_string())
+            );
+        }
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(doc.get::<u32>("log.retention_days"), Ok(7));
+}
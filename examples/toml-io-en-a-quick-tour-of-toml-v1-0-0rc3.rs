@@ -51,12 +51,7 @@ fn main() {
 
     assert_eq!(
         doc.get_string_by_key("str4"),
-        Some(
-            "
-Roses are red
-Violets are blue"
-                .to_string()
-        )
+        Some("Roses are red\nViolets are blue".to_string())
     );
     assert_eq!(
         doc.get_string_by_key("str5"),
@@ -0,0 +1,197 @@
+//! Test owned values.
+//! 所有する値のテスト。
+//!
+//! `cargo run --example value`
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::{model::layer310::Value, Toml};
+
+fn main() {
+    // Read a Toml file.
+    let doc = Toml::from_file("./resource/cover.toml");
+    let value = doc.to_value().unwrap();
+    let root = value.as_table().unwrap();
+
+    // Tables keep the written order.
+    // テーブルは書かれた順を保ちます。
+    assert_eq!(
+        root.keys().map(|m| m.as_str()).collect::<Vec<&str>>(),
+        vec![
+            "apple",
+            "int_array",
+            "float_array",
+            "string_array",
+            "mix_array",
+            "mix_type_array",
+            "points",
+            "bool_array",
+            "date_array",
+            "hex_array",
+            "nested_int_array",
+            "banana",
+            "cherry",
+            "pinapple"
+        ]
+    );
+    assert_eq!(root.get("apple"), Some(&Value::String("pie".to_string())));
+    assert_eq!(
+        root.get("hex_array"),
+        Some(&Value::Array(vec![
+            Value::Integer(255),
+            Value::Integer(1000)
+        ]))
+    );
+    assert_eq!(
+        root.get("mix_type_array"),
+        Some(&Value::Array(vec![
+            Value::Integer(1),
+            Value::Float(2.5),
+            Value::Boolean(true),
+            Value::String("a".to_string()),
+            Value::Datetime("1979-05-27".to_string())
+        ]))
+    );
+
+    // `[[cherry]]` is an array of tables.
+    // `[[cherry]]` はテーブルの配列です。
+    let cherry = root.get("cherry").unwrap().as_array().unwrap();
+    assert_eq!(cherry.len(), 3);
+    assert_eq!(
+        cherry[1].as_table().unwrap().get("color"),
+        Some(&Value::String("green".to_string()))
+    );
+
+    // `[pinapple.banana.cherry]` is nested.
+    // `[pinapple.banana.cherry]` は入れ子です。
+    let cherry = root
+        .get("pinapple")
+        .and_then(|m| m.as_table().unwrap().get("banana"))
+        .and_then(|m| m.as_table().unwrap().get("cherry"))
+        .unwrap()
+        .as_table()
+        .unwrap();
+    assert_eq!(cherry.get("drink"), Some(&Value::Integer(3)));
+    let grape = cherry.get("grape").unwrap().as_array().unwrap();
+    assert_eq!(
+        grape
+            .iter()
+            .map(|m| m.as_table().unwrap().get("drink").unwrap().as_integer())
+            .collect::<Vec<Option<i64>>>(),
+        vec![Some(4), Some(5), Some(6)]
+    );
+
+    // Dotted keys make tables.
+    // ドット付きキーはテーブルを作ります。
+    let value = Toml::from_string(
+        "a = { type.name = \"pug\" }
+[x]
+y.z = 1
+[x.y.v]
+w = 2
+",
+    )
+    .to_value()
+    .unwrap();
+    let root = value.as_table().unwrap();
+    assert_eq!(
+        format!("{:?}", root.get("a").unwrap()),
+        "Table(Table { items: [(\"type\", Table(Table { items: [(\"name\", String(\"pug\"))] }))] })"
+    );
+    assert_eq!(
+        format!("{:?}", root.get("x").unwrap()),
+        "Table(Table { items: [(\"y\", Table(Table { items: [(\"z\", Integer(1)), (\"v\", Table(Table { items: [(\"w\", Integer(2))] }))] }))] })"
+    );
+
+    // Every resource that is valid TOML.
+    // 正しい TOML のリソースすべて。
+    for file in &[
+        "./resource/comment.toml",
+        "./resource/edit-1.toml",
+        "./resource/edit-1.type.toml",
+        "./resource/from-toml.toml",
+        "./resource/inline-table.toml",
+        "./resource/mix-array.toml",
+        "./resource/spot.toml",
+        "./resource/table.toml",
+        "./resource/toml-io-en-a-quick-tour-of-toml-v1-0-0rc3.toml",
+    ] {
+        assert!(Toml::from_file(file).to_value().is_ok(), "{}", file);
+    }
+
+    // Errors.
+    // エラー。
+    assert_eq!(
+        Toml::from_string("a = 1\na = 2\n").to_value(),
+        Err("a is defined twice.".to_string())
+    );
+    assert_eq!(
+        Toml::from_string("[t]\n\"\" = 1\n'' = 2\n").to_value(),
+        Err("t.\"\" is defined twice.".to_string())
+    );
+    assert_eq!(
+        Toml::from_string("a = 1\n[a.b]\n").to_value(),
+        Err("a is not a table.".to_string())
+    );
+    for (text, why) in [
+        ("a = [{ b = 1 }]\n[[a]]\n", "a is not an array of tables."),
+        ("a = [{ b = 1 }]\n[a.c]\n", "a is not a table."),
+        ("a = [{ b = 1 }]\na.c = 1\n", "a is not a table."),
+        ("[t]\n[t]\n", "t is defined twice."),
+        ("[t]\n[t.u]\n[t]\n", "t is defined twice."),
+        ("[t]\n[[t]]\n", "t is defined twice."),
+        ("[[t]]\n[t]\n", "t is defined twice."),
+        ("a.b = 1\n[a]\n", "a is defined twice."),
+        ("a.b.c = 1\n[a.b]\n", "a.b is defined twice."),
+        ("[a.b]\n[a]\nb.d = 2\n", "a.b is defined twice."),
+        ("[a.b.c]\n[a]\nb.d = 2\n", "a.b is defined twice."),
+    ] {
+        assert_eq!(
+            Toml::from_string(text).to_value(),
+            Err(why.to_string()),
+            "{:?}",
+            text
+        );
+    }
+    // Allowed.
+    // 許されます。
+    for text in [
+        "[a.b]\n[a]\n",
+        "a.b.x = 1\n[a.b.c]\n",
+        "[fruit]\napple.color = 1\napple.taste = 2\n[fruit.apple.texture]\n",
+        "[[a]]\n[a.b]\n[[a]]\n[a.b]\n",
+        "[[a]]\nb = { c = 1 }\n[[a]]\nb.c = 1\n",
+        "[[a.b]]\n[a]\n",
+    ] {
+        assert!(Toml::from_string(text).to_value().is_ok(), "{:?}", text);
+    }
+    // Table keys are written as TOML.
+    // テーブルのキーは TOML として書きます。
+    let value = Toml::from_string("t = { \"a\\u007F\" = 1, \"cafe\u{301}\" = 2 }\n")
+        .to_value()
+        .unwrap();
+    assert_eq!(
+        value["t"].to_toml_string(),
+        Ok("{ \"a\\u007F\" = 1, \"cafe\u{301}\" = 2 }".to_string())
+    );
+    // TOML integers are 64-bit signed.
+    // TOML の整数は 64 ビット符号付きです。
+    assert_eq!(
+        Toml::from_string("big = 9223372036854775808\n").to_value(),
+        Err(
+            "big is 9223372036854775808, out of the range of a TOML integer (64-bit signed)."
+                .to_string()
+        )
+    );
+    assert_eq!(
+        Toml::from_string("small = -9223372036854775808\n")["small"],
+        Value::Integer(i64::MIN)
+    );
+    assert_eq!(
+        Toml::from_file("./resource/example.toml").to_value(),
+        Err(
+            "u64_max is 18_446_744_073_709_551_615, out of the range of a TOML integer (64-bit signed)."
+                .to_string()
+        )
+    );
+}
@@ -0,0 +1,195 @@
+//! Test visitors.
+//! ビジターのテスト。
+//!
+//! `cargo run --example visit`
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::{
+    model::{
+        layer110::{Token, TokenType},
+        layer210::{BasicString, Comment, Key, LiteralString, LiteralValue, Ws},
+        layer220::Array,
+        layer225::Keyval,
+        layer310::{visit, Visit, VisitMut},
+    },
+    Toml,
+};
+
+/// A linter. Keys, comments and the deepest array.
+/// リンター。キー、コメント、一番深い配列。
+#[derive(Default)]
+struct Lint {
+    keys: Vec<String>,
+    comments: Vec<String>,
+    depth: usize,
+    max_depth: usize,
+}
+impl Visit for Lint {
+    fn visit_keyval(&mut self, m: &Keyval) {
+        self.keys.push(m.key.to_string());
+        visit::walk_keyval(self, m);
+    }
+    fn visit_comment(&mut self, m: &Comment) {
+        self.comments.push(m.to_string());
+    }
+    fn visit_array(&mut self, m: &Array) {
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+        visit::walk_array(self, m);
+        self.depth -= 1;
+    }
+}
+
+/// A transform. One space around `=`, and every integer doubled.
+/// 変換。 `=` の前後を空白１つにし、全ての整数を２倍にします。
+struct Format;
+impl VisitMut for Format {
+    fn visit_keyval_mut(&mut self, m: &mut Keyval) {
+        m.ws1 = space();
+        m.ws2 = space();
+        visit::walk_keyval_mut(self, m);
+    }
+    fn visit_literal_value_mut(&mut self, m: &mut LiteralValue) {
+        if let Ok(n) = m.to_int::<i64>() {
+            m.tokens = vec![Token::new(&(2 * n).to_string(), TokenType::LiteralValue)];
+        }
+    }
+}
+/// Renames `old` to `new`, and `[t]` to `["t u"]`. The text and the names stay in sync.
+/// `old` を `new` に、 `[t]` を `["t u"]` に名前変更します。テキストと名前は揃ったままです。
+struct Rename;
+impl VisitMut for Rename {
+    fn visit_key_mut(&mut self, m: &mut Key) {
+        match m.to_string().as_str() {
+            "old" => m.set_segments(&["new".to_string()]),
+            "t" => m.set_segments(&["t u".to_string()]),
+            _ => {}
+        }
+    }
+}
+
+/// Changes every string. The source text is escaped again.
+/// 全ての文字列を変えます。ソース・テキストはエスケープし直します。
+struct Shout;
+impl VisitMut for Shout {
+    fn visit_basic_string_mut(&mut self, m: &mut BasicString) {
+        m.set_value(&format!("{}!\t\"", m.value()));
+    }
+    fn visit_literal_string_mut(&mut self, m: &mut LiteralString) {
+        m.set_value(&format!("{}!", m.value())).unwrap();
+    }
+}
+
+/// Comments in capitals.
+struct Upper;
+impl VisitMut for Upper {
+    fn visit_comment_mut(&mut self, m: &mut Comment) {
+        let text = m.to_string().to_uppercase();
+        m.tokens = vec![Token::new(&text, TokenType::Comment)];
+    }
+}
+
+fn space() -> Ws {
+    let mut m = Ws::default();
+    m.push_token(&Token::new(" ", TokenType::Wschar));
+    m
+}
+
+fn main() {
+    let doc = Toml::from_string(
+        "# Head.
+a = 1 # One.
+b = [ 1, [ 2, # Two.
+  [3] ] ]
+[t] # Table.
+c = { d = 4, e.f = 'x' }
+",
+    );
+
+    // Read.
+    // 読み取り。
+    let mut lint = Lint::default();
+    lint.visit_document(&doc);
+    assert_eq!(lint.keys, vec!["a", "b", "c", "d", "e.f"]);
+    assert_eq!(
+        lint.comments,
+        vec!["# Head.", "# One.", "# Two.", "# Table."]
+    );
+    assert_eq!(lint.max_depth, 3);
+
+    // Edit.
+    // 編集。
+    let mut doc = Toml::from_string("a=1\nb  =  [1, { c=2 }]\n");
+    assert_eq!(doc["a"].as_integer(), Some(1));
+    Format.visit_document_mut(&mut doc);
+    assert_eq!(doc.to_string(), "a = 2\nb = [2, { c = 4 }]\n");
+    assert_eq!(doc["a"].as_integer(), Some(2));
+    assert_eq!(doc["b"][1]["c"].as_integer(), Some(4));
+
+    // Keys. `doc["new"]` reads the new name.
+    // キー。 `doc["new"]` は新しい名前を読みます。
+    let mut doc = Toml::from_string("old = \"x\"\n[ t ] # T.\nold = 1\n");
+    Rename.visit_document_mut(&mut doc);
+    assert_eq!(doc.to_string(), "new = \"x\"\n[ \"t u\" ] # T.\nnew = 1\n");
+    assert_eq!(doc["new"].as_str(), Some("x"));
+    assert!(doc["old"].is_absent());
+    assert_eq!(doc["t u"]["new"].as_integer(), Some(1));
+    assert_eq!(doc.get::<i64>("\"t u\".new"), Ok(1));
+    assert_eq!(
+        Toml::from_string(&doc.to_string()).to_string(),
+        doc.to_string()
+    );
+
+    // Strings. The text and `doc["a"]` agree.
+    // 文字列。テキストと `doc["a"]` は一致します。
+    let mut doc = Toml::from_string("a = \"x\"\nb = '''\ny'''\nc = \"\"\"\nz\"\"\"\n");
+    Shout.visit_document_mut(&mut doc);
+    assert_eq!(
+        doc.to_string(),
+        "a = \"x!\\t\\\"\"\nb = '''\ny!'''\nc = \"\"\"\nz!\\t\\\"\"\"\"\n"
+    );
+    assert_eq!(doc["a"].as_str(), Some("x!\t\""));
+    assert_eq!(doc["b"].as_str(), Some("y!"));
+    assert_eq!(doc["c"].as_str(), Some("z!\t\""));
+    let again = Toml::from_string(&doc.to_string());
+    assert_eq!(again["a"].as_str(), Some("x!\t\""));
+    assert_eq!(again["c"].as_str(), Some("z!\t\""));
+
+    // A literal string can not hold `'`.
+    // リテラル文字列は `'` を持てません。
+    let mut m = LiteralString::default();
+    assert_eq!(
+        m.set_value("it's"),
+        Err("\"it's\" can't be written in a literal string.".to_string())
+    );
+    assert_eq!(m.value(), "");
+
+    // Comments in arrays are reached too.
+    // 配列の中のコメントにも届きます。
+    let mut doc = Toml::from_string("# Head.\nb = [ 1, # One.\n  2 ] # Tail.\n");
+    Upper.visit_document_mut(&mut doc);
+    assert_eq!(doc.to_string(), "# HEAD.\nb = [ 1, # ONE.\n  2 ] # TAIL.\n");
+    let mut lint = Lint::default();
+    lint.visit_document(&doc);
+    assert_eq!(lint.comments, vec!["# HEAD.", "# ONE.", "# TAIL."]);
+
+    // From below the document. `elements_mut()` drops the caches.
+    // ドキュメントより下から。 `elements_mut()` はキャッシュを捨てます。
+    let mut doc = Toml::from_string("a=1\nb=2\n");
+    assert_eq!(doc["b"].as_integer(), Some(2));
+    assert_eq!(doc.get::<i64>("b"), Ok(2));
+    Format.visit_expression_mut(&mut doc.elements_mut()[1]);
+    assert_eq!(doc.to_string(), "a=1\nb = 4\n");
+    assert_eq!(doc["b"].as_integer(), Some(4));
+    assert_eq!(doc.get::<i64>("b"), Ok(4));
+
+    // Every resource, unchanged by a visitor that does nothing.
+    // 何もしないビジターでは、全てのリソースは変わりません。
+    struct Nothing;
+    impl VisitMut for Nothing {}
+    let mut doc = Toml::from_file("./resource/cover.toml");
+    let text = doc.to_string();
+    Nothing.visit_document_mut(&mut doc);
+    assert_eq!(doc.to_string(), text);
+}
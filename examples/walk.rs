@@ -0,0 +1,99 @@
+//! Test keys, tables, entries and walk.
+//! キー、テーブル、エントリー、ウォークのテスト。
+//!
+//! `cargo run --example walk`
+
+extern crate tomboy_toml_dom;
+
+use tomboy_toml_dom::Toml;
+
+fn main() {
+    // Read a Toml file.
+    let doc = Toml::from_file("./resource/query.toml");
+
+    // Every configurable key.
+    // 設定できる全てのキー。
+    assert_eq!(
+        doc.keys().map(|m| m.to_string()).collect::<Vec<String>>(),
+        vec![
+            "timeout_secs",
+            "servers.alpha.ip",
+            "servers.alpha.timeout_secs",
+            "servers.beta.ip",
+            "database.connection.timeout_secs",
+            "products[0].name",
+            "products[0].sku",
+            "products[0].price",
+            "products[1].name",
+            "products[1].sku",
+            "products[1].price",
+            "products[2].name",
+            "products[2].sku",
+            "products[2].price",
+        ]
+    );
+    assert_eq!(
+        doc.entries()
+            .filter(|(path, _)| path.to_string().starts_with("servers."))
+            .map(|(path, value)| format!("{} = {:?}", path, value.as_str()))
+            .collect::<Vec<String>>(),
+        vec![
+            "servers.alpha.ip = Some(\"10.0.0.1\")",
+            "servers.alpha.timeout_secs = None",
+            "servers.beta.ip = Some(\"10.0.0.2\")",
+        ]
+    );
+
+    // Tables. Inline tables and arrays of tables too.
+    // テーブル。インライン・テーブルとテーブルの配列も。
+    assert_eq!(
+        doc.tables()
+            .map(|(path, table)| format!("{} {}", path, table.len()))
+            .collect::<Vec<String>>(),
+        vec![
+            "servers 2",
+            "servers.alpha 2",
+            "servers.beta 1",
+            "database 1",
+            "database.connection 1",
+            "products[0] 3",
+            "products[1] 3",
+            "products[2] 3",
+        ]
+    );
+
+    // Walk. Parent first, array items too.
+    // ウォーク。親が先で、配列の項目も。
+    let doc = Toml::from_string("a = [1, [2]]\n[b]\nc = { d = true }\n");
+    assert_eq!(
+        doc.walk()
+            .iter()
+            .map(|(path, value)| format!("{} {}", path, value.type_name()))
+            .collect::<Vec<String>>(),
+        vec![
+            "a array",
+            "a[0] integer",
+            "a[1] array",
+            "a[1][0] integer",
+            "b table",
+            "b.c table",
+            "b.c.d boolean",
+        ]
+    );
+    assert_eq!(
+        doc.keys().map(|m| m.to_string()).collect::<Vec<String>>(),
+        vec!["a", "b.c.d"]
+    );
+
+    // Quoted keys stay quoted.
+    // 引用符付きのキーは引用符付きのまま。
+    let doc = Toml::from_string("site.\"google.com\" = 1\n");
+    assert_eq!(
+        doc.keys().map(|m| m.to_string()).collect::<Vec<String>>(),
+        vec!["site.\"google.com\""]
+    );
+
+    // Nothing for a broken document.
+    // 壊れたドキュメントでは何もありません。
+    assert_eq!(Toml::from_string("a = 1\na = 2\n").walk().len(), 0);
+}
@@ -32,8 +32,7 @@ use crate::model::layer310::TomlDocument;
 use crate::parser::phase200::{layer210::PResult, layer310::DocumentP};
 use casual_logger::{ArrayOfTable, Log, Table};
 use std::convert::TryInto;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
 /// TOML.  
 /// トムル。  
@@ -42,44 +41,44 @@ impl Toml {
     /// Line scan.
     /// 行走査。
     pub fn from_file(path: &str) -> TomlDocument {
+        match fs::read_to_string(path) {
+            Ok(text) => Toml::from_string(&text),
+            Err(why) => panic!("{}", why),
+        }
+    }
+
+    /// Line scan. The line endings are kept, so `\r\n` and `\n` are both fine.  
+    /// 行走査。改行は残すので、 `\r\n` も `\n` も使えます。  
+    pub fn from_string(text: &str) -> TomlDocument {
         let mut error_tables = Vec::<Table>::new();
         let mut output_document = TomlDocument::default();
-        match File::open(path) {
-            Ok(file) => {
-                let mut document_p = DocumentP::default();
-                for (i, line) in BufReader::new(file).lines().enumerate() {
-                    let row_number = i + 1;
-                    let line = match line {
-                        Ok(line) => line,
-                        Err(why) => panic!(Log::fatal(&format!("{}", why))),
-                    };
-                    // Log::trace(&format!("from_file/line=|{}|", line));
+        let mut document_p = DocumentP::default();
+        for (i, line) in text.split_inclusive('\n').enumerate() {
+            let row_number = i + 1;
+            // Log::trace(&format!("from_string/line=|{}|", line));
 
-                    match document_p.scan_line(&line.chars().collect(), &mut output_document) {
-                        PResult::End => {} // Ignored it.
-                        PResult::Err(table) => {
-                            error_tables.push(
-                                Table::default()
-                                    .str("via", "lib.rs.65.")
-                                    .int(
-                                        "row_number",
-                                        if let Ok(n) = row_number.try_into() {
-                                            n
-                                        } else {
-                                            -1
-                                        },
-                                    )
-                                    .str("line", &format!("{}", line))
-                                    .sub_t("table", &table)
-                                    .sub_t("document_p", &document_p.log())
-                                    .clone(),
-                            );
-                        }
-                        PResult::Ongoing => {} // Ignored it.
-                    }
+            match document_p.scan_line(&line.chars().collect(), &mut output_document) {
+                PResult::End => {} // Ignored it.
+                PResult::Err(table) => {
+                    error_tables.push(
+                        Table::default()
+                            .str("via", "lib.rs.65.")
+                            .int(
+                                "row_number",
+                                if let Ok(n) = row_number.try_into() {
+                                    n
+                                } else {
+                                    -1
+                                },
+                            )
+                            .str("line", line.trim_end_matches(&['\r', '\n'][..]))
+                            .sub_t("table", &table)
+                            .sub_t("document_p", &document_p.log())
+                            .clone(),
+                    );
                 }
+                PResult::Ongoing => {} // Ignored it.
             }
-            Err(why) => panic!("{}", why),
         }
 
         if !error_tables.is_empty() {
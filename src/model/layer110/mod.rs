@@ -79,6 +79,8 @@ pub enum TokenType {
     Comment,
     DateTime,
     EscapeSequence,
+    /// `\r\n` or `\n`.
+    Newline,
     Table,
     /// White space.
     WSOld,
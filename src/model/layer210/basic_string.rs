@@ -7,12 +7,19 @@
 //! // "ハロー"
 //! ```
 
-use crate::model::{layer110::Token, layer210::BasicString};
+use crate::model::{
+    layer110::{Token, TokenType},
+    layer210::{BasicString, StringStyle},
+};
 use std::fmt;
 
 impl Default for BasicString {
     fn default() -> Self {
-        BasicString { tokens: Vec::new() }
+        BasicString {
+            tokens: Vec::new(),
+            raw: String::new(),
+            style: StringStyle::SingleLine,
+        }
     }
 }
 impl BasicString {
@@ -22,6 +29,37 @@ impl BasicString {
     pub fn push_token(&mut self, token: &Token) {
         self.tokens.push(token.clone());
     }
+    pub fn push_raw_character(&mut self, chr: char) {
+        self.raw.push(chr);
+    }
+    /// Value with the quotes removed and the escape sequences decoded.  
+    /// 引用符を外した値、エスケープ・シーケンスは解除済み。  
+    pub fn value(&self) -> String {
+        let mut buf = String::new();
+        for token in &self.tokens {
+            buf.push_str(&token.to_string());
+        }
+        buf
+    }
+    /// Replaces the value. The source text is escaped again, in the same style.  
+    /// 値を置き換えます。ソース・テキストは同じ書き方でエスケープし直します。  
+    pub fn set_value(&mut self, value: &str) {
+        self.raw = escape(value, self.style, head_newline(&self.raw));
+        self.tokens = vec![Token::new(value, TokenType::BasicString)];
+    }
+    /// Decoded characters.  
+    /// エスケープを解いた文字。  
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+    /// Source text between the quotes, as written.  
+    /// 引用符の間の、書かれたままのソース・テキスト。  
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+    pub fn style(&self) -> StringStyle {
+        self.style
+    }
     pub fn to_debug_string(&self) -> String {
         format!("{:?}", self)
     }
@@ -29,13 +67,45 @@ impl BasicString {
         format!("{}", self)
     }
 }
+/// Source text between the quotes of `value`, escaped. `head` is the newline after `"""`.  
+/// エスケープした、 `value` の引用符の間のソース・テキスト。 `head` は `"""` の後の改行です。  
+pub(crate) fn escape(value: &str, style: StringStyle, head: &str) -> String {
+    let multi_line = style == StringStyle::MultiLine;
+    let mut text = head.to_string();
+    for (i, ch) in value.chars().enumerate() {
+        match ch {
+            '\\' => text.push_str("\\\\"),
+            '"' => text.push_str("\\\""),
+            // The newline right after `"""` is trimmed, so the first one is escaped.
+            // `"""` の直後の改行は削られるので、最初の改行はエスケープします。
+            '\n' if multi_line && (0 < i || !head.is_empty()) => text.push('\n'),
+            '\n' => text.push_str("\\n"),
+            '\t' => text.push_str("\\t"),
+            '\r' => text.push_str("\\r"),
+            ch if ch.is_control() => text.push_str(&format!("\\u{:04X}", ch as u32)),
+            ch => text.push(ch),
+        }
+    }
+    text
+}
+/// The newline right after `"""` or `'''`. It is not a part of the string.  
+/// `"""` か `'''` の直後の改行。文字列には含まれません。  
+pub(crate) fn head_newline(raw: &str) -> &'static str {
+    if raw.starts_with("\r\n") {
+        "\r\n"
+    } else if raw.starts_with('\n') {
+        "\n"
+    } else {
+        ""
+    }
+}
 impl fmt::Display for BasicString {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut buf = String::new();
-        for token in &self.tokens {
-            buf.push_str(&token.to_string());
-        }
-        write!(f, "{}", buf)
+        let quotation = match self.style {
+            StringStyle::SingleLine => "\"",
+            StringStyle::MultiLine => "\"\"\"",
+        };
+        write!(f, "{}{}{}", quotation, self.raw, quotation)
     }
 }
 impl fmt::Debug for BasicString {
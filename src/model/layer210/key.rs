@@ -1,33 +1,69 @@
-//! Literal string model.  
-//! リテラル文字列モデル。  
+//! Key model.  
+//! キー・モデル。  
 //!
 //! # Examples
 //!
 //! ```
 //! // work_number
+//! // site."google.com"
 //! ```
 
-use crate::model::{layer110::Token, layer210::Key};
+use crate::model::{
+    layer110::{Token, TokenType},
+    layer210::{basic_string::escape, Key, StringStyle},
+};
 use std::fmt;
 
 impl Default for Key {
     fn default() -> Self {
-        Key { tokens: Vec::new() }
+        Key {
+            tokens: Vec::new(),
+            segments: Vec::new(),
+        }
     }
 }
 impl Key {
     pub fn from_token(token: &Token) -> Self {
         let mut m = Key::default();
         m.push_token(token);
+        m.push_segment(&token.value);
         m
     }
 
+    /// `["a", "b.c"]` is written as `a."b.c"`.  
+    /// `["a", "b.c"]` は `a."b.c"` と書きます。  
+    pub(crate) fn from_segments(segments: &[String]) -> Self {
+        let mut m = Key::default();
+        m.set_segments(segments);
+        m
+    }
+    /// Replaces the names. The source text is written again, quoted if needed.  
+    /// 名前を置き換えます。ソース・テキストは書き直し、必要なら引用符で囲みます。  
+    pub fn set_segments(&mut self, segments: &[String]) {
+        self.tokens = vec![Token::new(&to_dotted_key(segments), TokenType::Key)];
+        self.segments = segments.to_vec();
+    }
+    /// Source text, as written.  
+    /// 書かれたままのソース・テキスト。  
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
     pub fn extend_tokens(&mut self, tokens: &Vec<Token>) {
         self.tokens.extend(tokens.clone());
     }
     pub fn push_token(&mut self, token: &Token) {
         self.tokens.push(token.clone());
     }
+    /// Name of a segment. Quotes and escapes are already resolved.  
+    /// セグメントの名前。引用符とエスケープは解決済みです。  
+    pub fn push_segment(&mut self, name: &str) {
+        self.segments.push(name.to_string());
+    }
+    /// `a."b.c".d` is `["a", "b.c", "d"]`.  
+    pub fn get_segments(&self) -> &[String] {
+        &self.segments
+    }
     pub fn to_debug_string(&self) -> String {
         format!("{:?}", self)
     }
@@ -35,6 +71,25 @@ impl Key {
         format!("{}", self)
     }
 }
+/// `["a", "b.c", ""]` is `a."b.c".""`. TOML source text. Quoted keys are escaped as basic strings.  
+/// `["a", "b.c", ""]` は `a."b.c".""` です。 TOML のソース・テキストです。引用符で囲むキーは、基本文字列としてエスケープします。  
+pub(crate) fn to_dotted_key(segments: &[String]) -> String {
+    segments
+        .iter()
+        .map(|m| {
+            if !m.is_empty()
+                && m.chars()
+                    .all(|ch| matches!(ch, 'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_'))
+            {
+                m.to_string()
+            } else {
+                format!("\"{}\"", escape(m, StringStyle::SingleLine, ""))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
 impl fmt::Display for Key {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut buf = String::new();
@@ -7,12 +7,19 @@
 //! // 'ハロー'
 //! ```
 
-use crate::model::{layer110::Token, layer210::LiteralString};
+use crate::model::{
+    layer110::{Token, TokenType},
+    layer210::{basic_string::head_newline, LiteralString, StringStyle},
+};
 use std::fmt;
 
 impl Default for LiteralString {
     fn default() -> Self {
-        LiteralString { tokens: Vec::new() }
+        LiteralString {
+            tokens: Vec::new(),
+            raw: String::new(),
+            style: StringStyle::SingleLine,
+        }
     }
 }
 impl LiteralString {
@@ -22,6 +29,47 @@ impl LiteralString {
     pub fn push_token(&mut self, token: &Token) {
         self.tokens.push(token.clone());
     }
+    pub fn push_raw_character(&mut self, chr: char) {
+        self.raw.push(chr);
+    }
+    /// Value with the quotes removed.  
+    /// 引用符を外した値。  
+    pub fn value(&self) -> String {
+        let mut buf = String::new();
+        for token in &self.tokens {
+            buf.push_str(&token.to_string());
+        }
+        buf
+    }
+    /// Replaces the value. The source text is rewritten in the same style.  
+    /// 値を置き換えます。ソース・テキストは同じ書き方で書き直します。  
+    ///
+    /// # Errors
+    ///
+    /// `"it's" can't be written in a literal string.`  
+    pub fn set_value(&mut self, value: &str) -> Result<(), String> {
+        match to_raw(value, self.style, head_newline(&self.raw)) {
+            Some(raw) => {
+                self.raw = raw;
+                self.tokens = vec![Token::new(value, TokenType::LiteralString)];
+                Ok(())
+            }
+            None => Err(format!("{:?} can't be written in a literal string.", value)),
+        }
+    }
+    /// Characters of the value.  
+    /// 値の文字。  
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+    /// Source text between the quotes, as written.  
+    /// 引用符の間の、書かれたままのソース・テキスト。  
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+    pub fn style(&self) -> StringStyle {
+        self.style
+    }
     pub fn to_debug_string(&self) -> String {
         format!("{:?}", self)
     }
@@ -29,13 +77,44 @@ impl LiteralString {
         format!("{}", self)
     }
 }
+/// Source text between the quotes of `value`. `head` is the newline after `'''`.  
+/// `None` if it can not be written without escapes.  
+/// `value` の引用符の間のソース・テキスト。 `head` は `'''` の後の改行です。  
+/// エスケープ無しで書けなければ `None` 。  
+pub(crate) fn to_raw(value: &str, style: StringStyle, head: &str) -> Option<String> {
+    match style {
+        StringStyle::SingleLine => {
+            if value
+                .chars()
+                .any(|ch| ch == '\'' || (ch.is_control() && ch != '\t'))
+            {
+                None
+            } else {
+                Some(value.to_string())
+            }
+        }
+        StringStyle::MultiLine => {
+            if value.contains("'''")
+                || (head.is_empty() && value.starts_with('\n'))
+                || value.ends_with('\'')
+                || value
+                    .chars()
+                    .any(|ch| ch.is_control() && !matches!(ch, '\t' | '\n'))
+            {
+                None
+            } else {
+                Some(format!("{}{}", head, value))
+            }
+        }
+    }
+}
 impl fmt::Display for LiteralString {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut buf = String::new();
-        for token in &self.tokens {
-            buf.push_str(&token.to_string());
-        }
-        write!(f, "{}", buf)
+        let quotation = match self.style {
+            StringStyle::SingleLine => "'",
+            StringStyle::MultiLine => "'''",
+        };
+        write!(f, "{}{}{}", quotation, self.raw, quotation)
     }
 }
 impl fmt::Debug for LiteralString {
@@ -9,10 +9,23 @@
 
 use crate::model::{
     layer110::{Token, TokenType},
-    layer210::LiteralValue,
+    layer210::{LiteralValue, LiteralValueType},
 };
+use num_traits::{float::FloatCore, Num};
 use std::fmt;
 
+impl LiteralValueType {
+    /// Name of the type. For messages.  
+    /// 型の名前。メッセージ用。  
+    pub fn name(&self) -> &'static str {
+        match self {
+            LiteralValueType::Boolean => "boolean",
+            LiteralValueType::DateTime => "date-time",
+            LiteralValueType::Float => "float",
+            LiteralValueType::Integer => "integer",
+        }
+    }
+}
 impl Default for LiteralValue {
     fn default() -> Self {
         LiteralValue { tokens: Vec::new() }
@@ -37,6 +50,106 @@ impl LiteralValue {
     pub fn push_token(&mut self, token: &Token) {
         self.tokens.push(token.clone());
     }
+    /// Type judged from the text. `None` if it is not a TOML value.  
+    /// テキストから判断した型。TOMLの値でなければ `None` 。  
+    pub fn get_type(&self) -> Option<LiteralValueType> {
+        for token in &self.tokens {
+            match token.type_ {
+                TokenType::SPDateTimeString => return Some(LiteralValueType::DateTime),
+                TokenType::SPPositionalNumeralString => return Some(LiteralValueType::Integer),
+                _ => {}
+            }
+        }
+        let s = self.to_string();
+        match s.as_str() {
+            "true" | "false" => return Some(LiteralValueType::Boolean),
+            "inf" | "+inf" | "-inf" | "nan" | "+nan" | "-nan" => {
+                return Some(LiteralValueType::Float)
+            }
+            _ => {}
+        }
+        if !s.chars().any(|ch| ch.is_ascii_digit())
+            || !s
+                .chars()
+                .all(|ch| matches!(ch, '0'..='9' | '+' | '-' | '.' | 'e' | 'E' | '_'))
+        {
+            return None;
+        }
+        // アンダースコアは除去しないと変換できない。
+        let n = s.replace('_', "");
+        // Too large for `i128` is still an integer, such as `u128::MAX`.
+        // `i128` に収まらなくても、 `u128::MAX` などは整数です。
+        let digits = n.strip_prefix(&['+', '-'][..]).unwrap_or(&n);
+        if !digits.is_empty() && digits.chars().all(|ch| ch.is_ascii_digit()) {
+            Some(LiteralValueType::Integer)
+        } else if n.parse::<f64>().is_ok() {
+            Some(LiteralValueType::Float)
+        } else {
+            None
+        }
+    }
+    /// `0xDEADBEEF`, `1_000` and so on.  
+    /// `0xDEADBEEF` 、 `1_000` など。  
+    pub fn to_int<T: Num + std::str::FromStr>(&self) -> Result<T, String>
+    where
+        <T as num_traits::Num>::FromStrRadixErr: std::fmt::Display,
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        // アンダースコアは除去しないと変換できない。
+        let s = self.to_string().replace('_', "");
+
+        // 10進数ではないかも知れない。
+        let base_number = if s.starts_with("0b") {
+            2
+        } else if s.starts_with("0o") {
+            8
+        } else if s.starts_with("0x") {
+            16
+        } else {
+            10
+        };
+
+        if 10 != base_number {
+            // 頭の `0x` は除去しないと変換できない。
+            return match T::from_str_radix(&s[2..], base_number) {
+                Ok(n) => Ok(n),
+                Err(why) => Err(format!("{}", why)),
+            };
+        }
+
+        match s.parse() {
+            Ok(n) => Ok(n),
+            Err(why) => Err(format!("{}", why)),
+        }
+    }
+    /// `3.14`, `1e+22`, `inf`, `-nan` and so on.  
+    /// `3.14` 、 `1e+22` 、 `inf` 、 `-nan` など。  
+    pub fn to_float<T: FloatCore + Num + std::str::FromStr>(&self) -> Result<T, String>
+    where
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        // アンダースコアは除去しないと変換できない。
+        let s = self.to_string().replace('_', "");
+
+        match s.as_str() {
+            "nan" | "+nan" => return Ok(T::nan()),
+            "-nan" => return Ok(-T::nan()),
+            _ => {}
+        }
+
+        match s.parse() {
+            Ok(n) => Ok(n),
+            Err(why) => Err(format!("{}", why)),
+        }
+    }
+    /// `true` or `false`.  
+    /// `true` か `false` 。  
+    pub fn to_bool(&self) -> Result<bool, String> {
+        match self.to_string().parse() {
+            Ok(b) => Ok(b),
+            Err(why) => Err(format!("{}", why)),
+        }
+    }
     pub fn to_debug_string(&self) -> String {
         format!("{:?}", self)
     }
@@ -4,9 +4,11 @@ pub mod date_time;
 pub mod key;
 pub mod literal_string;
 pub mod literal_value;
+pub mod newline;
 pub mod non_ascii;
 pub mod non_eol;
 pub mod ws;
+pub mod ws_comment_newline;
 pub mod wschar;
 
 use crate::model::layer110::Token;
@@ -29,21 +31,38 @@ pub struct DateTime {
 /// １つの文字列を持ちます。  
 #[derive(Clone)]
 pub struct BasicString {
-    pub tokens: Vec<Token>,
+    /// Decoded characters. Change them with `set_value`, which rewrites `raw` too.  
+    /// エスケープを解いた文字。 `raw` も書き換える `set_value` で変えてください。  
+    pub(crate) tokens: Vec<Token>,
+    /// Source text between the quotes, as written.  
+    /// 引用符の間の、書かれたままのソース・テキスト。  
+    pub(crate) raw: String,
+    pub(crate) style: StringStyle,
 }
 
-/// Key.  
-/// キー。  
+/// Key. Dotted keys such as `a."b.c".d` too.  
+/// キー。 `a."b.c".d` のようなドット区切りのキーも。  
 #[derive(Clone)]
 pub struct Key {
-    pub tokens: Vec<Token>,
+    /// Source text, as written. Change it with `set_segments`, which rewrites `segments` too.  
+    /// 書かれたままのソース・テキスト。 `segments` も書き換える `set_segments` で変えてください。  
+    pub(crate) tokens: Vec<Token>,
+    /// Names between the dots, without quotes.  
+    /// ドットの間の名前。引用符は外します。  
+    segments: Vec<String>,
 }
 
 /// It has one string.  
 /// １つの文字列を持ちます。  
 #[derive(Clone)]
 pub struct LiteralString {
-    pub tokens: Vec<Token>,
+    /// Characters of the value. Change them with `set_value`, which rewrites `raw` too.  
+    /// 値の文字。 `raw` も書き換える `set_value` で変えてください。  
+    pub(crate) tokens: Vec<Token>,
+    /// Source text between the quotes, as written.  
+    /// 引用符の間の、書かれたままのソース・テキスト。  
+    pub(crate) raw: String,
+    pub(crate) style: StringStyle,
 }
 
 /// It has one string.  
@@ -53,6 +72,37 @@ pub struct LiteralValue {
     pub tokens: Vec<Token>,
 }
 
+/// Type of a literal value.  
+/// リテラル値の型。  
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LiteralValueType {
+    /// `true`, `false`.  
+    Boolean,
+    /// `1979-05-27T07:32:00Z`, `07:32:00`.  
+    DateTime,
+    /// `3.14`, `5e+22`, `inf`, `nan`.  
+    Float,
+    /// `42`, `1_000`, `0xDEADBEEF`.  
+    Integer,
+}
+
+/// Quoting style of a string.  
+/// 文字列の引用符の種類。  
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StringStyle {
+    /// `"abc"`, `'abc'`.  
+    SingleLine,
+    /// `"""abc"""`, `'''abc'''`.  
+    MultiLine,
+}
+
+/// Newline. `\r\n` or `\n`. Empty at the end of file.  
+/// 改行。 `\r\n` か `\n` 。ファイルの終わりでは空。  
+#[derive(Clone)]
+pub struct Newline {
+    pub tokens: Vec<Token>,
+}
+
 /// NonAscii.  
 /// 非ASCII。  
 #[derive(Clone)]
@@ -74,6 +124,13 @@ pub struct Ws {
     pub tokens: Vec<Token>,
 }
 
+/// Whitespace, comments and newlines. Example: Between array items.  
+/// 空白、コメント、改行。例: 配列の項目の間。  
+#[derive(Clone)]
+pub struct WsCommentNewline {
+    pub tokens: Vec<Token>,
+}
+
 /// Whitespace character.  
 /// 空白文字。  
 #[derive(Clone)]
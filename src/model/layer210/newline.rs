@@ -0,0 +1,49 @@
+//! Newline model.  
+//! 改行モデル。  
+//!
+//! # Examples
+//!
+//! ```
+//! // \r\n
+//! // \n
+//! ```
+
+use crate::model::{layer110::Token, layer210::Newline};
+use std::fmt;
+
+impl Default for Newline {
+    fn default() -> Self {
+        Newline { tokens: Vec::new() }
+    }
+}
+impl Newline {
+    pub fn push_token(&mut self, token: &Token) {
+        self.tokens.push(token.clone());
+    }
+    /// No newline. The end of file.  
+    /// 改行が無い。ファイルの終わり。  
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+    pub fn to_debug_string(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+impl fmt::Display for Newline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = String::new();
+        for token in &self.tokens {
+            buf.push_str(&token.to_string());
+        }
+        write!(f, "{}", buf)
+    }
+}
+impl fmt::Debug for Newline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = String::new();
+        for token in &self.tokens {
+            buf.push_str(&token.to_debug_string());
+        }
+        write!(f, "{}", buf)
+    }
+}
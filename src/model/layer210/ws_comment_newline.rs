@@ -0,0 +1,68 @@
+//! Whitespace, comment and newline model.  
+//! 空白、コメント、改行モデル。  
+//!
+//! # Examples
+//!
+//! ```
+//! // [
+//! //   "a", # Comment.
+//! //   "b",
+//! // ]
+//! ```
+
+use crate::model::{
+    layer110::{Token, TokenType},
+    layer210::{Comment, WsCommentNewline},
+};
+use std::fmt;
+
+impl Default for WsCommentNewline {
+    fn default() -> Self {
+        WsCommentNewline { tokens: Vec::new() }
+    }
+}
+impl WsCommentNewline {
+    pub fn push_token(&mut self, token: &Token) {
+        self.tokens.push(token.clone());
+    }
+    /// Comments in it.  
+    /// 含まれるコメント。  
+    pub fn get_comments(&self) -> Vec<Comment> {
+        let mut comments = Vec::new();
+        let mut comment: Option<Comment> = None;
+        for token in &self.tokens {
+            if let TokenType::Comment = token.type_ {
+                comment
+                    .get_or_insert_with(Comment::default)
+                    .push_token(token);
+            } else if let Some(m) = comment.take() {
+                comments.push(m);
+            }
+        }
+        if let Some(m) = comment {
+            comments.push(m);
+        }
+        comments
+    }
+    pub fn to_debug_string(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+impl fmt::Display for WsCommentNewline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = String::new();
+        for token in &self.tokens {
+            buf.push_str(&token.to_string());
+        }
+        write!(f, "{}", buf)
+    }
+}
+impl fmt::Debug for WsCommentNewline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = String::new();
+        for token in &self.tokens {
+            buf.push_str(&token.to_debug_string());
+        }
+        write!(f, "{}", buf)
+    }
+}
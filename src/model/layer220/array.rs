@@ -5,32 +5,133 @@
 //!
 //! ```
 //! // [ 1, 2, 3 ]
+//!
+//! // [
+//! //   "a", # Comment.
+//! //   "b",
+//! // ]
 //! ```
-use num_traits::Num;
+use num_traits::{float::FloatCore, Num};
 
 use crate::model::{
-    layer210::{BasicString, LiteralString, LiteralValue},
+    layer110::{Token, TokenType},
+    layer210::{
+        BasicString, Comment, LiteralString, LiteralValue, LiteralValueType, WsCommentNewline,
+    },
     layer220::{Array, ItemValue},
+    layer225::{FromToml, GetError, InlineTable, Val},
 };
+use std::collections::HashMap;
 use std::fmt;
 
 impl Default for Array {
     fn default() -> Self {
-        Array { items: Vec::new() }
+        Array {
+            items: Vec::new(),
+            layout: vec![WsCommentNewline::default()],
+        }
     }
 }
 impl Array {
     pub fn push_literal_string(&mut self, m: &LiteralValue) {
-        self.items.push(ItemValue::LiteralValue(m.clone()));
+        self.push_item(ItemValue::LiteralValue(m.clone()));
     }
     pub fn push_single_quote_string(&mut self, m: &LiteralString) {
-        self.items.push(ItemValue::LiteralString(m.clone()));
+        self.push_item(ItemValue::LiteralString(m.clone()));
     }
     pub fn push_double_quote_string(&mut self, m: &BasicString) {
-        self.items.push(ItemValue::BasicString(m.clone()));
+        self.push_item(ItemValue::BasicString(m.clone()));
+    }
+    pub fn push_inline_table(&mut self, m: &InlineTable) {
+        self.push_item(ItemValue::InlineTable(m.clone()));
     }
     pub fn push_array(&mut self, m: &Array) {
-        self.items.push(ItemValue::Array(m.clone()));
+        self.push_item(ItemValue::Array(m.clone()));
+    }
+    fn push_item(&mut self, m: ItemValue) {
+        self.items.push(m);
+        // After the item.
+        // 項目の後。
+        self.layout.push(WsCommentNewline::default());
+    }
+    /// `,`.
+    pub fn push_comma(&mut self) {
+        // Before the next item, or `]`.
+        // 次の項目か、 `]` の前。
+        self.layout.push(WsCommentNewline::default());
+    }
+    /// Whitespace, comment or newline at the current position.  
+    /// 現在の位置の空白、コメント、改行。  
+    pub fn push_ws_comment_newline_token(&mut self, token: &Token) {
+        if let Some(m) = self.layout.last_mut() {
+            m.push_token(token);
+        }
+    }
+    /// Comments between the brackets.  
+    /// 角括弧の間のコメント。  
+    pub fn get_comments(&self) -> Vec<Comment> {
+        let mut comments = Vec::new();
+        for m in &self.layout {
+            comments.extend(m.get_comments());
+        }
+        comments
+    }
+    /// Number of items.  
+    /// 項目の数。  
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    /// Item at the index.  
+    /// 添え字の位置の項目。  
+    pub fn get(&self, index: usize) -> Option<&ItemValue> {
+        self.items.get(index)
+    }
+    /// Items in order.  
+    /// 順番通りの項目。  
+    pub fn iter(&self) -> std::slice::Iter<'_, ItemValue> {
+        self.items.iter()
+    }
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, ItemValue> {
+        self.items.iter_mut()
+    }
+    /// Items and layout. For visitors.  
+    /// 項目とレイアウト。ビジター用。  
+    pub(crate) fn parts(&self) -> (&[ItemValue], &[WsCommentNewline]) {
+        (&self.items, &self.layout)
+    }
+    /// Bytes from `[` to the item. Same as `Display`.  
+    /// `[` から項目までのバイト数。 `Display` と同じです。  
+    pub(crate) fn item_offset(&self, index: usize) -> Option<usize> {
+        if self.items.len() <= index {
+            return None;
+        }
+        let mut offset = 1;
+        for (i, item) in self.items[..index].iter().enumerate() {
+            offset += self.layout[2 * i].to_string().len()
+                + item.to_string().len()
+                + self.layout[2 * i + 1].to_string().len()
+                + 1;
+        }
+        Some(offset + self.layout[2 * index].to_string().len())
+    }
+    pub(crate) fn parts_mut(&mut self) -> (&mut [ItemValue], &mut [WsCommentNewline]) {
+        (&mut self.items, &mut self.layout)
+    }
+    /// Removes the item. Comments after its comma go with it.  
+    /// 項目を取り除きます。カンマの後のコメントも一緒です。  
+    pub(crate) fn remove(&mut self, index: usize) -> Option<ItemValue> {
+        if self.items.len() <= index {
+            return None;
+        }
+        if 1 < self.items.len() && index + 1 == self.items.len() {
+            remove_last_layout(&mut self.layout, index);
+        } else {
+            remove_layout(&mut self.layout, index, self.items.len());
+        }
+        Some(self.items.remove(index))
     }
     pub fn to_debug_string(&self) -> String {
         format!("{:?}", self)
@@ -41,31 +142,234 @@ impl Array {
     pub fn to_string_vector(&self) -> Vec<String> {
         let mut vec = Vec::<String>::new();
         for item in &self.items {
-            vec.push(item.to_string());
+            vec.push(match item {
+                ItemValue::BasicString(m) => m.value(),
+                ItemValue::LiteralString(m) => m.value(),
+                _ => item.to_string(),
+            });
         }
         vec
     }
-    pub fn to_int_vector<T: Num + std::str::FromStr>(&self) -> Result<Vec<T>, String>
+    /// `[1, 0xff, 1_000]`.  
+    ///
+    /// # Errors
+    ///
+    /// `[2] is a string, not an integer.`  
+    pub fn to_int_vector<T: Num + std::str::FromStr>(&self) -> Result<Vec<T>, GetError>
+    where
+        <T as num_traits::Num>::FromStrRadixErr: std::fmt::Display,
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.to_literal_value_vector(LiteralValueType::Integer, |m| {
+            m.to_int()
+                .map_err(|_| GetError::out_of_range(&m.to_string(), std::any::type_name::<T>()))
+        })
+    }
+    /// `[0.5, 1e+22, inf]`.  
+    ///
+    /// # Errors
+    ///
+    /// `[2] is an integer, not a float.`  
+    pub fn to_float_vector<T: FloatCore + Num + std::str::FromStr>(
+        &self,
+    ) -> Result<Vec<T>, GetError>
+    where
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.to_literal_value_vector(LiteralValueType::Float, |m| {
+            m.to_float().map_err(|why| GetError::invalid_format(&why))
+        })
+    }
+    /// `[true, false]`.  
+    ///
+    /// # Errors
+    ///
+    /// `[2] is a string, not a boolean.`  
+    pub fn to_bool_vector(&self) -> Result<Vec<bool>, GetError> {
+        self.to_literal_value_vector(LiteralValueType::Boolean, |m| {
+            m.to_bool().map_err(|why| GetError::invalid_format(&why))
+        })
+    }
+    /// `[1979-05-27, 1979-05-27T07:32:00Z]`.  
+    /// `T` is a `chrono` type, such as `NaiveDate` or `DateTime<Utc>`.  
+    /// `T` は `NaiveDate` や `DateTime<Utc>` のような `chrono` の型です。  
+    ///
+    /// # Errors
+    ///
+    /// `[2] is a string, not a date-time.`  
+    pub fn to_datetime_vector<T: std::str::FromStr>(&self) -> Result<Vec<T>, GetError>
     where
         <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.to_literal_value_vector(LiteralValueType::DateTime, |m| {
+            match m.to_string().parse::<T>() {
+                Ok(n) => Ok(n),
+                Err(why) => Err(GetError::invalid_format(&why.to_string())),
+            }
+        })
+    }
+    /// `[[1, 2], [3]]`. Each array is converted by `f`.  
+    /// 各配列は `f` で変換します。  
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // let vec: Vec<Vec<i64>> = array.to_nested_vector(|m| m.to_int_vector())?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `[1][0] is a string, not an integer.`  
+    pub fn to_nested_vector<T, F>(&self, f: F) -> Result<Vec<T>, GetError>
+    where
+        F: Fn(&Array) -> Result<T, GetError>,
     {
         let mut vec = Vec::<T>::new();
-        for item in &self.items {
-            let num = match item.to_string().parse() {
-                Ok(n) => n,
-                Err(why) => return Err(format!("{}", why)),
-            };
-            vec.push(num);
+        for (i, item) in self.items.iter().enumerate() {
+            if let ItemValue::Array(m) = item {
+                match f(m) {
+                    Ok(n) => vec.push(n),
+                    Err(why) => return Err(why.with_parent(&format!("[{}]", i))),
+                }
+            } else {
+                return Err(type_mismatch(i, item, "array"));
+            }
+        }
+        Ok(vec)
+    }
+    /// `[ { x = 1 }, { x = 2 } ]`. The map keys are the names without quotes, same as `FromToml` for `HashMap`.  
+    /// マップのキーは、 `HashMap` の `FromToml` と同じく、引用符を外した名前です。  
+    ///
+    /// # Errors
+    ///
+    /// `[2] is an integer, not an inline table.`  
+    pub fn to_inline_table_vector(&self) -> Result<Vec<HashMap<String, Val>>, GetError> {
+        let mut vec = Vec::new();
+        for (i, item) in self.items.iter().enumerate() {
+            if let ItemValue::InlineTable(m) = item {
+                match HashMap::from_toml(&Val::InlineTable(m.clone())) {
+                    Ok(n) => vec.push(n),
+                    Err(why) => return Err(why.with_parent(&format!("[{}]", i))),
+                }
+            } else {
+                return Err(type_mismatch(i, item, "inline table"));
+            }
+        }
+        Ok(vec)
+    }
+    fn to_literal_value_vector<T, F>(
+        &self,
+        expected: LiteralValueType,
+        f: F,
+    ) -> Result<Vec<T>, GetError>
+    where
+        F: Fn(&LiteralValue) -> Result<T, GetError>,
+    {
+        let mut vec = Vec::<T>::new();
+        for (i, item) in self.items.iter().enumerate() {
+            match item {
+                ItemValue::LiteralValue(m) if m.get_type() == Some(expected) => match f(m) {
+                    Ok(n) => vec.push(n),
+                    Err(why) => return Err(why.with_parent(&format!("[{}]", i))),
+                },
+                _ => return Err(type_mismatch(i, item, expected.name())),
+            }
         }
         Ok(vec)
     }
 }
+
+/// Drops the layout around the item at `index` of `len` items.  
+/// The next item takes the whitespace before the removed one. The last item gives its whitespace before `]` to the new last item.  
+/// `len` 個の項目のうち、 `index` の位置の項目の周りのレイアウトを捨てます。  
+/// 次の項目は、取り除いた項目の前の空白を受け継ぎます。最後の項目は `]` の前の空白を、新しい最後の項目に渡します。  
+pub(crate) fn remove_layout<T: Default>(layout: &mut Vec<T>, index: usize, len: usize) {
+    if len == 1 {
+        *layout = vec![T::default()];
+    } else if index + 1 < len {
+        layout.drain(2 * index + 1..=2 * index + 2);
+    } else {
+        layout.drain(2 * index - 1..=2 * index);
+    }
+}
+
+/// Drops the layout around the last item at `index`, which is not the first one.  
+/// A comment after the previous comma stays with the comma. Comments of the removed item are dropped.  
+/// 最初ではない最後の項目 `index` の周りのレイアウトを捨てます。  
+/// 前のカンマの後のコメントは、カンマと一緒に残ります。取り除いた項目のコメントは捨てます。  
+fn remove_last_layout(layout: &mut Vec<WsCommentNewline>, index: usize) {
+    // After the previous comma, up to the removed item.
+    let before = layout[2 * index].tokens.clone();
+    // From the removed item to `]`.
+    let mut closing = layout[2 * index + 1].tokens.clone();
+    let trailing_comma = layout.len() == 2 * index + 3;
+    if trailing_comma {
+        closing.extend(layout[2 * index + 2].tokens.iter().cloned());
+    }
+    // The last newline and the indentation of `]`. Whitespace only if there is no newline.
+    if let Some(i) = last_newline(&closing) {
+        closing.drain(..i);
+    }
+    let has_comment = before
+        .iter()
+        .any(|token| matches!(token.type_, TokenType::Comment));
+    layout.truncate(2 * index);
+    match last_newline(&before) {
+        Some(i) if has_comment || trailing_comma => {
+            // The previous comma is now the trailing comma.
+            let mut tokens = before[..i].to_vec();
+            tokens.extend(
+                before[i..]
+                    .iter()
+                    .take_while(|token| matches!(token.type_, TokenType::Newline))
+                    .cloned(),
+            );
+            tokens.extend(
+                closing
+                    .into_iter()
+                    .skip_while(|token| matches!(token.type_, TokenType::Newline)),
+            );
+            layout.push(WsCommentNewline { tokens });
+        }
+        _ if trailing_comma => layout.push(WsCommentNewline { tokens: closing }),
+        _ => layout[2 * index - 1].tokens.extend(closing),
+    }
+}
+
+/// Index of the last newline. `\r\n` starts at `\r`.  
+/// 最後の改行の位置。 `\r\n` なら `\r` の位置です。  
+fn last_newline(tokens: &[Token]) -> Option<usize> {
+    let i = tokens
+        .iter()
+        .rposition(|token| matches!(token.type_, TokenType::Newline))?;
+    if 0 < i && tokens[i - 1].value == "\r" {
+        Some(i - 1)
+    } else {
+        Some(i)
+    }
+}
+
 impl fmt::Display for Array {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // `layout` is `[before 0, after 0, before 1, after 1, ..., after the trailing comma]`.
+        // `layout` は `[項目0の前, 項目0の後, 項目1の前, 項目1の後, ..., 末尾のカンマの後]` です。
         let mut buf = String::new();
-        for item in &self.items {
-            buf.push_str(&format!("{},", item))
+        buf.push('[');
+        for (i, item) in self.items.iter().enumerate() {
+            buf.push_str(&format!(
+                "{}{}{}",
+                self.layout[2 * i],
+                item,
+                self.layout[2 * i + 1]
+            ));
+            if 2 * i + 2 < self.layout.len() {
+                buf.push(',');
+            }
+        }
+        if 2 * self.items.len() < self.layout.len() {
+            buf.push_str(&self.layout[2 * self.items.len()].to_string());
         }
+        buf.push(']');
         write!(f, "{}", buf)
     }
 }
@@ -78,3 +382,13 @@ impl fmt::Debug for Array {
         write!(f, "[ {} ]", buf)
     }
 }
+/// `[2] is a string, not an integer.`
+fn type_mismatch(index: usize, item: &ItemValue, expected: &'static str) -> GetError {
+    GetError::type_mismatch(expected, item.type_name()).with_parent(&format!("[{}]", index))
+}
+pub(crate) fn article(name: &str) -> &'static str {
+    match name.chars().next() {
+        Some('a') | Some('e') | Some('i') | Some('o') | Some('u') => "an",
+        _ => "a",
+    }
+}
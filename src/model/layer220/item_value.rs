@@ -1,9 +1,49 @@
 //! Item value model.  
 //! アイテム値モデル。  
 
-use crate::model::layer220::ItemValue;
+use crate::model::{layer220::ItemValue, layer225::Val};
 use std::fmt;
 
+impl ItemValue {
+    /// Name of the type. For messages.  
+    /// 型の名前。メッセージ用。  
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ItemValue::Array(_) => "array",
+            ItemValue::BasicString(_) | ItemValue::LiteralString(_) => "string",
+            ItemValue::InlineTable(_) => "inline table",
+            ItemValue::Keyval(_) => "key-value",
+            ItemValue::LiteralValue(m) => match m.get_type() {
+                Some(type_) => type_.name(),
+                None => "literal value",
+            },
+        }
+    }
+    /// As a right value. `None` for a key-value.  
+    /// 右値として。キー・バリューなら `None` 。  
+    pub fn to_val(&self) -> Option<Val> {
+        match self {
+            ItemValue::Array(m) => Some(Val::Array(m.clone())),
+            ItemValue::BasicString(m) => Some(Val::BasicString(m.clone())),
+            ItemValue::InlineTable(m) => Some(Val::InlineTable(m.clone())),
+            ItemValue::Keyval(_) => None,
+            ItemValue::LiteralValue(m) => Some(Val::LiteralValue(m.clone())),
+            ItemValue::LiteralString(m) => Some(Val::LiteralString(m.clone())),
+        }
+    }
+    /// As an item of an array.  
+    /// 配列の項目として。  
+    pub(crate) fn from_val(val: Val) -> ItemValue {
+        match val {
+            Val::Array(m) => ItemValue::Array(m),
+            Val::BasicString(m) => ItemValue::BasicString(m),
+            Val::InlineTable(m) => ItemValue::InlineTable(m),
+            Val::LiteralValue(m) => ItemValue::LiteralValue(m),
+            Val::LiteralString(m) => ItemValue::LiteralString(m),
+        }
+    }
+}
+
 impl fmt::Display for ItemValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
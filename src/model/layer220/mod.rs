@@ -2,7 +2,7 @@ pub mod array;
 pub mod item_value;
 
 use crate::model::{
-    layer210::{BasicString, LiteralString, LiteralValue},
+    layer210::{BasicString, LiteralString, LiteralValue, WsCommentNewline},
     layer225::{InlineTable, Keyval},
 };
 
@@ -11,6 +11,9 @@ use crate::model::{
 #[derive(Clone)]
 pub struct Array {
     items: Vec<ItemValue>,
+    /// Whitespace, comments and newlines. Before and after each item, and after the trailing comma.  
+    /// 空白、コメント、改行。各項目の前後と、末尾のカンマの後。  
+    layout: Vec<WsCommentNewline>,
 }
 
 /// Array, inline table item.  
@@ -0,0 +1,222 @@
+//! Conversion from a right value.  
+//! 右値からの変換。  
+//!
+//! # Examples
+//!
+//! ```
+//! // let port: u16 = doc.get("server.port")?;
+//! // let hosts: Vec<String> = doc.get("hosts")?;
+//! // let timeout: Option<f64> = doc.get("timeout")?;
+//! // let limits: HashMap<String, i64> = doc.get("limits")?;
+//! ```
+
+use crate::model::{
+    layer210::{key::to_dotted_key, Key, LiteralValue, LiteralValueType},
+    layer225::{FromToml, GetError, InlineTable, Keyval, Val},
+};
+use chrono::prelude::{DateTime, Local, Utc};
+use chrono::FixedOffset;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+macro_rules! impl_from_toml_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromToml for $t {
+                fn from_toml(val: &Val) -> Result<Self, GetError> {
+                    let m = to_literal_value(val, LiteralValueType::Integer)?;
+                    m.to_int()
+                        .map_err(|_| GetError::out_of_range(&m.to_string(), stringify!($t)))
+                }
+            }
+        )*
+    };
+}
+impl_from_toml_for_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl FromToml for f32 {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        to_literal_value(val, LiteralValueType::Float)?
+            .to_float()
+            .map_err(|why| GetError::invalid_format(&why))
+    }
+}
+impl FromToml for f64 {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        to_literal_value(val, LiteralValueType::Float)?
+            .to_float()
+            .map_err(|why| GetError::invalid_format(&why))
+    }
+}
+impl FromToml for bool {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        to_literal_value(val, LiteralValueType::Boolean)?
+            .to_bool()
+            .map_err(|why| GetError::invalid_format(&why))
+    }
+}
+impl FromToml for String {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        match val {
+            Val::BasicString(m) => Ok(m.value()),
+            Val::LiteralString(m) => Ok(m.value()),
+            _ => Err(GetError::type_mismatch("string", val.type_name())),
+        }
+    }
+}
+
+macro_rules! impl_from_toml_for_datetime {
+    ($($t:ty),*) => {
+        $(
+            impl FromToml for $t {
+                fn from_toml(val: &Val) -> Result<Self, GetError> {
+                    parse_datetime(val)
+                }
+            }
+        )*
+    };
+}
+impl_from_toml_for_datetime!(
+    DateTime<Utc>,
+    DateTime<Local>,
+    DateTime<FixedOffset>,
+    NaiveDateTime,
+    NaiveDate,
+    NaiveTime
+);
+
+/// The right value as is.  
+/// 右値そのまま。  
+impl FromToml for Val {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        Ok(val.clone())
+    }
+}
+impl<T: FromToml> FromToml for Option<T> {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        Ok(Some(T::from_toml(val)?))
+    }
+    fn from_missing() -> Result<Self, GetError> {
+        Ok(None)
+    }
+}
+/// `[1, 2, 3]`.
+///
+/// # Errors
+///
+/// The path is like `[2]`.  
+impl<T: FromToml> FromToml for Vec<T> {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        if let Val::Array(array) = val {
+            let mut vec = Vec::new();
+            for (i, item) in array.iter().enumerate() {
+                let n = match item.to_val() {
+                    Some(m) => T::from_toml(&m),
+                    None => Err(GetError::type_mismatch("value", item.type_name())),
+                };
+                match n {
+                    Ok(n) => vec.push(n),
+                    Err(why) => return Err(why.with_parent(&format!("[{}]", i))),
+                }
+            }
+            Ok(vec)
+        } else {
+            Err(GetError::type_mismatch("array", val.type_name()))
+        }
+    }
+}
+/// `{ x = 1, y = 2 }`. The map keys are the names without quotes.  
+/// A dotted key such as `a.b = 2` is a table at `a`, same as `TomlDocument::to_value`.  
+/// マップのキーは引用符を外した名前です。  
+/// `a.b = 2` のようなドット区切りのキーは、 `TomlDocument::to_value` と同じく `a` のテーブルです。  
+///
+/// # Errors
+///
+/// The path is like `.y`.  
+impl<T: FromToml, S: BuildHasher + Default> FromToml for HashMap<String, T, S> {
+    fn from_toml(val: &Val) -> Result<Self, GetError> {
+        if let Val::InlineTable(inline_table) = val {
+            let mut map = HashMap::default();
+            // Dotted keys, gathered by the first name.
+            // ドット区切りのキーを、最初の名前でまとめたもの。
+            let mut tables: Vec<(String, InlineTable)> = Vec::new();
+            for (key, m) in inline_table.iter() {
+                match key.get_segments() {
+                    [name] => match T::from_toml(m) {
+                        Ok(n) => {
+                            map.insert(name.to_string(), n);
+                        }
+                        Err(why) => {
+                            return Err(why.with_parent(&format!(
+                                ".{}",
+                                to_dotted_key(std::slice::from_ref(name))
+                            )))
+                        }
+                    },
+                    [name, rest @ ..] => {
+                        let keyval = Keyval::new(&Key::from_segments(rest), m);
+                        match tables.iter_mut().find(|(m, _table)| m == name) {
+                            Some((_name, table)) => {
+                                table.push_comma();
+                                table.push_keyval(&keyval);
+                            }
+                            None => {
+                                let mut table = InlineTable::default();
+                                table.push_keyval(&keyval);
+                                tables.push((name.to_string(), table));
+                            }
+                        }
+                    }
+                    [] => {}
+                }
+            }
+            for (name, table) in tables {
+                match T::from_toml(&Val::InlineTable(table)) {
+                    Ok(n) => {
+                        map.insert(name, n);
+                    }
+                    Err(why) => {
+                        return Err(why.with_parent(&format!(".{}", to_dotted_key(&[name]))))
+                    }
+                }
+            }
+            Ok(map)
+        } else {
+            Err(GetError::type_mismatch("inline table", val.type_name()))
+        }
+    }
+}
+
+/// `ports` and `[2] is a string, not an integer.` make `ports[2] is a string, not an integer.`  
+/// `ports` と `is not found.` は `ports is not found.` になります。  
+pub(crate) fn join_path(path: &str, why: &str) -> String {
+    if why.starts_with('[') || why.starts_with('.') {
+        format!("{}{}", path, why)
+    } else {
+        format!("{} {}", path, why)
+    }
+}
+fn to_literal_value(val: &Val, expected: LiteralValueType) -> Result<&LiteralValue, GetError> {
+    match val {
+        Val::LiteralValue(m) if m.get_type() == Some(expected) => Ok(m),
+        _ => Err(GetError::type_mismatch(expected.name(), val.type_name())),
+    }
+}
+/// `1979-05-27 07:32:00Z` is read as `1979-05-27T07:32:00Z`.  
+/// `1979-05-27 07:32:00Z` は `1979-05-27T07:32:00Z` として読みます。  
+fn parse_datetime<T: std::str::FromStr>(val: &Val) -> Result<T, GetError>
+where
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    let mut s = to_literal_value(val, LiteralValueType::DateTime)?.to_string();
+    if s.len() > 10 && s.is_char_boundary(10) && &s[10..11] == " " {
+        s.replace_range(10..11, "T");
+    }
+    match s.parse() {
+        Ok(n) => Ok(n),
+        Err(why) => Err(GetError::invalid_format(&why.to_string())),
+    }
+}
@@ -0,0 +1,111 @@
+//! Error of a typed get.  
+//! 型付きゲットのエラー。  
+//!
+//! # Examples
+//!
+//! ```
+//! // match doc.get::<u16>("server.port") {
+//! //     Err(GetError::TypeMismatch { span: Some(span), .. }) => println!("See line {}.", span.line),
+//! //     ...
+//! // }
+//! ```
+
+use crate::model::{
+    layer220::array::article,
+    layer225::{from_toml::join_path, GetError, Span},
+};
+use std::fmt;
+
+impl GetError {
+    /// For your own `FromToml`. `why` is like `is `x`, not a level.`  
+    /// 自分の `FromToml` 用。  
+    pub fn invalid_format(why: &str) -> Self {
+        GetError::InvalidFormat {
+            path: String::new(),
+            why: why.to_string(),
+            span: None,
+        }
+    }
+    /// For your own `FromToml`. `expected` and `found` are like `integer` and `string`.  
+    /// 自分の `FromToml` 用。  
+    pub fn type_mismatch(expected: &'static str, found: &'static str) -> Self {
+        GetError::TypeMismatch {
+            path: String::new(),
+            expected,
+            found,
+            span: None,
+        }
+    }
+    /// `70000` and `u16` make `= 70000 does not fit in u16.`, read after the path.  
+    /// `70000` と `u16` は `= 70000 does not fit in u16.` になり、パスの後に読みます。  
+    pub(crate) fn out_of_range(value: &str, type_name: &str) -> Self {
+        GetError::OutOfRange {
+            path: String::new(),
+            why: format!("= {} does not fit in {}.", value, type_name),
+            span: None,
+        }
+    }
+    pub fn path(&self) -> &str {
+        match self {
+            GetError::NotFound { path }
+            | GetError::TypeMismatch { path, .. }
+            | GetError::OutOfRange { path, .. }
+            | GetError::InvalidFormat { path, .. } => path,
+        }
+    }
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            GetError::NotFound { .. } => None,
+            GetError::TypeMismatch { span, .. }
+            | GetError::OutOfRange { span, .. }
+            | GetError::InvalidFormat { span, .. } => *span,
+        }
+    }
+    /// `ports` and `[2]` make `ports[2]`.  
+    /// `ports` と `[2]` は `ports[2]` になります。  
+    pub(crate) fn with_parent(mut self, parent: &str) -> Self {
+        match &mut self {
+            GetError::NotFound { path }
+            | GetError::TypeMismatch { path, .. }
+            | GetError::OutOfRange { path, .. }
+            | GetError::InvalidFormat { path, .. } => path.insert_str(0, parent),
+        }
+        self
+    }
+    pub(crate) fn with_span(mut self, m: Option<Span>) -> Self {
+        match &mut self {
+            GetError::NotFound { .. } => {}
+            GetError::TypeMismatch { span, .. }
+            | GetError::OutOfRange { span, .. }
+            | GetError::InvalidFormat { span, .. } => *span = m,
+        }
+        self
+    }
+}
+impl fmt::Display for GetError {
+    /// `line 3, column 9: ports[2] is a string, not an integer.`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(span) = self.span() {
+            write!(f, "line {}, column {}: ", span.line, span.column)?;
+        }
+        let message = match self {
+            GetError::NotFound { .. } => "is not found.".to_string(),
+            GetError::TypeMismatch {
+                expected, found, ..
+            } => format!(
+                "is {} {}, not {} {}.",
+                article(found),
+                found,
+                article(expected),
+                expected
+            ),
+            GetError::OutOfRange { why, .. } | GetError::InvalidFormat { why, .. } => why.clone(),
+        };
+        if self.path().is_empty() {
+            write!(f, "{}", message)
+        } else {
+            write!(f, "{}", join_path(self.path(), &message))
+        }
+    }
+}
+impl std::error::Error for GetError {}
@@ -7,17 +7,352 @@
 //! // { name="a", pass="b", age=3 }
 //! ```
 
-use crate::model::layer225::{InlineTable, Keyval};
+use crate::model::{
+    layer110::Token,
+    layer210::{key::to_dotted_key, Key, LiteralValue, LiteralValueType, Ws},
+    layer220::{array::remove_layout, Array},
+    layer225::{GetError, InlineTable, Keyval, Val},
+};
+use chrono::prelude::{DateTime, Local, Utc};
+use chrono::FixedOffset;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use num_traits::{float::FloatCore, Num};
+use std::collections::HashMap;
 use std::fmt;
 
 impl Default for InlineTable {
     fn default() -> Self {
-        InlineTable { items: Vec::new() }
+        InlineTable {
+            items: Vec::new(),
+            layout: vec![Ws::default()],
+        }
     }
 }
 impl InlineTable {
     pub fn push_keyval(&mut self, m: &Keyval) {
         self.items.push(m.clone());
+        // After the key-value.
+        // キー・バリューの後。
+        self.layout.push(Ws::default());
+    }
+    /// `,`.
+    pub fn push_comma(&mut self) {
+        // Before the next key-value.
+        // 次のキー・バリューの前。
+        self.layout.push(Ws::default());
+    }
+    /// Whitespace at the current position.  
+    /// 現在の位置の空白。  
+    pub fn push_ws_token(&mut self, token: &Token) {
+        if let Some(m) = self.layout.last_mut() {
+            m.push_token(token);
+        }
+    }
+    /// Number of key-values.  
+    /// キー・バリューの数。  
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    /// Right value of the key. The key is the name without quotes, such as `a b` for `"a b" = 1`.  
+    /// A dotted key is written as in a path, such as `type.name`.  
+    /// キーの右値。キーは引用符を外した名前です。 `"a b" = 1` なら `a b` です。  
+    /// ドット区切りのキーは、 `type.name` のようにパスと同じく書きます。  
+    pub fn get(&self, key: &str) -> Option<&Val> {
+        self.items
+            .iter()
+            .find(|m| match m.key.get_segments() {
+                [name] => name == key,
+                segments => to_dotted_key(segments) == key,
+            })
+            .map(|m| &*m.val)
+    }
+    /// Key-values in the written order.  
+    /// 書かれた順のキー・バリュー。  
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &Val)> {
+        self.items.iter().map(|m| (&*m.key, &*m.val))
+    }
+    /// Key-values and layout. For visitors.  
+    /// キー・バリューとレイアウト。ビジター用。  
+    pub(crate) fn parts(&self) -> (&[Keyval], &[Ws]) {
+        (&self.items, &self.layout)
+    }
+    /// Bytes from `{` to the key-value. Same as `Display`.  
+    /// `{` からキー・バリューまでのバイト数。 `Display` と同じです。  
+    pub(crate) fn keyval_offset(&self, index: usize) -> Option<usize> {
+        if self.items.len() <= index {
+            return None;
+        }
+        let mut offset = 1;
+        for (i, item) in self.items[..index].iter().enumerate() {
+            offset += self.layout[2 * i].to_string().len()
+                + item.to_string().len()
+                + self.layout[2 * i + 1].to_string().len()
+                + 1;
+        }
+        Some(offset + self.layout[2 * index].to_string().len())
+    }
+    pub(crate) fn parts_mut(&mut self) -> (&mut [Keyval], &mut [Ws]) {
+        (&mut self.items, &mut self.layout)
+    }
+    /// Removes the key-value.  
+    /// キー・バリューを取り除きます。  
+    pub(crate) fn remove(&mut self, index: usize) -> Option<Keyval> {
+        if self.items.len() <= index {
+            return None;
+        }
+        remove_layout(&mut self.layout, index, self.items.len());
+        Some(self.items.remove(index))
+    }
+    /// Contains key.  
+    /// キーを含むか？  
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Right inline table of `left = { x = 1 }`.  
+    /// キー・バリューの右のインライン・テーブル。  
+    pub fn get_inline_table_by_key(&self, key: &str) -> Result<Option<&InlineTable>, GetError> {
+        match self.get(key) {
+            Some(Val::InlineTable(m)) => Ok(Some(m)),
+            Some(val) => {
+                Err(GetError::type_mismatch("inline table", val.type_name()).with_parent(key))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Right array of `left = [1, 2, 3]`.  
+    /// キー・バリューの右の配列。  
+    pub fn get_array_by_key(&self, key: &str) -> Result<Option<&Array>, GetError> {
+        match self.get(key) {
+            Some(Val::Array(m)) => Ok(Some(m)),
+            Some(val) => Err(GetError::type_mismatch("array", val.type_name()).with_parent(key)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get a [-1, 0, 1].
+    ///
+    /// The error names the item. Example: `ports[2] is a string, not an integer.`  
+    pub fn get_int_array_by_key<T: Num + std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<T>>, GetError>
+    where
+        <T as num_traits::Num>::FromStrRadixErr: std::fmt::Display,
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.to_array_by_key(key, |m| m.to_int_vector())
+    }
+
+    /// Get a [0.0, 0.5, 1.0].
+    pub fn get_float_array_by_key<T: FloatCore + Num + std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<T>>, GetError>
+    where
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.to_array_by_key(key, |m| m.to_float_vector())
+    }
+
+    /// Get a [true, false].
+    pub fn get_bool_array_by_key(&self, key: &str) -> Result<Option<Vec<bool>>, GetError> {
+        self.to_array_by_key(key, |m| m.to_bool_vector())
+    }
+
+    /// Get a [1979-05-27, 1979-05-28].
+    ///
+    /// `T` is a `chrono` type, such as `NaiveDate` or `DateTime<Utc>`.  
+    pub fn get_datetime_array_by_key<T: std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<T>>, GetError>
+    where
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.to_array_by_key(key, |m| m.to_datetime_vector())
+    }
+
+    /// Get a [[1, 2], [3]]. Each array is converted by `f`.
+    pub fn get_nested_array_by_key<T, F>(&self, key: &str, f: F) -> Result<Option<Vec<T>>, GetError>
+    where
+        F: Fn(&Array) -> Result<T, GetError>,
+    {
+        self.to_array_by_key(key, |m| m.to_nested_vector(&f))
+    }
+
+    /// Get a [ { x = 1 }, { x = 2 } ] as maps.
+    pub fn get_inline_table_array_by_key(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<HashMap<String, Val>>>, GetError> {
+        self.to_array_by_key(key, |m| m.to_inline_table_vector())
+    }
+
+    /// Get a ["a", 'b', '"c"'].
+    pub fn get_string_array_by_key(&self, key: &str) -> Result<Option<Vec<String>>, GetError> {
+        self.to_array_by_key(key, |m| Ok(m.to_string_vector()))
+    }
+
+    /// Right of `left = right`.  
+    /// キー・バリューの右値。  
+    pub fn get_literal_string_by_key(&self, key: &str) -> Option<&LiteralValue> {
+        if let Some(Val::LiteralValue(m)) = self.get(key) {
+            return Some(m);
+        }
+        None
+    }
+
+    pub fn get_i128_by_key_v2(&self, key: &str) -> Result<Option<i128>, GetError> {
+        self.get_int_by_key_v2(key)
+    }
+    pub fn get_isize_by_key_v2(&self, key: &str) -> Result<Option<isize>, GetError> {
+        self.get_int_by_key_v2(key)
+    }
+    pub fn get_u128_by_key_v2(&self, key: &str) -> Result<Option<u128>, GetError> {
+        self.get_int_by_key_v2(key)
+    }
+    pub fn get_usize_by_key_v2(&self, key: &str) -> Result<Option<usize>, GetError> {
+        self.get_int_by_key_v2(key)
+    }
+    pub fn get_f64_by_key_v2(&self, key: &str) -> Result<Option<f64>, GetError> {
+        self.get_float_by_key_v2(key)
+    }
+
+    /// Right integer of `left = 123`.  
+    /// キー・バリューの右の整数値。  
+    ///
+    /// # Errors
+    ///
+    /// `port = 70000 does not fit in u16.`  
+    pub fn get_int_by_key_v2<T: Num + std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, GetError>
+    where
+        <T as num_traits::Num>::FromStrRadixErr: std::fmt::Display,
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.to_literal_value_by_key(key, LiteralValueType::Integer, |m| {
+            m.to_int()
+                .map_err(|_| GetError::out_of_range(&m.to_string(), std::any::type_name::<T>()))
+        })
+    }
+
+    /// Right float of `left = 1.2`.  
+    /// キー・バリューの右の浮動小数点数。  
+    pub fn get_float_by_key_v2<T: FloatCore + Num + std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, GetError>
+    where
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.to_literal_value_by_key(key, LiteralValueType::Float, |m| {
+            m.to_float().map_err(|why| GetError::invalid_format(&why))
+        })
+    }
+
+    /// Right string of `left = "abc"`.  
+    /// キー・バリューの右の文字列。  
+    pub fn get_string_by_key(&self, key: &str) -> Option<String> {
+        match self.get(key) {
+            Some(Val::BasicString(m)) => Some(m.value()),
+            Some(Val::LiteralString(m)) => Some(m.value()),
+            _ => None,
+        }
+    }
+
+    /// Right boolean of `left = true`.  
+    /// キー・バリューの右の論理値。  
+    pub fn get_bool_by_key(&self, key: &str) -> Option<bool> {
+        match self.get_literal_string_by_key(key) {
+            Some(m) if m.get_type() == Some(LiteralValueType::Boolean) => m.to_bool().ok(),
+            _ => None,
+        }
+    }
+
+    /// DateTime. UTC.  
+    /// 日付と時刻。協定世界時。  
+    pub fn get_datetime_utc_by_key(&self, key: &str) -> Option<DateTime<Utc>> {
+        self.parse_literal_value_by_key(key)
+    }
+
+    /// DateTime. Local.  
+    /// 日付と時刻。ローカル時。  
+    pub fn get_datetime_local_by_key(&self, key: &str) -> Option<DateTime<Local>> {
+        self.parse_literal_value_by_key(key)
+    }
+
+    /// DateTime. Fixed offset.  
+    /// 日付と時刻。オフセット。  
+    pub fn get_datetime_fixed_offset_by_key(&self, key: &str) -> Option<DateTime<FixedOffset>> {
+        self.parse_literal_value_by_key(key)
+    }
+
+    /// DateTime. Naive.  
+    /// 日付と時刻。ナイーブ。  
+    pub fn get_naive_datetime_by_key(&self, key: &str) -> Option<NaiveDateTime> {
+        self.parse_literal_value_by_key(key)
+    }
+
+    /// Date. Naive.  
+    /// 日付。ナイーブ。  
+    pub fn get_naive_date_by_key(&self, key: &str) -> Option<NaiveDate> {
+        self.parse_literal_value_by_key(key)
+    }
+
+    /// Time. Naive.  
+    /// 日時。ナイーブ。  
+    pub fn get_naive_time_by_key(&self, key: &str) -> Option<NaiveTime> {
+        self.parse_literal_value_by_key(key)
+    }
+
+    fn parse_literal_value_by_key<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
+        match self.get_literal_string_by_key(key) {
+            Some(m) => m.to_string().parse().ok(),
+            None => None,
+        }
+    }
+    fn to_array_by_key<T, F>(&self, key: &str, f: F) -> Result<Option<T>, GetError>
+    where
+        F: Fn(&Array) -> Result<T, GetError>,
+    {
+        if let Some(array) = self.get_array_by_key(key)? {
+            return match f(array) {
+                Ok(x) => Ok(Some(x)),
+                Err(why) => Err(why.with_parent(key)),
+            };
+        }
+        Ok(None)
+    }
+    /// Other literal types are a mismatch. Strings, arrays and tables are `None`, same as the document.  
+    /// 他のリテラルの型は不一致です。文字列、配列、テーブルはドキュメントと同じく `None` です。  
+    fn to_literal_value_by_key<T, F>(
+        &self,
+        key: &str,
+        expected: LiteralValueType,
+        f: F,
+    ) -> Result<Option<T>, GetError>
+    where
+        F: Fn(&LiteralValue) -> Result<T, GetError>,
+    {
+        match self.get(key) {
+            Some(Val::LiteralValue(m)) if m.get_type() == Some(expected) => match f(m) {
+                Ok(n) => Ok(Some(n)),
+                Err(why) => Err(why.with_parent(key)),
+            },
+            Some(val @ Val::LiteralValue(_)) => {
+                Err(GetError::type_mismatch(expected.name(), val.type_name()).with_parent(key))
+            }
+            _ => Ok(None),
+        }
     }
     pub fn to_debug_string(&self) -> String {
         format!("{:?}", self)
@@ -28,10 +363,25 @@ impl InlineTable {
 }
 impl fmt::Display for InlineTable {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // `layout` is `[before 0, after 0, before 1, after 1, ...]`. `[before 0]` if empty.
+        // `layout` は `[項目0の前, 項目0の後, 項目1の前, 項目1の後, ...]` です。空なら `[項目0の前]` 。
         let mut buf = String::new();
-        for item in &self.items {
-            buf.push_str(&format!("{},", item))
+        buf.push('{');
+        for (i, item) in self.items.iter().enumerate() {
+            buf.push_str(&format!(
+                "{}{}{}",
+                self.layout[2 * i],
+                item,
+                self.layout[2 * i + 1]
+            ));
+            if 2 * i + 2 < self.layout.len() {
+                buf.push(',');
+            }
+        }
+        if 2 * self.items.len() < self.layout.len() {
+            buf.push_str(&self.layout[2 * self.items.len()].to_string());
         }
+        buf.push('}');
         write!(f, "{}", buf)
     }
 }
@@ -2,7 +2,7 @@
 //! キー値モデル。  
 
 use crate::model::{
-    layer210::Key,
+    layer210::{Key, Ws},
     layer225::{Keyval, Val},
 };
 use std::fmt;
@@ -11,9 +11,16 @@ impl Keyval {
     pub fn new(key: &Key, value: &Val) -> Self {
         Keyval {
             key: Box::new(key.clone()),
+            ws1: Ws::default(),
+            ws2: Ws::default(),
             val: Box::new(value.clone()),
         }
     }
+    /// Bytes from the key to the value.  
+    /// キーから値までのバイト数。  
+    pub(crate) fn val_offset(&self) -> usize {
+        self.key.to_string().len() + self.ws1.to_string().len() + 1 + self.ws2.to_string().len()
+    }
     pub fn to_debug_string(&self) -> String {
         format!("{:?}", self)
     }
@@ -23,7 +30,7 @@ impl Keyval {
 }
 impl fmt::Display for Keyval {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}={}", self.key, self.val)
+        write!(f, "{}{}={}{}", self.key, self.ws1, self.ws2, self.val)
     }
 }
 impl fmt::Debug for Keyval {
@@ -1,9 +1,11 @@
+pub mod from_toml;
+pub mod get_error;
 pub mod inline_table;
 pub mod keyval;
 pub mod val;
 
 use crate::model::{
-    layer210::{BasicString, Key, LiteralString, LiteralValue},
+    layer210::{BasicString, Key, LiteralString, LiteralValue, Ws},
     layer220::Array,
 };
 
@@ -12,6 +14,12 @@ use crate::model::{
 #[derive(Clone)]
 pub struct Keyval {
     pub key: Box<Key>,
+    /// Whitespace before `=`.  
+    /// `=` の前の空白。  
+    pub ws1: Ws,
+    /// Whitespace after `=`.  
+    /// `=` の後の空白。  
+    pub ws2: Ws,
     /// Right value. Recursive.
     /// 右値。 再帰的。
     pub val: Box<Val>,
@@ -38,4 +46,81 @@ pub enum Val {
 #[derive(Clone)]
 pub struct InlineTable {
     items: Vec<Keyval>,
+    /// Whitespace before and after each key-value.  
+    /// 各キー・バリューの前後の空白。  
+    layout: Vec<Ws>,
+}
+
+/// Converts a right value into a Rust value. Used by `TomlDocument::get`.  
+/// You can implement it for your own types, such as enums and newtypes.  
+/// 右値を Rust の値に変換します。 `TomlDocument::get` で使います。  
+/// 列挙型やニュータイプなど、自分の型に実装することもできます。  
+///
+/// # Examples
+///
+/// ```
+/// // struct Port(u16);
+/// // impl FromToml for Port {
+/// //     fn from_toml(val: &Val) -> Result<Self, GetError> {
+/// //         Ok(Port(u16::from_toml(val)?))
+/// //     }
+/// // }
+/// // let port: Port = doc.get("server.port")?;
+/// ```
+pub trait FromToml: Sized {
+    /// # Errors
+    ///
+    /// The path is empty, or relative like `[2]`. The caller puts its path in front.  
+    /// パスは空か、 `[2]` のような相対です。呼び出し元が自分のパスを前に付けます。  
+    fn from_toml(val: &Val) -> Result<Self, GetError>;
+    /// When the key is not found. `GetError::NotFound` by default, `None` for `Option<T>`.  
+    /// キーが見つからないとき。既定では `GetError::NotFound` 、 `Option<T>` では `None` 。  
+    fn from_missing() -> Result<Self, GetError> {
+        Err(GetError::NotFound {
+            path: String::new(),
+        })
+    }
+}
+
+/// Why a typed get failed. `path` is like `servers.alpha.ports[2]`.  
+/// `span` is where the value is written. `None` until the document fills it in.  
+/// 型付きゲットが失敗した理由。 `span` は値が書かれている位置です。ドキュメントが埋めるまでは `None` 。  
+#[derive(Clone, Debug, PartialEq)]
+pub enum GetError {
+    /// No value, so no span.  
+    /// 値が無いので、位置もありません。  
+    NotFound { path: String },
+    /// `expected` and `found` are type names, such as `integer` and `string`.  
+    /// `expected` と `found` は `integer` や `string` のような型の名前です。  
+    TypeMismatch {
+        path: String,
+        expected: &'static str,
+        found: &'static str,
+        span: Option<Span>,
+    },
+    /// `300` for `u8`.
+    OutOfRange {
+        path: String,
+        why: String,
+        span: Option<Span>,
+    },
+    /// The text can not be read as the type. Such as `1979-05-27` for `DateTime<Utc>`.  
+    /// テキストをその型として読めません。
+    InvalidFormat {
+        path: String,
+        why: String,
+        span: Option<Span>,
+    },
+}
+
+/// Where a value is written. `start` and `end` are byte offsets in the document text.  
+/// `line` and `column` start from 1. `column` counts characters.  
+/// 値が書かれている位置。 `start` と `end` はドキュメント・テキストのバイト位置です。  
+/// `line` と `column` は 1 から数えます。 `column` は文字数です。  
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
 }
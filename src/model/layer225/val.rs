@@ -1,9 +1,39 @@
 //! Right value model.  
 //! 右値モデル。  
 
-use crate::model::layer225::Val;
+use crate::model::{layer225::Val, layer230::Expression};
+use crate::Toml;
 use std::fmt;
 
+impl Val {
+    /// Right value from TOML text, such as `14` or `[1, 2]`.  
+    /// `14` や `[1, 2]` のような、TOMLテキストからの右値。  
+    pub(crate) fn parse(text: &str) -> Result<Val, String> {
+        let doc = Toml::from_string(&format!("v = {}", text));
+        if let Some(Expression::Keyval(_ws1, keyval, _ws2, _comment, _newline)) =
+            doc.elements.first()
+        {
+            if doc.elements.len() == 1 && keyval.val.to_string() == text {
+                return Ok(*keyval.val.clone());
+            }
+        }
+        Err(format!("`{}` is not a TOML value.", text))
+    }
+    /// Name of the type. For messages.  
+    /// 型の名前。メッセージ用。  
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Val::Array(_) => "array",
+            Val::BasicString(_) | Val::LiteralString(_) => "string",
+            Val::InlineTable(_) => "inline table",
+            Val::LiteralValue(m) => match m.get_type() {
+                Some(type_) => type_.name(),
+                None => "literal value",
+            },
+        }
+    }
+}
+
 impl fmt::Display for Val {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -3,10 +3,12 @@
 
 use crate::model::layer230::Ws;
 use crate::model::{
-    layer210::Comment,
+    layer110::Token,
+    layer210::{Comment, Newline},
     layer225::Keyval,
     layer230::{Expression, HeaderOfArrayOfTable, HeaderOfTable},
 };
+use crate::Toml;
 use std::fmt;
 
 impl Expression {
@@ -14,7 +16,7 @@ impl Expression {
         Expression::HeaderOfArrayOfTable(m.clone())
     }
     pub fn from_empty_line(ws: &Ws, comment: &Comment) -> Self {
-        Expression::EmptyLine(ws.clone(), Some(comment.clone()))
+        Expression::EmptyLine(ws.clone(), Some(comment.clone()), Newline::default())
     }
     pub fn from_keyval(ws1: &Ws, keyval: &Keyval, ws2: &Ws, comment: &Comment) -> Self {
         Expression::Keyval(
@@ -22,11 +24,50 @@ impl Expression {
             keyval.clone(),
             ws2.clone(),
             Some(comment.clone()),
+            Newline::default(),
         )
     }
     pub fn from_header_of_table(m: &HeaderOfTable) -> Self {
         Expression::HeaderOfTable(m.clone())
     }
+    /// `\r` or `\n` at the end of the line.  
+    /// 行末の `\r` か `\n` 。  
+    pub fn push_newline_token(&mut self, token: &Token) {
+        match self {
+            Expression::HeaderOfArrayOfTable(m) => m.newline.push_token(token),
+            Expression::EmptyLine(_ws, _comment, newline) => newline.push_token(token),
+            Expression::Keyval(_ws1, _keyval, _ws2, _comment, newline) => newline.push_token(token),
+            Expression::HeaderOfTable(m) => m.newline.push_token(token),
+        }
+    }
+    /// `\r\n` or `\n` at the end of the line. Empty at the end of file.  
+    /// 行末の `\r\n` か `\n` 。ファイルの終わりでは空。  
+    pub fn newline(&self) -> &Newline {
+        match self {
+            Expression::HeaderOfArrayOfTable(m) => &m.newline,
+            Expression::EmptyLine(_ws, _comment, newline) => newline,
+            Expression::Keyval(_ws1, _keyval, _ws2, _comment, newline) => newline,
+            Expression::HeaderOfTable(m) => &m.newline,
+        }
+    }
+    pub(crate) fn newline_mut(&mut self) -> &mut Newline {
+        match self {
+            Expression::HeaderOfArrayOfTable(m) => &mut m.newline,
+            Expression::EmptyLine(_ws, _comment, newline) => newline,
+            Expression::Keyval(_ws1, _keyval, _ws2, _comment, newline) => newline,
+            Expression::HeaderOfTable(m) => &mut m.newline,
+        }
+    }
+    /// Lines from TOML text, such as `[a]\nb = 1\n`.  
+    /// `[a]\nb = 1\n` のような、TOMLテキストからの行。  
+    pub(crate) fn parse_lines(text: &str) -> Result<Vec<Expression>, String> {
+        let doc = Toml::from_string(text);
+        if doc.to_string() == text {
+            Ok(doc.elements)
+        } else {
+            Err(format!("`{}` is not TOML.", text))
+        }
+    }
     pub fn to_debug_string(&self) -> String {
         format!("{:?}", self)
     }
@@ -37,30 +78,36 @@ impl Expression {
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Expression::HeaderOfArrayOfTable(m) => write!(f, "{}", m),
-            Expression::EmptyLine(ws, comment) => write!(
+            Expression::HeaderOfArrayOfTable(m) => write!(
                 f,
-                "{}{}",
-                ws,
-                if let Some(comment) = comment {
-                    comment.to_string()
-                } else {
-                    "".to_string()
-                }
+                "{}{}{}{}{}",
+                m.ws1,
+                m,
+                m.ws2,
+                comment_to_string(&m.comment),
+                m.newline
             ),
-            Expression::Keyval(ws1, keyval, ws2, comment) => write!(
+            Expression::EmptyLine(ws, comment, newline) => {
+                write!(f, "{}{}{}", ws, comment_to_string(comment), newline)
+            }
+            Expression::Keyval(ws1, keyval, ws2, comment, newline) => write!(
                 f,
-                "{}{}{}{}",
+                "{}{}{}{}{}",
                 ws1,
                 keyval,
                 ws2,
-                if let Some(comment) = comment {
-                    comment.to_string()
-                } else {
-                    "".to_string()
-                }
+                comment_to_string(comment),
+                newline
+            ),
+            Expression::HeaderOfTable(m) => write!(
+                f,
+                "{}{}{}{}{}",
+                m.ws1,
+                m,
+                m.ws2,
+                comment_to_string(&m.comment),
+                m.newline
             ),
-            Expression::HeaderOfTable(m) => write!(f, "{}", m),
         }
     }
 }
@@ -68,11 +115,24 @@ impl fmt::Debug for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Expression::HeaderOfArrayOfTable(m) => write!(f, "{:?}", m),
-            Expression::EmptyLine(ws, comment) => write!(f, "{:?}{:?}", ws, comment),
-            Expression::Keyval(ws1, keyval, ws2, comment) => {
-                write!(f, "{:?}{:?}{:?}{:?}", ws1, keyval, ws2, comment)
+            Expression::EmptyLine(ws, comment, newline) => {
+                write!(f, "{:?}{:?}{:?}", ws, comment, newline)
+            }
+            Expression::Keyval(ws1, keyval, ws2, comment, newline) => {
+                write!(
+                    f,
+                    "{:?}{:?}{:?}{:?}{:?}",
+                    ws1, keyval, ws2, comment, newline
+                )
             }
             Expression::HeaderOfTable(m) => write!(f, "{:?}", m),
         }
     }
 }
+fn comment_to_string(comment: &Option<Comment>) -> String {
+    if let Some(comment) = comment {
+        comment.to_string()
+    } else {
+        "".to_string()
+    }
+}
@@ -7,12 +7,23 @@
 //! // [[name.name.name]]
 //! ```
 
-use crate::model::{layer110::Token, layer230::HeaderOfArrayOfTable};
+use crate::model::{
+    layer110::Token,
+    layer210::{Key, Newline, Ws},
+    layer230::HeaderOfArrayOfTable,
+};
 use std::fmt;
 
 impl Default for HeaderOfArrayOfTable {
     fn default() -> Self {
-        HeaderOfArrayOfTable { tokens: Vec::new() }
+        HeaderOfArrayOfTable {
+            tokens: Vec::new(),
+            key: Key::default(),
+            ws1: Ws::default(),
+            ws2: Ws::default(),
+            comment: None,
+            newline: Newline::default(),
+        }
     }
 }
 impl HeaderOfArrayOfTable {
@@ -35,7 +46,7 @@ impl fmt::Display for HeaderOfArrayOfTable {
         for token in &self.tokens {
             buf.push_str(&token.to_string());
         }
-        write!(f, "{}", buf)
+        write!(f, "[[{}]]", buf)
     }
 }
 impl fmt::Debug for HeaderOfArrayOfTable {
@@ -7,12 +7,23 @@
 //! // [name.name.name]
 //! ```
 
-use crate::model::{layer110::Token, layer230::HeaderOfTable};
+use crate::model::{
+    layer110::Token,
+    layer210::{Key, Newline, Ws},
+    layer230::HeaderOfTable,
+};
 use std::fmt;
 
 impl Default for HeaderOfTable {
     fn default() -> Self {
-        HeaderOfTable { tokens: Vec::new() }
+        HeaderOfTable {
+            tokens: Vec::new(),
+            key: Key::default(),
+            ws1: Ws::default(),
+            ws2: Ws::default(),
+            comment: None,
+            newline: Newline::default(),
+        }
     }
 }
 impl HeaderOfTable {
@@ -35,7 +46,7 @@ impl fmt::Display for HeaderOfTable {
         for token in &self.tokens {
             buf.push_str(&token.to_string());
         }
-        write!(f, "{}", buf)
+        write!(f, "[{}]", buf)
     }
 }
 impl fmt::Debug for HeaderOfTable {
@@ -4,14 +4,27 @@ pub mod header_of_table;
 
 use crate::model::{
     layer110::Token,
-    layer210::{Comment, Ws},
+    layer210::{Comment, Key, Newline, Ws},
     layer225::Keyval,
 };
 
 /// WIP.  
 #[derive(Clone)]
 pub struct HeaderOfArrayOfTable {
+    /// Source text between the brackets, as written.  
+    /// 角括弧の間の、書かれたままのソース・テキスト。  
     pub tokens: Vec<Token>,
+    /// Table name.  
+    /// テーブル名。  
+    pub key: Key,
+    /// Whitespace before `[[`.  
+    /// `[[` の前の空白。  
+    pub ws1: Ws,
+    /// Whitespace after `]]`.  
+    /// `]]` の後の空白。  
+    pub ws2: Ws,
+    pub comment: Option<Comment>,
+    pub newline: Newline,
 }
 
 /// Either a Empty-line, Comment, Key Value, Table or a Array-of-table.  
@@ -19,13 +32,26 @@ pub struct HeaderOfArrayOfTable {
 #[derive(Clone)]
 pub enum Expression {
     HeaderOfArrayOfTable(HeaderOfArrayOfTable),
-    EmptyLine(Ws, Option<Comment>),
-    Keyval(Ws, Keyval, Ws, Option<Comment>),
+    EmptyLine(Ws, Option<Comment>, Newline),
+    Keyval(Ws, Keyval, Ws, Option<Comment>, Newline),
     HeaderOfTable(HeaderOfTable),
 }
 
 /// WIP.  
 #[derive(Clone)]
 pub struct HeaderOfTable {
+    /// Source text between the brackets, as written.  
+    /// 角括弧の間の、書かれたままのソース・テキスト。  
     pub tokens: Vec<Token>,
+    /// Table name.  
+    /// テーブル名。  
+    pub key: Key,
+    /// Whitespace before `[`.  
+    /// `[` の前の空白。  
+    pub ws1: Ws,
+    /// Whitespace after `]`.  
+    /// `]` の後の空白。  
+    pub ws2: Ws,
+    pub comment: Option<Comment>,
+    pub newline: Newline,
 }
@@ -0,0 +1,56 @@
+//! Key index of a document.  
+//! ドキュメントのキー索引。  
+//!
+//! # Examples
+//!
+//! ```
+//! // let index = KeyIndex::new(doc.elements());
+//! // let elem = index.get_by_key("age").map(|i| &doc.elements()[i]);
+//! ```
+
+use crate::model::{layer230::Expression, layer310::KeyIndex};
+use std::collections::HashMap;
+
+impl Default for KeyIndex {
+    fn default() -> Self {
+        KeyIndex {
+            by_key: HashMap::new(),
+            by_segments: HashMap::new(),
+        }
+    }
+}
+impl KeyIndex {
+    pub(crate) fn new(elements: &[Expression]) -> Self {
+        let mut index = KeyIndex::default();
+        // `None` in an array of tables.
+        // テーブルの配列の中では `None` 。
+        let mut table: Option<&[String]> = Some(&[]);
+        for (i, elem) in elements.iter().enumerate() {
+            match elem {
+                Expression::HeaderOfArrayOfTable(_) => table = None,
+                Expression::HeaderOfTable(m) => table = Some(m.key.get_segments()),
+                Expression::EmptyLine(_ws, _comment, _newline) => {}
+                Expression::Keyval(_ws1, keyval, _ws2, _comment, _newline) => {
+                    index.by_key.entry(keyval.key.to_string()).or_insert(i);
+                    if let Some(table) = table {
+                        index
+                            .by_segments
+                            .entry([table, keyval.key.get_segments()].concat())
+                            .or_insert(i);
+                    }
+                }
+            }
+        }
+        index
+    }
+    /// Position of `left = right` by `left`, as written.  
+    /// 書かれたままの `left` による `left = right` の位置。  
+    pub(crate) fn get_by_key(&self, key: &str) -> Option<usize> {
+        self.by_key.get(key).copied()
+    }
+    /// Position of `left = right` by the table header and `left`.  
+    /// テーブル・ヘッダーと `left` による `left = right` の位置。  
+    pub(crate) fn get_by_segments(&self, segments: &[String]) -> Option<usize> {
+        self.by_segments.get(segments).copied()
+    }
+}
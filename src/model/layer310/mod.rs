@@ -1,6 +1,21 @@
+pub mod key_index;
+pub mod path;
+pub mod query;
+pub mod table;
 pub mod toml_document;
+pub mod value;
+pub mod visit;
 
-use crate::model::layer230::Expression;
+use crate::model::{
+    layer210::{
+        BasicString, Comment, Key, LiteralString, LiteralValue, Newline, Ws, WsCommentNewline,
+    },
+    layer220::{Array, ItemValue},
+    layer225::{InlineTable, Keyval, Val},
+    layer230::{Expression, HeaderOfArrayOfTable, HeaderOfTable},
+};
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// It has multiple `document_element`.  
 /// 複数の `縦幅を持つ行` を持ちます。  
@@ -8,5 +23,220 @@ use crate::model::layer230::Expression;
 pub struct TomlDocument {
     /// Line with height.
     /// 縦幅を持つ行。
-    pub elements: Vec<Expression>,
+    ///
+    /// Read it with `elements()`, edit it with `elements_mut()`, so the caches are dropped.  
+    /// `elements()` で読み、 `elements_mut()` で編集してください。キャッシュを捨てるためです。  
+    pub(crate) elements: Vec<Expression>,
+    /// `to_value()` made on the first `doc["key"]`.  
+    /// 最初の `doc["key"]` で作った `to_value()` 。  
+    value: OnceLock<Result<Value, String>>,
+    /// Made on the first lookup by key.  
+    /// キーによる最初の検索で作ります。  
+    index: OnceLock<KeyIndex>,
+}
+
+/// Positions in `elements` by key, so a lookup does not scan the document.  
+/// Dropped on each edit. The first one wins, same as the scan.  
+/// キーによる `elements` の中の位置。検索でドキュメントを走査しません。  
+/// 編集のたびに捨てます。走査と同じく、最初のものが勝ちます。  
+#[derive(Clone)]
+pub(crate) struct KeyIndex {
+    /// `left` of `left = right`, as written. Table headers are not taken into account.  
+    /// 書かれたままの `left = right` の `left` 。テーブル・ヘッダーは考慮しません。  
+    by_key: HashMap<String, usize>,
+    /// The table header, then the key. Not in an array of tables.  
+    /// テーブル・ヘッダー、それからキー。テーブルの配列の中は含みません。  
+    by_segments: HashMap<Vec<String>, usize>,
+}
+
+/// Owned data of a document, without the source text. Like `serde_json::Value`.  
+/// ソース・テキストを持たない、ドキュメントの所有データ。 `serde_json::Value` のようなものです。  
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    /// As written. Parse it with `chrono`.  
+    /// 書かれたまま。 `chrono` でパースしてください。  
+    Datetime(String),
+    Array(Vec<Value>),
+    Table(Table),
+    /// Nothing is there. `doc["no"]["such"][0]` gives it instead of panicking.  
+    /// `to_value()` never makes it.  
+    /// 何もありません。 `doc["no"]["such"][0]` はパニックせずにこれを返します。 `to_value()` は作りません。  
+    Absent,
+}
+
+/// Key-values in the order they were first written.  
+/// 最初に書かれた順のキー・バリュー。  
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table {
+    items: Vec<(String, Value)>,
+}
+
+/// `servers.alpha.ip`, `products[1].name`, `a."b.c"[0]`.  
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+/// A part of a path.  
+/// パスの一部。  
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum PathSegment {
+    /// `name` or `"b.c"`.
+    Key(String),
+    /// `[0]`.
+    Index(usize),
+}
+
+/// `servers.*.ip`, `products[*].sku`, `**.timeout_secs`, `products[?price > 10]`.  
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    steps: Vec<QueryStep>,
+}
+
+/// A part of a query.  
+/// クエリーの一部。  
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryStep {
+    /// `name` or `"b.c"`.
+    Key(String),
+    /// `[0]`.
+    Index(usize),
+    /// `*`. Every value in a table.  
+    /// テーブルの全ての値。  
+    AnyKey,
+    /// `[*]`. Every item in an array.  
+    /// 配列の全ての項目。  
+    AnyIndex,
+    /// `**`. The value itself and everything under it.  
+    /// その値自身と、その下の全て。  
+    Descendants,
+    /// `[?price > 10]`. Items in an array that match.  
+    /// 配列のうち、合う項目。  
+    Filter(QueryFilter),
+}
+
+/// `price > 10`. The left is a key of the item. The right is a number, string or boolean.  
+/// 左は項目のキー。右は数、文字列、論理値。  
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryFilter {
+    pub key: String,
+    pub op: QueryOp,
+    pub value: Value,
+}
+
+/// `==`, `!=`, `<`, `<=`, `>`, `>=`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueryOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Reads the model, node by node. Every method recurses by default.  
+/// Override the nodes you need, and call `visit::walk_*` to keep going down.  
+/// モデルをノードごとに読みます。全てのメソッドは既定で再帰します。  
+/// 必要なノードを上書きして、下へ進むには `visit::walk_*` を呼んでください。  
+///
+/// # Examples
+///
+/// ```
+/// // struct Keys(Vec<String>);
+/// // impl Visit for Keys {
+/// //     fn visit_keyval(&mut self, m: &Keyval) {
+/// //         self.0.push(m.key.to_string());
+/// //         visit::walk_keyval(self, m);
+/// //     }
+/// // }
+/// // let mut keys = Keys(Vec::new());
+/// // keys.visit_document(&doc);
+/// ```
+pub trait Visit {
+    fn visit_document(&mut self, m: &TomlDocument) {
+        visit::walk_document(self, m);
+    }
+    fn visit_expression(&mut self, m: &Expression) {
+        visit::walk_expression(self, m);
+    }
+    fn visit_header_of_table(&mut self, m: &HeaderOfTable) {
+        visit::walk_header_of_table(self, m);
+    }
+    fn visit_header_of_array_of_table(&mut self, m: &HeaderOfArrayOfTable) {
+        visit::walk_header_of_array_of_table(self, m);
+    }
+    fn visit_keyval(&mut self, m: &Keyval) {
+        visit::walk_keyval(self, m);
+    }
+    fn visit_val(&mut self, m: &Val) {
+        visit::walk_val(self, m);
+    }
+    fn visit_item_value(&mut self, m: &ItemValue) {
+        visit::walk_item_value(self, m);
+    }
+    fn visit_array(&mut self, m: &Array) {
+        visit::walk_array(self, m);
+    }
+    fn visit_inline_table(&mut self, m: &InlineTable) {
+        visit::walk_inline_table(self, m);
+    }
+    fn visit_ws_comment_newline(&mut self, m: &WsCommentNewline) {
+        visit::walk_ws_comment_newline(self, m);
+    }
+    fn visit_key(&mut self, _m: &Key) {}
+    fn visit_basic_string(&mut self, _m: &BasicString) {}
+    fn visit_literal_string(&mut self, _m: &LiteralString) {}
+    fn visit_literal_value(&mut self, _m: &LiteralValue) {}
+    fn visit_comment(&mut self, _m: &Comment) {}
+    fn visit_ws(&mut self, _m: &Ws) {}
+    fn visit_newline(&mut self, _m: &Newline) {}
+}
+
+/// Edits the model, node by node. Every method recurses by default, same as `Visit`.  
+/// To start below the document, reach the node through `elements_mut()`, which drops the caches.  
+/// モデルをノードごとに編集します。全てのメソッドは `Visit` と同じく、既定で再帰します。  
+/// ドキュメントより下から始めるなら、キャッシュを捨てる `elements_mut()` を通してノードへ届いてください。  
+pub trait VisitMut {
+    fn visit_document_mut(&mut self, m: &mut TomlDocument) {
+        visit::walk_document_mut(self, m);
+    }
+    fn visit_expression_mut(&mut self, m: &mut Expression) {
+        visit::walk_expression_mut(self, m);
+    }
+    fn visit_header_of_table_mut(&mut self, m: &mut HeaderOfTable) {
+        visit::walk_header_of_table_mut(self, m);
+    }
+    fn visit_header_of_array_of_table_mut(&mut self, m: &mut HeaderOfArrayOfTable) {
+        visit::walk_header_of_array_of_table_mut(self, m);
+    }
+    fn visit_keyval_mut(&mut self, m: &mut Keyval) {
+        visit::walk_keyval_mut(self, m);
+    }
+    fn visit_val_mut(&mut self, m: &mut Val) {
+        visit::walk_val_mut(self, m);
+    }
+    fn visit_item_value_mut(&mut self, m: &mut ItemValue) {
+        visit::walk_item_value_mut(self, m);
+    }
+    fn visit_array_mut(&mut self, m: &mut Array) {
+        visit::walk_array_mut(self, m);
+    }
+    fn visit_inline_table_mut(&mut self, m: &mut InlineTable) {
+        visit::walk_inline_table_mut(self, m);
+    }
+    fn visit_ws_comment_newline_mut(&mut self, m: &mut WsCommentNewline) {
+        visit::walk_ws_comment_newline_mut(self, m);
+    }
+    fn visit_key_mut(&mut self, _m: &mut Key) {}
+    fn visit_basic_string_mut(&mut self, _m: &mut BasicString) {}
+    fn visit_literal_string_mut(&mut self, _m: &mut LiteralString) {}
+    fn visit_literal_value_mut(&mut self, _m: &mut LiteralValue) {}
+    fn visit_comment_mut(&mut self, _m: &mut Comment) {}
+    fn visit_ws_mut(&mut self, _m: &mut Ws) {}
+    fn visit_newline_mut(&mut self, _m: &mut Newline) {}
 }
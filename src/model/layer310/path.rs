@@ -0,0 +1,210 @@
+//! Path model.  
+//! パス・モデル。  
+//!
+//! # Examples
+//!
+//! ```
+//! // servers.alpha.ip
+//! // products[1].name
+//! // a."b.c"[0]
+//! ```
+
+use crate::model::{
+    layer210::key::to_dotted_key,
+    layer310::{Path, PathSegment},
+};
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+impl Default for Path {
+    fn default() -> Self {
+        Path {
+            segments: Vec::new(),
+        }
+    }
+}
+impl Path {
+    /// Keys are bare or quoted, same as TOML. Escapes such as `\t` and `\u00E9` are decoded.  
+    /// Whitespace around `.` is allowed. An empty text is the root table.  
+    /// キーは TOML と同じく、裸か引用符付きです。 `\t` や `\u00E9` のようなエスケープは解きます。  
+    /// `.` の前後の空白は許します。空文字列はルート・テーブルです。  
+    ///
+    /// # Errors
+    ///
+    /// `` `a..b` is not a path. ``  
+    /// `` `a[1` has an unclosed `[`. ``  
+    pub fn parse(path: &str) -> Result<Path, String> {
+        let mut m = Path::default();
+        let mut chars = path.trim().chars().peekable();
+        if chars.peek().is_none() {
+            return Ok(m);
+        }
+        loop {
+            // Key.
+            // キー。
+            let key = match read_key(&mut chars, path)? {
+                Some(key) => key,
+                None => return Err(format!("`{}` is not a path.", path)),
+            };
+            m.push_key(&key);
+            // Indexes.
+            // 添え字。
+            while let Some('[') = chars.peek() {
+                chars.next();
+                let mut digits = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(ch) => digits.push(ch),
+                        None => return Err(format!("`{}` has an unclosed `[`.", path)),
+                    }
+                }
+                match digits.trim().parse() {
+                    Ok(n) => m.push_index(n),
+                    Err(_) => return Err(format!("`{}` is not a path.", path)),
+                }
+            }
+            skip_ws(&mut chars);
+            match chars.next() {
+                Some('.') => {}
+                None => return Ok(m),
+                Some(_) => return Err(format!("`{}` is not a path.", path)),
+            }
+            skip_ws(&mut chars);
+        }
+    }
+    pub(crate) fn from_segments(segments: &[PathSegment]) -> Self {
+        Path {
+            segments: segments.to_vec(),
+        }
+    }
+    pub fn push_key(&mut self, key: &str) {
+        self.segments.push(PathSegment::Key(key.to_string()));
+    }
+    pub fn push_index(&mut self, index: usize) {
+        self.segments.push(PathSegment::Index(index));
+    }
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+    /// `Some(["a", "b.c"])` for `a."b.c"`. `None` if it has an index.  
+    /// `a."b.c"` なら `Some(["a", "b.c"])` 。添え字があれば `None` 。  
+    pub fn to_keys(&self) -> Option<Vec<String>> {
+        self.segments
+            .iter()
+            .map(|m| match m {
+                PathSegment::Key(key) => Some(key.clone()),
+                PathSegment::Index(_) => None,
+            })
+            .collect()
+    }
+}
+/// Bare or quoted key. `None` if a key does not start here.  
+/// 裸か引用符付きのキー。ここからキーが始まらなければ `None` 。  
+pub(crate) fn read_key(chars: &mut Peekable<Chars>, text: &str) -> Result<Option<String>, String> {
+    let mut key = String::new();
+    match chars.peek() {
+        Some('"') => {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => key.push(read_escape(chars, text)?),
+                    Some(ch) => key.push(ch),
+                    None => return Err(format!("`{}` has an unclosed quote.", text)),
+                }
+            }
+        }
+        Some('\'') => {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(ch) => key.push(ch),
+                    None => return Err(format!("`{}` has an unclosed quote.", text)),
+                }
+            }
+        }
+        Some(ch) if is_bare_key_char(*ch) => {
+            while let Some(ch) = chars.peek() {
+                if !is_bare_key_char(*ch) {
+                    break;
+                }
+                key.push(*ch);
+                chars.next();
+            }
+        }
+        _ => return Ok(None),
+    }
+    Ok(Some(key))
+}
+/// The character after `\`, same as a basic string. `\t`, `\"`, `\u00E9`...  
+/// `\` の後の文字。基本文字列と同じです。  
+fn read_escape(chars: &mut Peekable<Chars>, text: &str) -> Result<char, String> {
+    let digits = match chars.next() {
+        Some('b') => return Ok('\u{0008}'),
+        Some('t') => return Ok('\t'),
+        Some('n') => return Ok('\n'),
+        Some('f') => return Ok('\u{000C}'),
+        Some('r') => return Ok('\r'),
+        Some('"') => return Ok('"'),
+        Some('\\') => return Ok('\\'),
+        Some('u') => 4,
+        Some('U') => 8,
+        Some(ch) => {
+            return Err(format!(
+                "`{}` has an invalid escape sequence `\\{}`.",
+                text,
+                ch.escape_debug()
+            ))
+        }
+        None => return Err(format!("`{}` has an unclosed quote.", text)),
+    };
+    let hex: String = chars.by_ref().take(digits).collect();
+    if hex.chars().count() != digits || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Err(format!(
+            "`{}` needs {} hexadecimal digits after `\\{}`.",
+            text,
+            digits,
+            if digits == 4 { 'u' } else { 'U' }
+        ));
+    }
+    match u32::from_str_radix(&hex, 16)
+        .ok()
+        .and_then(std::char::from_u32)
+    {
+        Some(ch) => Ok(ch),
+        None => Err(format!(
+            "`{}` has U+{}, which is not a Unicode scalar value.",
+            text, hex
+        )),
+    }
+}
+pub(crate) fn skip_ws(chars: &mut Peekable<Chars>) {
+    while let Some('\t' | ' ') = chars.peek() {
+        chars.next();
+    }
+}
+fn is_bare_key_char(ch: char) -> bool {
+    matches!(ch, 'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_')
+}
+impl fmt::Display for Path {
+    /// Keys are quoted when needed.  
+    /// キーは必要なら引用符で囲みます。  
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = String::new();
+        for m in &self.segments {
+            match m {
+                PathSegment::Key(key) => {
+                    if !buf.is_empty() {
+                        buf.push('.');
+                    }
+                    buf.push_str(&to_dotted_key(std::slice::from_ref(key)));
+                }
+                PathSegment::Index(index) => buf.push_str(&format!("[{}]", index)),
+            }
+        }
+        write!(f, "{}", buf)
+    }
+}
@@ -0,0 +1,270 @@
+//! Query model.  
+//! クエリー・モデル。  
+//!
+//! # Examples
+//!
+//! ```
+//! // servers.*.ip
+//! // products[*].sku
+//! // **.timeout_secs
+//! // products[?price > 10].name
+//! ```
+
+use crate::model::layer310::{
+    path::{read_key, skip_ws},
+    Path, Query, QueryFilter, QueryOp, QueryStep, Value,
+};
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
+
+impl Default for Query {
+    fn default() -> Self {
+        Query { steps: Vec::new() }
+    }
+}
+impl Query {
+    /// A path, plus `*`, `[*]`, `**` and `[?key op value]`.  
+    /// パスに加えて `*` 、 `[*]` 、 `**` 、 `[?key op value]` 。  
+    ///
+    /// # Errors
+    ///
+    /// `` `a[?b ~ 1]` is not a query. ``  
+    pub fn parse(query: &str) -> Result<Query, String> {
+        let mut m = Query::default();
+        let mut chars = query.trim().chars().peekable();
+        if chars.peek().is_none() {
+            return Ok(m);
+        }
+        loop {
+            if let Some('*') = chars.peek() {
+                chars.next();
+                if let Some('*') = chars.peek() {
+                    chars.next();
+                    m.steps.push(QueryStep::Descendants);
+                } else {
+                    m.steps.push(QueryStep::AnyKey);
+                }
+            } else {
+                match read_key(&mut chars, query)? {
+                    Some(key) => m.steps.push(QueryStep::Key(key)),
+                    None => return Err(not_a_query_message(query)),
+                }
+            }
+            while let Some('[') = chars.peek() {
+                chars.next();
+                skip_ws(&mut chars);
+                let step = match chars.peek() {
+                    Some('*') => {
+                        chars.next();
+                        QueryStep::AnyIndex
+                    }
+                    Some('?') => {
+                        chars.next();
+                        QueryStep::Filter(parse_filter(&mut chars, query)?)
+                    }
+                    _ => {
+                        let mut digits = String::new();
+                        while let Some(ch @ '0'..='9') = chars.peek() {
+                            digits.push(*ch);
+                            chars.next();
+                        }
+                        match digits.parse() {
+                            Ok(n) => QueryStep::Index(n),
+                            Err(_) => return Err(not_a_query_message(query)),
+                        }
+                    }
+                };
+                skip_ws(&mut chars);
+                if chars.next() != Some(']') {
+                    return Err(not_a_query_message(query));
+                }
+                m.steps.push(step);
+            }
+            skip_ws(&mut chars);
+            match chars.next() {
+                Some('.') => {}
+                None => return Ok(m),
+                Some(_) => return Err(not_a_query_message(query)),
+            }
+            skip_ws(&mut chars);
+        }
+    }
+    pub fn steps(&self) -> &[QueryStep] {
+        &self.steps
+    }
+    /// Every match under `value`, with its path. In the written order.  
+    /// `value` の下の全ての一致と、そのパス。書かれた順です。  
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<(Path, &'a Value)> {
+        let mut matches = Vec::new();
+        select(value, Path::default(), &self.steps, &mut matches);
+        matches
+    }
+}
+fn select<'a>(
+    value: &'a Value,
+    path: Path,
+    steps: &[QueryStep],
+    matches: &mut Vec<(Path, &'a Value)>,
+) {
+    let (step, rest) = match steps.split_first() {
+        Some(m) => m,
+        None => {
+            if !value.is_absent() {
+                matches.push((path, value));
+            }
+            return;
+        }
+    };
+    match step {
+        QueryStep::Key(key) => {
+            if let Value::Table(table) = value {
+                if let Some(m) = table.get(key) {
+                    select(m, child_path(&path, key), rest, matches);
+                }
+            }
+        }
+        QueryStep::Index(index) => {
+            if let Value::Array(vec) = value {
+                if let Some(m) = vec.get(*index) {
+                    select(m, index_path(&path, *index), rest, matches);
+                }
+            }
+        }
+        QueryStep::AnyKey => {
+            if let Value::Table(table) = value {
+                for (key, m) in table.iter() {
+                    select(m, child_path(&path, key), rest, matches);
+                }
+            }
+        }
+        QueryStep::AnyIndex => {
+            if let Value::Array(vec) = value {
+                for (i, m) in vec.iter().enumerate() {
+                    select(m, index_path(&path, i), rest, matches);
+                }
+            }
+        }
+        QueryStep::Descendants => {
+            select(value, path.clone(), rest, matches);
+            match value {
+                Value::Table(table) => {
+                    for (key, m) in table.iter() {
+                        select(m, child_path(&path, key), steps, matches);
+                    }
+                }
+                Value::Array(vec) => {
+                    for (i, m) in vec.iter().enumerate() {
+                        select(m, index_path(&path, i), steps, matches);
+                    }
+                }
+                _ => {}
+            }
+        }
+        QueryStep::Filter(filter) => {
+            if let Value::Array(vec) = value {
+                for (i, m) in vec.iter().enumerate() {
+                    if filter.is_match(m) {
+                        select(m, index_path(&path, i), rest, matches);
+                    }
+                }
+            }
+        }
+    }
+}
+fn child_path(path: &Path, key: &str) -> Path {
+    let mut m = path.clone();
+    m.push_key(key);
+    m
+}
+fn index_path(path: &Path, index: usize) -> Path {
+    let mut m = path.clone();
+    m.push_index(index);
+    m
+}
+
+impl QueryFilter {
+    /// The item is a table, and its value at the key compares true. Different types never match.  
+    /// 項目はテーブルで、そのキーの値の比較が真。型が違えば合いません。  
+    pub fn is_match(&self, item: &Value) -> bool {
+        let ordering = match (&item[self.key.as_str()], &self.value) {
+            (Value::Integer(a), Value::Integer(b)) => a.partial_cmp(b),
+            (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::String(a), Value::String(b)) | (Value::Datetime(a), Value::String(b)) => {
+                a.partial_cmp(b)
+            }
+            (Value::Boolean(a), Value::Boolean(b)) => match self.op {
+                QueryOp::Eq | QueryOp::Ne => a.partial_cmp(b),
+                _ => None,
+            },
+            _ => None,
+        };
+        match ordering {
+            Some(ordering) => match self.op {
+                QueryOp::Eq => ordering == Ordering::Equal,
+                QueryOp::Ne => ordering != Ordering::Equal,
+                QueryOp::Lt => ordering == Ordering::Less,
+                QueryOp::Le => ordering != Ordering::Greater,
+                QueryOp::Gt => ordering == Ordering::Greater,
+                QueryOp::Ge => ordering != Ordering::Less,
+            },
+            None => false,
+        }
+    }
+}
+/// `price > 10`, `name == "Nail"`, `"in stock" != false`.
+fn parse_filter(chars: &mut Peekable<Chars>, query: &str) -> Result<QueryFilter, String> {
+    skip_ws(chars);
+    let key = match read_key(chars, query)? {
+        Some(m) => m,
+        None => return Err(not_a_query_message(query)),
+    };
+    skip_ws(chars);
+    let mut op = String::new();
+    while let Some(ch @ ('=' | '!' | '<' | '>')) = chars.peek() {
+        op.push(*ch);
+        chars.next();
+    }
+    let op = match op.as_str() {
+        "==" => QueryOp::Eq,
+        "!=" => QueryOp::Ne,
+        "<" => QueryOp::Lt,
+        "<=" => QueryOp::Le,
+        ">" => QueryOp::Gt,
+        ">=" => QueryOp::Ge,
+        _ => return Err(not_a_query_message(query)),
+    };
+    skip_ws(chars);
+    let value = match chars.peek() {
+        Some('"' | '\'') => match read_key(chars, query)? {
+            Some(m) => Value::String(m),
+            None => return Err(not_a_query_message(query)),
+        },
+        _ => {
+            let mut s = String::new();
+            while let Some(ch) = chars.peek() {
+                if *ch == ']' || *ch == ' ' || *ch == '\t' {
+                    break;
+                }
+                s.push(*ch);
+                chars.next();
+            }
+            let n = s.replace('_', "");
+            if s == "true" || s == "false" {
+                Value::Boolean(s == "true")
+            } else if let Ok(n) = n.parse() {
+                Value::Integer(n)
+            } else if let Ok(n) = n.parse() {
+                Value::Float(n)
+            } else {
+                return Err(not_a_query_message(query));
+            }
+        }
+    };
+    Ok(QueryFilter { key, op, value })
+}
+fn not_a_query_message(query: &str) -> String {
+    format!("`{}` is not a query.", query)
+}
@@ -0,0 +1,138 @@
+//! Table of values.  
+//! 値のテーブル。  
+//!
+//! # Examples
+//!
+//! ```
+//! // let table = doc.to_value()?;
+//! // let port = table.as_table().unwrap().get("port");
+//! ```
+
+use crate::model::{
+    layer210::key::to_dotted_key,
+    layer310::{value::ABSENT, Table, Value},
+};
+use std::ops::Index;
+
+impl Default for Table {
+    fn default() -> Self {
+        Table { items: Vec::new() }
+    }
+}
+impl Table {
+    /// Number of keys.  
+    /// キーの数。  
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.items.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.items
+            .iter_mut()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+    /// Contains key.  
+    /// キーを含むか？  
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+    /// A new key goes last. An existing key keeps its place, and the old value is returned.  
+    /// 新しいキーは最後に入ります。既存のキーは位置を保ち、古い値を返します。  
+    pub fn insert(&mut self, key: &str, value: Value) -> Option<Value> {
+        if let Some(old) = self.get_mut(key) {
+            return Some(std::mem::replace(old, value));
+        }
+        self.items.push((key.to_string(), value));
+        None
+    }
+    /// Keys in order.  
+    /// 順番通りのキー。  
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.items.iter().map(|(k, _)| k)
+    }
+    /// Key-values in order.  
+    /// 順番通りのキー・バリュー。  
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.items.iter().map(|(k, v)| (k, v))
+    }
+
+    /// The table at the segments. Missing tables are made. An array of tables gives its last table.  
+    /// セグメントの位置のテーブル。無ければ作ります。テーブルの配列なら最後のテーブルです。  
+    pub(crate) fn get_table_by_segments_mut(
+        &mut self,
+        segments: &[String],
+    ) -> Result<&mut Table, String> {
+        let mut table = self;
+        for (i, segment) in segments.iter().enumerate() {
+            if !table.contains_key(segment) {
+                table.insert(segment, Value::Table(Table::default()));
+            }
+            table = match table.get_mut(segment) {
+                Some(Value::Table(m)) => m,
+                Some(Value::Array(vec)) => match vec.last_mut() {
+                    Some(Value::Table(m)) => m,
+                    _ => return Err(not_a_table_message(&segments[..=i])),
+                },
+                _ => return Err(not_a_table_message(&segments[..=i])),
+            };
+        }
+        Ok(table)
+    }
+    /// `a.b.c = 1`.
+    ///
+    /// # Errors
+    ///
+    /// `a.b.c is defined twice.`  
+    pub(crate) fn insert_by_segments(
+        &mut self,
+        segments: &[String],
+        value: Value,
+    ) -> Result<(), String> {
+        if let Some((last, parents)) = segments.split_last() {
+            let table = self.get_table_by_segments_mut(parents)?;
+            if table.contains_key(last) {
+                return Err(format!("{} is defined twice.", to_dotted_key(segments)));
+            }
+            table.insert(last, value);
+        }
+        Ok(())
+    }
+    /// `[[a.b]]`. Pushes a new table, and returns it.  
+    /// 新しいテーブルを追加して、それを返します。  
+    pub(crate) fn push_array_of_table_by_segments(
+        &mut self,
+        segments: &[String],
+    ) -> Result<&mut Table, String> {
+        if let Some((last, parents)) = segments.split_last() {
+            let table = self.get_table_by_segments_mut(parents)?;
+            if !table.contains_key(last) {
+                table.insert(last, Value::Array(Vec::new()));
+            }
+            if let Some(Value::Array(vec)) = table.get_mut(last) {
+                vec.push(Value::Table(Table::default()));
+                if let Some(Value::Table(m)) = vec.last_mut() {
+                    return Ok(m);
+                }
+            }
+        }
+        Err(not_a_table_message(segments))
+    }
+}
+/// `table["key"]`. `Value::Absent` if the key is not there.  
+/// キーが無ければ `Value::Absent` 。  
+impl Index<&str> for Table {
+    type Output = Value;
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).unwrap_or(&ABSENT)
+    }
+}
+/// `a.b is not a table.`
+fn not_a_table_message(segments: &[String]) -> String {
+    format!("{} is not a table.", to_dotted_key(segments))
+}
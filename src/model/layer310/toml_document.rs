@@ -1,134 +1,1063 @@
 //! Document model.  
 //! ドキュメント・モデル。  
 
-use crate::model::layer210::LiteralValue;
-use crate::model::layer225::Val;
+use crate::model::layer210::{key::to_dotted_key, Key, LiteralValue};
+use crate::model::layer220::{Array, ItemValue};
+use crate::model::layer225::{from_toml::join_path, FromToml, GetError, InlineTable, Span, Val};
 use crate::model::layer230::Expression::Keyval;
-use crate::model::{layer230::Expression, layer310::TomlDocument};
+use crate::model::{
+    layer230::Expression,
+    layer310::{value::ABSENT, KeyIndex, Path, PathSegment, Query, Table, TomlDocument, Value},
+};
+use crate::util::edit_distance;
 use chrono::prelude::{DateTime, Local, Utc};
 use chrono::FixedOffset;
 use chrono::NaiveDate;
 use chrono::NaiveDateTime;
 use chrono::NaiveTime;
 use num_traits::Num;
+use std::collections::HashMap;
 use std::fmt;
+use std::ops::Index;
+use std::sync::OnceLock;
 
 impl Default for TomlDocument {
     fn default() -> Self {
         TomlDocument {
             elements: Vec::new(),
+            value: OnceLock::new(),
+            index: OnceLock::new(),
         }
     }
 }
 impl TomlDocument {
-    /// Get a [-1, 0, 1].
-    pub fn get_int_array_by_key<T: num_traits::Num + std::str::FromStr>(
-        &self,
+    /// Typed value at the path. Table headers are taken into account.  
+    /// `T` is anything that implements `FromToml`.  
+    /// A table such as `[srv]` is read as an inline table, and an array of tables as an array of inline tables.  
+    /// パスにある、型付きの値。テーブル・ヘッダーも考慮します。  
+    /// `[srv]` のようなテーブルはインライン・テーブルとして、テーブルの配列はインライン・テーブルの配列として読みます。  
+    ///
+    /// ```
+    /// // let port: u16 = doc.get("server.port")?;
+    /// // let name: Option<String> = doc.get("site.\"google.com\".name")?;
+    /// // let srv: HashMap<String, i64> = doc.get("srv")?;
+    /// // let name: String = doc.get("products[0].name")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `GetError::NotFound` unless `T` is `Option`. The others have the span of the value.  
+    /// `line 3, column 8: server.port is a string, not an integer.`  
+    pub fn get<T: FromToml>(&self, path: &str) -> Result<T, GetError> {
+        let path = Path::parse(path).map_err(|why| GetError::invalid_format(&why))?;
+        let table;
+        let val = match path
+            .to_keys()
+            .and_then(|segments| self.get_val_by_segments(&segments))
+        {
+            Some(val) => val,
+            None => match self.get_val_by_path(path.segments()) {
+                Some(val) => {
+                    table = val;
+                    &table
+                }
+                None => return T::from_missing().map_err(|why| why.with_parent(&path.to_string())),
+            },
+        };
+        T::from_toml(val).map_err(|why| {
+            let why = why.with_parent(&path.to_string());
+            let span = self.get_span_by_path(why.path());
+            why.with_span(span)
+        })
+    }
+
+    /// Where the value at the path is written.  
+    /// パスの位置の値が書かれている場所。  
+    ///
+    /// ```
+    /// // let line = doc.get_span_by_path("servers.alpha.ports[1]").unwrap().line;
+    /// // let line = doc.get_span_by_path("products[1].name").unwrap().line;
+    /// ```
+    pub fn get_span_by_path(&self, path: &str) -> Option<Span> {
+        let path = Path::parse(path).ok()?;
+        let segments = path.segments();
+        let mut offset = 0;
+        for (elem, m) in self.elements.iter().zip(self.element_paths()) {
+            if let (Keyval(ws1, keyval, _ws2, _comment, _newline), Some(m)) = (elem, m) {
+                if segments.starts_with(&m) {
+                    let start = offset + ws1.to_string().len() + keyval.val_offset();
+                    if let Some((start, end)) =
+                        get_span_in_val(&keyval.val, start, &segments[m.len()..])
+                    {
+                        let text = self.to_string();
+                        let before = &text[..start];
+                        return Some(Span {
+                            start,
+                            end,
+                            line: before.matches('\n').count() + 1,
+                            column: before.rsplit('\n').next().map_or(0, |m| m.chars().count()) + 1,
+                        });
+                    }
+                }
+            }
+            offset += elem.to_string().len();
+        }
+        None
+    }
+
+    /// Replaces the value at the path. The key, the spacing around `=` and the comment stay.  
+    /// The new value is written in the style of the old one, such as `0xFF` or `'literal'`.  
+    /// パスの位置の値を置き換えます。キー、 `=` の前後の空白、コメントは残ります。  
+    /// 新しい値は `0xFF` や `'literal'` のように、古い値の書き方に合わせます。  
+    ///
+    /// ```
+    /// // `retention_days = 7 # days` becomes `retention_days = 14 # days`.
+    /// // doc.set("log.retention_days", 14)?;
+    /// // doc.set("ports[1]", 8081)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `log.retention is not found.` The key must be written already.  
+    pub fn set<V: Into<Value>>(&mut self, path: &str, value: V) -> Result<(), String> {
+        let value = value.into();
+        let path = Path::parse(path)?;
+        let segments = path.segments();
+        // The first written key on the path, same as `get` and `remove`.
+        // `get` や `remove` と同じく、パスの上の最初に書かれたキー。
+        let paths = self.element_paths();
+        for (elem, m) in self.elements.iter_mut().zip(&paths) {
+            if let (Keyval(_ws1, keyval, _ws2, _comment, _newline), Some(m)) = (elem, m) {
+                if segments.starts_with(m)
+                    && set_in_val(&mut keyval.val, &segments[m.len()..], &value)
+                        .map_err(|why| join_path(&path.to_string(), &why))?
+                {
+                    self.clear_cache();
+                    return Ok(());
+                }
+            }
+        }
+        Err(format!("{} is not found.", path))
+    }
+
+    /// Adds a key-value at the end of its table, such as `[log]` for `log.retention_days`.  
+    /// The table is made at the end of the document if there is none.  
+    /// The indentation and the spacing around `=` follow the keys of the table.  
+    /// テーブルの最後にキー・バリューを追加します。 `log.retention_days` なら `[log]` です。  
+    /// テーブルが無ければ、ドキュメントの最後に作ります。  
+    /// インデントと `=` の前後の空白は、テーブルのキーに合わせます。  
+    ///
+    /// ```
+    /// // doc.insert("log.retention_days", 14)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `log.retention_days is defined already. Try `doc.set()`.`  
+    pub fn insert<V: Into<Value>>(&mut self, path: &str, value: V) -> Result<(), String> {
+        let value = value.into();
+        let segments = self.keys_to_insert(path)?;
+        let (name, parent) = match segments.split_last() {
+            Some(m) => m,
+            None => return Err(format!("`{}` is not a path.", path)),
+        };
+        let sections = self.sections();
+
+        // `[parent]`, or a table that writes the parent as a dotted key, such as `parent.a = 1`.
+        // `[parent]` か、 `parent.a = 1` のようにドット付きキーで親を書くテーブル。
+        let mut found = None;
+        for (header, table, keyvals) in sections.iter().rev() {
+            let mut key = parent[table.len().min(parent.len())..].to_vec();
+            key.push(name.to_string());
+            let at = match keyvals.last() {
+                Some(i) => i + 1,
+                None => header.map_or(0, |i| i + 1),
+            };
+            if table.as_slice() == parent {
+                found = Some((at, key, keyvals.first().copied()));
+                break;
+            }
+            if parent.starts_with(table)
+                && keyvals.iter().any(|i| match &self.elements[*i] {
+                    Keyval(_ws1, keyval, _ws2, _comment, _newline) => keyval
+                        .key
+                        .get_segments()
+                        .starts_with(&parent[table.len()..]),
+                    _ => false,
+                })
+            {
+                found = Some((at, key, keyvals.first().copied()));
+                break;
+            }
+        }
+        match found {
+            Some((at, key, like)) => {
+                let keyval = self.new_keyval(like, &key, &value)?;
+                self.insert_lines(at, keyval);
+            }
+            None => {
+                // The keys of the last table are the model.
+                // 最後のテーブルのキーを手本にします。
+                let like = sections
+                    .iter()
+                    .rev()
+                    .find_map(|(_header, _table, keyvals)| keyvals.first().copied());
+                let newline = self.newline();
+                let mut lines = if self.elements.is_empty() {
+                    String::new()
+                } else {
+                    newline.to_string()
+                };
+                lines.push_str(&format!("[{}]{}", to_dotted_key(parent), newline));
+                let mut vec = Expression::parse_lines(&lines)?;
+                vec.extend(self.new_keyval(like, &[name.to_string()], &value)?);
+                let at = self.elements.len();
+                self.insert_lines(at, vec);
+            }
+        }
+        Ok(())
+    }
+    /// Adds a key-value right before the sibling, in the same table. `key` is one name, not a path.  
+    /// 兄弟の直前、同じテーブルにキー・バリューを追加します。 `key` はパスではなく、１つの名前です。  
+    ///
+    /// ```
+    /// // doc.insert_before("log.retention_days", "retention_size", 100)?;
+    /// ```
+    pub fn insert_before<V: Into<Value>>(
+        &mut self,
+        sibling: &str,
         key: &str,
-    ) -> Result<Option<Vec<T>>, String>
-    where
-        <T as std::str::FromStr>::Err: std::fmt::Display,
-    {
-        if let Some(val) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = val {
-                if keyval.key.to_string() == key.to_string() {
-                    if let Val::Array(array) = &*keyval.val {
-                        match array.to_int_vector() {
-                            Ok(x) => return Ok(Some(x)),
-                            Err(why) => return Err(why),
-                        }
-                    } else {
-                        return Err(format!(
-                            "{} is not array. It's a {}.",
-                            key.to_string(),
-                            keyval.val
-                        ));
+        value: V,
+    ) -> Result<(), String> {
+        self.insert_next_to(sibling, key, value.into(), 0)
+    }
+    /// Adds a key-value right after the sibling, in the same table. `key` is one name, not a path.  
+    /// 兄弟の直後、同じテーブルにキー・バリューを追加します。 `key` はパスではなく、１つの名前です。  
+    ///
+    /// ```
+    /// // doc.insert_after("log.retention_days", "retention_size", 100)?;
+    /// ```
+    pub fn insert_after<V: Into<Value>>(
+        &mut self,
+        sibling: &str,
+        key: &str,
+        value: V,
+    ) -> Result<(), String> {
+        self.insert_next_to(sibling, key, value.into(), 1)
+    }
+    fn insert_next_to(
+        &mut self,
+        sibling: &str,
+        key: &str,
+        value: Value,
+        offset: usize,
+    ) -> Result<(), String> {
+        let sibling = self.keys_of(sibling)?;
+        let i = match self.position_by_segments(&sibling) {
+            Some(i) => i,
+            None => return Err(format!("{} is not found.", to_dotted_key(&sibling))),
+        };
+        // The sibling may be a dotted key, such as `a.b = 1` in `[t]`.
+        // 兄弟は `[t]` の中の `a.b = 1` のような、ドット付きキーかもしれません。
+        let mut written = match &self.elements[i] {
+            Keyval(_ws1, keyval, _ws2, _comment, _newline) => keyval.key.get_segments().to_vec(),
+            _ => return Err(format!("{} is not found.", to_dotted_key(&sibling))),
+        };
+        written.pop();
+        written.push(key.to_string());
+        let mut path = sibling[..sibling.len() - 1].to_vec();
+        path.push(key.to_string());
+        self.keys_to_insert(&to_dotted_key(&path))?;
+        let keyval = self.new_keyval(Some(i), &written, &value)?;
+        self.insert_lines(i + offset, keyval);
+        Ok(())
+    }
+    /// Removes the key-value, the `[table]` with its body and sub-tables, the `[[array]]` element such as `products[1]`,  
+    /// or the item of an array or an inline table such as `ports[1]`.  
+    /// Blank lines around it are merged into one. The comments right before the next table stay.  
+    /// キー・バリュー、本体とサブ・テーブルを含む `[table]` 、 `products[1]` のような `[[array]]` の要素、  
+    /// または `ports[1]` のような配列やインライン・テーブルの項目を取り除きます。  
+    /// 前後の空行は１つにまとめます。次のテーブルの直前のコメントは残ります。  
+    ///
+    /// ```
+    /// // doc.remove("log.retention_days")?;
+    /// // doc.remove("products[1]")?;
+    /// ```
+    pub fn remove(&mut self, path: &str) -> Result<(), String> {
+        self.remove_path(path, false)
+    }
+    /// Same as `remove`, with the comment lines right before it.  
+    /// `remove` と同じですが、直前のコメント行も取り除きます。  
+    pub fn remove_with_comments(&mut self, path: &str) -> Result<(), String> {
+        self.remove_path(path, true)
+    }
+    fn remove_path(&mut self, path: &str, with_comments: bool) -> Result<(), String> {
+        let path = Path::parse(path)?;
+        let target = path.segments();
+        let paths = self.element_paths();
+        let len = self.elements.len();
+        let mut marks: Vec<bool> = paths
+            .iter()
+            .map(|m| m.as_ref().is_some_and(|m| m.starts_with(target)))
+            .collect();
+        if !marks.contains(&true) {
+            // An item in a value.
+            // 値の中の項目。
+            for (elem, m) in self.elements.iter_mut().zip(&paths) {
+                if let (Keyval(_ws1, keyval, _ws2, _comment, _newline), Some(m)) = (elem, m) {
+                    if m.len() < target.len()
+                        && target.starts_with(m)
+                        && remove_in_val(&mut keyval.val, &target[m.len()..])
+                    {
+                        self.clear_cache();
+                        return Ok(());
                     }
                 }
-            } else {
-                return Err(format!(
-                    "{} is not keyval. It's a {}.",
-                    key.to_string(),
-                    val
-                ));
             }
+            return Err(format!("{} is not found.", path));
         }
-        Ok(None)
+
+        // The body of a table, but not the comments right before the next table.
+        // テーブルの本体。ただし、次のテーブルの直前のコメントは除きます。
+        for i in 0..len {
+            if marks[i] && self.is_header(i) {
+                let mut j = i + 1;
+                while j < len && !self.is_header(j) {
+                    marks[j] = true;
+                    j += 1;
+                }
+                if j < len && !marks[j] {
+                    while i + 1 < j && self.is_comment_line(j - 1) {
+                        marks[j - 1] = false;
+                        j -= 1;
+                    }
+                }
+            }
+        }
+        if with_comments {
+            for i in (0..len).rev() {
+                if marks[i] && 0 < i && !marks[i - 1] && self.is_comment_line(i - 1) {
+                    marks[i - 1] = true;
+                }
+            }
+        }
+        // Blank lines. Not at the top or the bottom, and not two in a row, but one between lines.
+        // 空行。先頭や末尾には残さず、２つ続けませんが、行の間には１つ残します。
+        let runs: Vec<(usize, usize)> = (0..len)
+            .filter(|i| marks[*i] && (*i == 0 || !marks[*i - 1]))
+            .map(|start| {
+                let end = (start..len).find(|i| !marks[*i]).unwrap_or(len);
+                (start, end)
+            })
+            .collect();
+        for (start, end) in runs {
+            if start == 0 {
+                let mut i = end;
+                while i < len && self.is_blank_line(i) {
+                    marks[i] = true;
+                    i += 1;
+                }
+            } else if end == len {
+                let mut i = start;
+                while 0 < i && self.is_blank_line(i - 1) {
+                    marks[i - 1] = true;
+                    i -= 1;
+                }
+            } else if self.is_blank_line(start - 1) && self.is_blank_line(end) {
+                marks[end] = true;
+            } else if !self.is_blank_line(start - 1) && !self.is_blank_line(end) {
+                // Keeps one blank line that separated them.
+                // 間を分けていた空行を１つ残します。
+                if let Some(i) = (start..end).rev().find(|i| self.is_blank_line(*i)) {
+                    marks[i] = false;
+                }
+            }
+        }
+
+        let mut marks = marks.into_iter();
+        self.elements.retain(|_| !marks.next().unwrap_or(false));
+        self.clear_cache();
+        Ok(())
     }
+    /// The path of each line, such as `products[1].name`. `None` for empty lines.  
+    /// 各行のパス。 `products[1].name` など。空行は `None` 。  
+    fn element_paths(&self) -> Vec<Option<Vec<PathSegment>>> {
+        // The current element of each array of tables, and the count of the elements.
+        // テーブルの配列それぞれの現在の要素と、要素の数。
+        let mut current = HashMap::<Vec<String>, usize>::new();
+        let mut counts = HashMap::<Vec<PathSegment>, usize>::new();
+        let to_path = |keys: &[String], current: &HashMap<Vec<String>, usize>| {
+            let mut path = Vec::new();
+            for (i, key) in keys.iter().enumerate() {
+                path.push(PathSegment::Key(key.to_string()));
+                if i + 1 < keys.len() {
+                    if let Some(index) = current.get(&keys[..=i]) {
+                        path.push(PathSegment::Index(*index));
+                    }
+                }
+            }
+            path
+        };
+        let mut table = Vec::new();
+        let mut vec = Vec::new();
+        for elem in &self.elements {
+            match elem {
+                Expression::HeaderOfArrayOfTable(m) => {
+                    let keys = m.key.get_segments();
+                    let mut path = to_path(keys, &current);
+                    let count = counts.entry(path.clone()).or_insert(0);
+                    path.push(PathSegment::Index(*count));
+                    current.insert(keys.to_vec(), *count);
+                    *count += 1;
+                    table = path.clone();
+                    vec.push(Some(path));
+                }
+                Expression::HeaderOfTable(m) => {
+                    table = to_path(m.key.get_segments(), &current);
+                    vec.push(Some(table.clone()));
+                }
+                Expression::EmptyLine(_ws, _comment, _newline) => vec.push(None),
+                Keyval(_ws1, keyval, _ws2, _comment, _newline) => {
+                    let mut path = table.clone();
+                    for key in keyval.key.get_segments() {
+                        path.push(PathSegment::Key(key.to_string()));
+                    }
+                    vec.push(Some(path));
+                }
+            }
+        }
+        vec
+    }
+    fn is_header(&self, i: usize) -> bool {
+        matches!(
+            self.elements[i],
+            Expression::HeaderOfTable(_) | Expression::HeaderOfArrayOfTable(_)
+        )
+    }
+    fn is_blank_line(&self, i: usize) -> bool {
+        matches!(self.elements[i], Expression::EmptyLine(_, None, _))
+    }
+    fn is_comment_line(&self, i: usize) -> bool {
+        matches!(self.elements[i], Expression::EmptyLine(_, Some(_), _))
+    }
+    /// Renames the last key of the path. The value, the comments and the position stay.  
+    /// A table renames its header and its sub-tables. The new key is quoted if needed.  
+    /// パスの最後のキーの名前を変えます。値、コメント、位置はそのままです。  
+    /// テーブルなら、ヘッダーとサブ・テーブルの名前を変えます。新しいキーは必要なら引用符で囲みます。  
+    ///
+    /// ```
+    /// // `timeout_secs = 30 # Seconds.` becomes `timeout = 30 # Seconds.`
+    /// // doc.rename("server.timeout_secs", "timeout")?;
+    /// // doc.rename("products[1].spec", "size chart")?;
+    /// ```
+    pub fn rename(&mut self, path: &str, new_key: &str) -> Result<(), String> {
+        let from = Path::parse(path)?;
+        let to = match from.segments().split_last() {
+            Some((PathSegment::Key(_), parent)) => {
+                let mut to = Path::from_segments(parent);
+                to.push_key(new_key);
+                to
+            }
+            Some((PathSegment::Index(_), _)) => {
+                return Err(format!("`{}` ends with an index.", from))
+            }
+            None => return Err(format!("`{}` is not a path.", from)),
+        };
+        if !self.path(&to.to_string()).is_absent() {
+            return Err(format!("{} is defined already.", to));
+        }
+        if self.rekey(from.segments(), to.segments())? {
+            return Ok(());
+        }
 
-    /// Get a ["a", 'b', '"c"'].
-    pub fn get_string_array_by_key(&self, key: &str) -> Result<Option<Vec<String>>, String> {
-        if let Some(val) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = val {
-                if keyval.key.to_string() == key.to_string() {
-                    if let Val::Array(array) = &*keyval.val {
-                        return Ok(Some(array.to_string_vector()));
-                    } else {
+        // A key in a value.
+        // 値の中のキー。
+        let target = from.segments();
+        let paths = self.element_paths();
+        for (elem, m) in self.elements.iter_mut().zip(&paths) {
+            if let (Keyval(_ws1, keyval, _ws2, _comment, _newline), Some(m)) = (elem, m) {
+                if m.len() < target.len()
+                    && target.starts_with(m)
+                    && rename_in_val(&mut keyval.val, &target[m.len()..], new_key)?
+                {
+                    self.clear_cache();
+                    return Ok(());
+                }
+            }
+        }
+        Err(format!("{} is not found.", from))
+    }
+    /// Moves the table, such as `[log]` to `[logging.file]`. Its sub-tables move too.  
+    /// The headers are rewritten in place, so the body and the comments stay.  
+    /// `[log]` から `[logging.file]` のように、テーブルを移します。サブ・テーブルも移ります。  
+    /// ヘッダーはその場で書き換えるので、本体とコメントはそのままです。  
+    ///
+    /// ```
+    /// // doc.move_table("log", "logging.file")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `log.x is a dotted key in [log]. It can not move out of the table.`  
+    pub fn move_table(&mut self, from: &str, to: &str) -> Result<(), String> {
+        let from = self.keys_of(from)?;
+        let to = self.keys_of(to)?;
+        let dotted = to_dotted_key(&from);
+        match self.path(&dotted) {
+            Value::Table(_) => {}
+            m if m.is_array_of_tables() => {}
+            Value::Absent => return Err(format!("{} is not found.", dotted)),
+            _ => return Err(format!("{} is not a table.", dotted)),
+        }
+        if self.get_val_by_segments(&from).is_some() {
+            return Err(format!("{} is an inline table. Try `doc.set()`.", dotted));
+        }
+        if self.is_defined(&to) {
+            return Err(format!("{} is defined already.", to_dotted_key(&to)));
+        }
+        self.check_parents(&to)?;
+        let to_path = |keys: &[String]| -> Vec<PathSegment> {
+            keys.iter()
+                .map(|m| PathSegment::Key(m.to_string()))
+                .collect()
+        };
+        self.rekey(&to_path(&from), &to_path(&to))?;
+        Ok(())
+    }
+    /// Rewrites the headers and the keys under `from` to be under `to`. `false` if nothing is under `from`.  
+    /// `from` の下のヘッダーとキーを、 `to` の下に書き換えます。 `from` の下に何も無ければ `false` 。  
+    fn rekey(&mut self, from: &[PathSegment], to: &[PathSegment]) -> Result<bool, String> {
+        let keys_only = |segments: Vec<PathSegment>| -> Vec<String> {
+            segments
+                .into_iter()
+                .filter_map(|m| match m {
+                    PathSegment::Key(key) => Some(key),
+                    PathSegment::Index(_) => None,
+                })
+                .collect()
+        };
+        let moved = |path: &[PathSegment]| -> Vec<PathSegment> {
+            let mut vec = to.to_vec();
+            vec.extend_from_slice(&path[from.len()..]);
+            vec
+        };
+
+        // Check everything first, then rewrite.
+        // 先に全てを確かめてから、書き換えます。
+        let paths = self.element_paths();
+        let mut edits = Vec::new();
+        for (i, m) in paths.iter().enumerate() {
+            let path = match m {
+                Some(m) if m.starts_with(from) => m,
+                _ => continue,
+            };
+            match &self.elements[i] {
+                Expression::HeaderOfTable(_) | Expression::HeaderOfArrayOfTable(_) => {
+                    edits.push((i, keys_only(moved(path))));
+                }
+                Keyval(_ws1, keyval, _ws2, _comment, _newline) => {
+                    let table = &path[..path.len() - keyval.key.get_segments().len()];
+                    if from.len() <= table.len() {
+                        // In the body of a table that moves.
+                        // 移るテーブルの本体の中。
+                        continue;
+                    }
+                    if to.len() < table.len() || &to[..table.len()] != table {
                         return Err(format!(
-                            "{} is not array. It's a {}.",
-                            key.to_string(),
-                            keyval.val
+                            "{} is a dotted key in [{}]. It can not move out of the table.",
+                            Path::from_segments(path),
+                            Path::from_segments(table)
                         ));
                     }
+                    edits.push((i, keys_only(moved(path)[table.len()..].to_vec())));
                 }
-            } else {
-                return Err(format!(
-                    "{} is not keyval. It's a {}.",
-                    key.to_string(),
-                    val
-                ));
+                Expression::EmptyLine(_ws, _comment, _newline) => {}
             }
         }
-        Ok(None)
+        if edits.is_empty() {
+            return Ok(false);
+        }
+        for (i, keys) in edits {
+            rewrite_key(&mut self.elements[i], &keys)?;
+        }
+        self.clear_cache();
+        Ok(true)
+    }
+    /// Keys of the path. No indexes.  
+    /// パスのキー。添え字は含みません。  
+    fn keys_of(&self, path: &str) -> Result<Vec<String>, String> {
+        let path = Path::parse(path)?;
+        match path.to_keys() {
+            Some(m) if !m.is_empty() => Ok(m),
+            Some(_) => Err(format!("`{}` is not a path.", path)),
+            None => Err(format!("`{}` has an index.", path)),
+        }
+    }
+    /// Keys of a new key-value. Nothing is there, and the parents are tables.  
+    /// 新しいキー・バリューのキー。何も無く、親はテーブルです。  
+    fn keys_to_insert(&self, path: &str) -> Result<Vec<String>, String> {
+        let segments = self.keys_of(path)?;
+        if self.is_defined(&segments) {
+            return Err(format!(
+                "{} is defined already. Try `doc.set()`.",
+                to_dotted_key(&segments)
+            ));
+        }
+        self.check_parents(&segments)?;
+        Ok(segments)
+    }
+    fn is_defined(&self, segments: &[String]) -> bool {
+        self.get_val_by_segments(segments).is_some()
+            || !self.path(&to_dotted_key(segments)).is_absent()
+    }
+    /// The parents are tables, and not arrays of tables.  
+    /// 親はテーブルで、テーブルの配列ではありません。  
+    fn check_parents(&self, segments: &[String]) -> Result<(), String> {
+        for len in 1..segments.len() {
+            let parent = to_dotted_key(&segments[..len]);
+            if self.get_val_by_segments(&segments[..len]).is_some() {
+                return Err(format!("{} is not a table.", parent));
+            }
+            if self.path(&parent).is_array_of_tables() {
+                return Err(format!("{} is an array of tables.", parent));
+            }
+        }
+        Ok(())
+    }
+    /// The header, the table and the key-values of the root table and each `[table]`. Not arrays of tables.  
+    /// ルート・テーブルと各 `[table]` の、ヘッダー、テーブル、キー・バリュー。テーブルの配列は含みません。  
+    fn sections(&self) -> Vec<(Option<usize>, Vec<String>, Vec<usize>)> {
+        let mut sections = vec![(None, Vec::new(), Vec::new())];
+        let mut in_table = true;
+        for (i, elem) in self.elements.iter().enumerate() {
+            match elem {
+                Expression::HeaderOfArrayOfTable(_) => in_table = false,
+                Expression::HeaderOfTable(m) => {
+                    sections.push((Some(i), m.key.get_segments().to_vec(), Vec::new()));
+                    in_table = true;
+                }
+                Expression::EmptyLine(_ws, _comment, _newline) => {}
+                Keyval(_ws1, _keyval, _ws2, _comment, _newline) => {
+                    if in_table {
+                        if let Some((_header, _table, keyvals)) = sections.last_mut() {
+                            keyvals.push(i);
+                        }
+                    }
+                }
+            }
+        }
+        sections
+    }
+    /// A key-value line written like the one at `like`.  
+    /// `like` の位置のものに合わせて書いたキー・バリューの行。  
+    fn new_keyval(
+        &self,
+        like: Option<usize>,
+        key: &[String],
+        value: &Value,
+    ) -> Result<Vec<Expression>, String> {
+        let (indent, ws1, ws2) = match like.map(|i| &self.elements[i]) {
+            Some(Keyval(ws1, keyval, _ws2, _comment, _newline)) => (
+                ws1.to_string(),
+                keyval.ws1.to_string(),
+                keyval.ws2.to_string(),
+            ),
+            _ => (String::new(), " ".to_string(), " ".to_string()),
+        };
+        let value = value
+            .to_toml_string()
+            .map_err(|why| join_path(&to_dotted_key(key), &why))?;
+        Expression::parse_lines(&format!(
+            "{}{}{}={}{}{}",
+            indent,
+            to_dotted_key(key),
+            ws1,
+            ws2,
+            value,
+            self.newline()
+        ))
+    }
+    /// The first line ending in the document. `\n` if there is none.  
+    /// ドキュメントの最初の改行。無ければ `\n` 。  
+    fn newline(&self) -> String {
+        self.elements
+            .iter()
+            .map(|m| m.newline().to_string())
+            .find(|m| !m.is_empty())
+            .unwrap_or_else(|| "\n".to_string())
+    }
+    /// Puts the lines at `at`. The line before gets a line ending if it is the last line.  
+    /// 行を `at` に置きます。前の行が最終行なら、改行を付けます。  
+    fn insert_lines(&mut self, at: usize, lines: Vec<Expression>) {
+        if 0 < at && self.elements[at - 1].newline().is_empty() {
+            let newline = Expression::parse_lines(&self.newline())
+                .ok()
+                .and_then(|m| m.first().map(|m| m.newline().clone()));
+            if let Some(newline) = newline {
+                *self.elements[at - 1].newline_mut() = newline;
+            }
+        }
+        self.elements.splice(at..at, lines);
+        self.clear_cache();
     }
 
-    /// Right of `left = right`.  
-    /// キー・バリューの右値。  
-    pub fn get_val_by_key(&self, key: &str) -> Option<&Expression> {
-        // println!("[trace22]");
+    /// Existing keys near the missing one, in the same table. Nearest first, then in the written order.  
+    /// For typos such as `retention_day` for `retention_days`.  
+    /// 見つからないキーに近い、同じテーブルの既存のキー。近い順、同じなら書かれた順。  
+    ///
+    /// ```
+    /// // if let Err(GetError::NotFound { path }) = doc.get::<u32>("log.retention_day") {
+    /// //     if let Some(m) = doc.suggest_keys(&path).first() {
+    /// //         println!("{} is not found. Did you mean `{}`?", path, m); // `log.retention_days`
+    /// //     }
+    /// // }
+    /// ```
+    pub fn suggest_keys(&self, path: &str) -> Vec<Path> {
+        let path = match Path::parse(path) {
+            Ok(m) => m,
+            Err(_) => return Vec::new(),
+        };
+        let (name, parent) = match path.segments().split_last() {
+            Some((PathSegment::Key(name), parent)) => (name, parent),
+            _ => return Vec::new(),
+        };
+        let mut names = Vec::<String>::new();
+        for (elem, m) in self.elements.iter().zip(self.element_paths()) {
+            match (elem, m) {
+                (Keyval(_ws1, keyval, _ws2, _comment, _newline), Some(m)) => {
+                    push_child_names(&mut names, parent, &m, &keyval.val);
+                }
+                (_, Some(m)) => push_child_name(&mut names, parent, &m),
+                (_, None) => {}
+            }
+        }
+
+        // Up to a third of the name may differ.
+        // 名前の３分の１までの違いは許します。
+        let limit = std::cmp::max(1, name.chars().count() / 3);
+        let mut near: Vec<(usize, String)> = names
+            .into_iter()
+            .filter(|m| m != name)
+            .map(|m| (edit_distance(name, &m), m))
+            .filter(|(distance, _)| *distance <= limit)
+            .collect();
+        near.sort_by_key(|(distance, _)| *distance);
+        near.into_iter()
+            .map(|(_, m)| {
+                let mut path = Path::from_segments(parent);
+                path.push_key(&m);
+                path
+            })
+            .collect()
+    }
+
+    /// Owned data, resolved by the table headers. Tables keep the written order.  
+    /// テーブル・ヘッダーで解決した所有データ。テーブルは書かれた順を保ちます。  
+    ///
+    /// ```
+    /// // let value = doc.to_value()?;
+    /// // let port = value.as_table().unwrap().get("server").unwrap().as_table().unwrap().get("port");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `fruit.color is defined twice.`  
+    /// `fruit is not a table.`  
+    /// `fruit is not an array of tables.`  
+    /// `fruit is an inline table. It can't be extended.`  
+    pub fn to_value(&self) -> Result<Value, String> {
+        let mut root = Table::default();
+        // Segments of the current table. `[[a]]` is the last table of `a`.
+        // 現在のテーブルのセグメント。 `[[a]]` は `a` の最後のテーブル。
+        let mut table: &[String] = &[];
+        // How the tables and the arrays were made.
+        // テーブルと配列の作られ方。
+        let mut defined = HashMap::<Vec<String>, Defined>::new();
         for elem in &self.elements {
             match elem {
-                Expression::HeaderOfArrayOfTable(_) => {
-                    // TODO
-                    // println!("[trace27]");
-                }
-                Expression::EmptyLine(_ws, _comment) => {
-                    // println!("[trace33]");
-                }
-                Expression::Keyval(_ws1, keyval, _ws2, _comment) => {
-                    if keyval.key.to_string() == key.to_string() {
-                        /*
-                        println!(
-                            // "[trace36 Hit m.key={} key={}]",
-                            m.key.to_string(),
-                            key.to_string()
-                        ); // In development.
-                        */
-                        return Some(elem);
-                    } else {
-                        /*
-                        println!(
-                            // "[trace44 Not found m.key={} key={}]",
-                            m.key.to_string(),
-                            key.to_string()
-                        ); // In development.
-                        */
+                Expression::HeaderOfArrayOfTable(m) => {
+                    table = m.key.get_segments();
+                    define_parents(&mut defined, table)?;
+                    match defined.get(table) {
+                        None => {
+                            defined.insert(table.to_vec(), Defined::ArrayOfTables);
+                        }
+                        Some(Defined::ArrayOfTables) => {}
+                        Some(Defined::StaticArray) => {
+                            return Err(format!(
+                                "{} is not an array of tables.",
+                                to_dotted_key(table)
+                            ))
+                        }
+                        Some(_) => {
+                            return Err(format!("{} is defined twice.", to_dotted_key(table)))
+                        }
                     }
+                    // A new table. The tables in the previous one are done.
+                    // 新しいテーブル。前のものの中のテーブルは終わりです。
+                    defined.retain(|m, _| m.len() <= table.len() || !m.starts_with(table));
+                    root.push_array_of_table_by_segments(table)?;
                 }
-                Expression::HeaderOfTable(_) => {
-                    // TODO
-                    // println!("[trace45]");
+                Expression::HeaderOfTable(m) => {
+                    table = m.key.get_segments();
+                    define_parents(&mut defined, table)?;
+                    match defined.get(table) {
+                        None | Some(Defined::Implicit) => {
+                            defined.insert(table.to_vec(), Defined::Header);
+                        }
+                        Some(_) => {
+                            return Err(format!("{} is defined twice.", to_dotted_key(table)))
+                        }
+                    }
+                    root.get_table_by_segments_mut(table)?;
+                }
+                Expression::EmptyLine(_ws, _comment, _newline) => {}
+                Keyval(_ws1, keyval, _ws2, _comment, _newline) => {
+                    let mut segments = table.to_vec();
+                    segments.extend_from_slice(keyval.key.get_segments());
+                    define_dotted(&mut defined, &segments, table.len())?;
+                    match &*keyval.val {
+                        Val::InlineTable(_) => {
+                            defined.insert(segments, Defined::Inline);
+                        }
+                        Val::Array(_) => {
+                            defined.insert(segments, Defined::StaticArray);
+                        }
+                        _ => {}
+                    }
+                    let value = match Value::from_val(&keyval.val) {
+                        Ok(m) => m,
+                        Err(why) => {
+                            let mut segments = table.to_vec();
+                            segments.extend_from_slice(keyval.key.get_segments());
+                            return Err(join_path(&to_dotted_key(&segments), &why));
+                        }
+                    };
+                    if let Err(why) = root
+                        .get_table_by_segments_mut(table)?
+                        .insert_by_segments(keyval.key.get_segments(), value)
+                    {
+                        return Err(format!("{}{}", dotted(table), why));
+                    }
+                }
+            }
+        }
+        Ok(Value::Table(root))
+    }
+
+    /// Right value at the key segments. Keys in an array of tables are not reached yet.  
+    /// キー・セグメントの位置の右値。テーブルの配列の中のキーにはまだ届きません。  
+    fn get_val_by_segments(&self, segments: &[String]) -> Option<&Val> {
+        // The shortest written key first, then into its inline tables.
+        // 書かれた最も短いキーから、そのインライン・テーブルの中へ。
+        for len in 1..=segments.len() {
+            if let Some(i) = self.position_by_segments(&segments[..len]) {
+                if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = &self.elements[i] {
+                    let key = keyval.key.get_segments();
+                    if let Some(val) = find_val(&keyval.val, key, &segments[len - key.len()..]) {
+                        return Some(val);
+                    }
                 }
             }
         }
         None
     }
+    /// Value at the path, indexes too. A table is made of the lines under it as an inline table,  
+    /// and an array of tables as an array of inline tables.  
+    /// 添え字も含むパスの位置の値。テーブルはその下の行からインライン・テーブルとして作り、  
+    /// テーブルの配列はインライン・テーブルの配列として作ります。  
+    fn get_val_by_path(&self, segments: &[PathSegment]) -> Option<Val> {
+        let paths = self.element_paths();
+        // A key-value at the path, or above it.
+        // パスの位置か、その上のキー・バリュー。
+        for (elem, m) in self.elements.iter().zip(&paths) {
+            if let (Keyval(_ws1, keyval, _ws2, _comment, _newline), Some(m)) = (elem, m) {
+                if segments.starts_with(m) {
+                    if let Some(val) = get_in_val(&keyval.val, &segments[m.len()..]) {
+                        return Some(val);
+                    }
+                }
+            }
+        }
+        // Lines under the path.
+        // パスの下の行。
+        let mut found = false;
+        let mut lines = Vec::new();
+        for (elem, m) in self.elements.iter().zip(&paths) {
+            if let Some(m) = m {
+                if m.starts_with(segments) {
+                    found = true;
+                    match elem {
+                        Keyval(_ws1, keyval, _ws2, _comment, _newline) => {
+                            lines.push((&m[segments.len()..], Some(&*keyval.val)))
+                        }
+                        _ => lines.push((&m[segments.len()..], None)),
+                    }
+                }
+            }
+        }
+        if found {
+            Some(gather_lines(&lines))
+        } else {
+            None
+        }
+    }
+
+    /// Right array of `left = [1, 2, 3]`.  
+    /// キー・バリューの右の配列。  
+    pub fn get_array_by_key(&self, key: &str) -> Result<Option<&Array>, GetError> {
+        if let Some(Keyval(_ws1, keyval, _ws2, _comment, _newline)) = self.get_val_by_key(key) {
+            if let Val::Array(array) = &*keyval.val {
+                return Ok(Some(array));
+            } else {
+                return Err(
+                    GetError::type_mismatch("array", keyval.val.type_name()).with_parent(key)
+                );
+            }
+        }
+        Ok(None)
+    }
+
+    /// Right inline table of `left = { x = 1 }`.  
+    /// The table has the same typed getters as the document.  
+    /// キー・バリューの右のインライン・テーブル。ドキュメントと同じ型付きゲッターを持ちます。  
+    ///
+    /// ```
+    /// // let server = doc.get_inline_table_by_key("server")?.unwrap();
+    /// // let port: Option<u16> = server.get_int_by_key_v2("port")?;
+    /// ```
+    pub fn get_inline_table_by_key(&self, key: &str) -> Result<Option<&InlineTable>, GetError> {
+        if let Some(Keyval(_ws1, keyval, _ws2, _comment, _newline)) = self.get_val_by_key(key) {
+            if let Val::InlineTable(inline_table) = &*keyval.val {
+                return Ok(Some(inline_table));
+            } else {
+                return Err(
+                    GetError::type_mismatch("inline table", keyval.val.type_name())
+                        .with_parent(key),
+                );
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get a [-1, 0, 1].
+    ///
+    /// The error names the item. Example: `ports[2] is a string, not an integer.`  
+    pub fn get_int_array_by_key<T: num_traits::Num + std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<T>>, GetError>
+    where
+        <T as num_traits::Num>::FromStrRadixErr: std::fmt::Display,
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        if let Some(array) = self.get_array_by_key(key)? {
+            return match array.to_int_vector() {
+                Ok(x) => Ok(Some(x)),
+                Err(why) => Err(why.with_parent(key)),
+            };
+        }
+        Ok(None)
+    }
+
+    /// Get a [0.0, 0.5, 1.0].
+    pub fn get_float_array_by_key<T: num_traits::float::FloatCore + Num + std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<T>>, GetError>
+    where
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        if let Some(array) = self.get_array_by_key(key)? {
+            return match array.to_float_vector() {
+                Ok(x) => Ok(Some(x)),
+                Err(why) => Err(why.with_parent(key)),
+            };
+        }
+        Ok(None)
+    }
+
+    /// Get a [true, false].
+    pub fn get_bool_array_by_key(&self, key: &str) -> Result<Option<Vec<bool>>, GetError> {
+        if let Some(array) = self.get_array_by_key(key)? {
+            return match array.to_bool_vector() {
+                Ok(x) => Ok(Some(x)),
+                Err(why) => Err(why.with_parent(key)),
+            };
+        }
+        Ok(None)
+    }
+
+    /// Get a [1979-05-27, 1979-05-28].
+    ///
+    /// `T` is a `chrono` type, such as `NaiveDate` or `DateTime<Utc>`.  
+    pub fn get_datetime_array_by_key<T: std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<T>>, GetError>
+    where
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        if let Some(array) = self.get_array_by_key(key)? {
+            return match array.to_datetime_vector() {
+                Ok(x) => Ok(Some(x)),
+                Err(why) => Err(why.with_parent(key)),
+            };
+        }
+        Ok(None)
+    }
+
+    /// Get a [[1, 2], [3]]. Each array is converted by `f`.
+    ///
+    /// ```
+    /// // let vec: Option<Vec<Vec<i64>>> = doc.get_nested_array_by_key("key", |m| m.to_int_vector())?;
+    /// ```
+    pub fn get_nested_array_by_key<T, F>(&self, key: &str, f: F) -> Result<Option<Vec<T>>, GetError>
+    where
+        F: Fn(&Array) -> Result<T, GetError>,
+    {
+        if let Some(array) = self.get_array_by_key(key)? {
+            return match array.to_nested_vector(f) {
+                Ok(x) => Ok(Some(x)),
+                Err(why) => Err(why.with_parent(key)),
+            };
+        }
+        Ok(None)
+    }
+
+    /// Get a [ { x = 1 }, { x = 2 } ] as maps.
+    pub fn get_inline_table_array_by_key(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<HashMap<String, Val>>>, GetError> {
+        if let Some(array) = self.get_array_by_key(key)? {
+            return match array.to_inline_table_vector() {
+                Ok(x) => Ok(Some(x)),
+                Err(why) => Err(why.with_parent(key)),
+            };
+        }
+        Ok(None)
+    }
+
+    /// Get a ["a", 'b', '"c"'].
+    pub fn get_string_array_by_key(&self, key: &str) -> Result<Option<Vec<String>>, GetError> {
+        if let Some(array) = self.get_array_by_key(key)? {
+            return Ok(Some(array.to_string_vector()));
+        }
+        Ok(None)
+    }
+
+    /// Right of `left = right`.  
+    /// キー・バリューの右値。  
+    pub fn get_val_by_key(&self, key: &str) -> Option<&Expression> {
+        self.position_by_key(key).map(|i| &self.elements[i])
+    }
 
     /// Right of `left = right`.  
     /// キー・バリューの右値。  
     pub fn get_literal_string_by_key(&self, key: &str) -> Option<&LiteralValue> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         return Some(literal_value);
@@ -143,7 +1072,7 @@ impl TomlDocument {
     /// キーを含むか？  
     pub fn contains_key(&self, key: &str) -> bool {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 return keyval.key.to_string() == key.to_string();
             }
         }
@@ -249,7 +1178,7 @@ impl TomlDocument {
         <T as num_traits::Num>::FromStrRadixErr: std::fmt::Display,
     {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         // アンダースコアは除去しないと変換できない。
@@ -303,7 +1232,7 @@ impl TomlDocument {
         <T as std::str::FromStr>::Err: std::fmt::Display,
     {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         // アンダースコアは除去しないと変換できない。
@@ -353,7 +1282,7 @@ impl TomlDocument {
         key: &str,
     ) -> Option<T> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         // アンダースコアは除去しないと変換できない。
@@ -395,7 +1324,7 @@ impl TomlDocument {
         <T as std::str::FromStr>::Err: std::fmt::Display,
     {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         // アンダースコアは除去しないと変換できない。
@@ -437,14 +1366,14 @@ impl TomlDocument {
     /// キー・バリューの右の文字列。  
     pub fn get_string_by_key(&self, key: &str) -> Option<String> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     match &*keyval.val {
                         Val::BasicString(basic_string) => {
-                            return Some(basic_string.to_string());
+                            return Some(basic_string.value());
                         }
                         Val::LiteralString(literal_string) => {
-                            return Some(literal_string.to_string());
+                            return Some(literal_string.value());
                         }
                         _ => {}
                     }
@@ -456,7 +1385,7 @@ impl TomlDocument {
     /// For this library developer.
     pub fn get_debug_string_by_key(&self, key: &str) -> String {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     match &*keyval.val {
                         Val::BasicString(basic_string) => {
@@ -477,7 +1406,7 @@ impl TomlDocument {
     /// キー・バリューの右の論理値。  
     pub fn get_bool_by_key(&self, key: &str) -> Option<bool> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         match literal_value.to_string().parse() {
@@ -495,7 +1424,7 @@ impl TomlDocument {
     /// 日付と時刻。協定世界時。  
     pub fn get_datetime_utc_by_key(&self, key: &str) -> Option<DateTime<Utc>> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         match format!("{}", literal_value).to_string().parse() {
@@ -513,7 +1442,7 @@ impl TomlDocument {
     /// 日付と時刻。ローカル時。  
     pub fn get_datetime_local_by_key(&self, key: &str) -> Option<DateTime<Local>> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         match format!("{}", literal_value).to_string().parse() {
@@ -531,7 +1460,7 @@ impl TomlDocument {
     /// 日付と時刻。オフセット。  
     pub fn get_datetime_fixed_offset_by_key(&self, key: &str) -> Option<DateTime<FixedOffset>> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         match format!("{}", literal_value).to_string().parse() {
@@ -549,7 +1478,7 @@ impl TomlDocument {
     /// 日付と時刻。ナイーブ。  
     pub fn get_naive_datetime_by_key(&self, key: &str) -> Option<NaiveDateTime> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         match format!("{}", literal_value).to_string().parse() {
@@ -567,7 +1496,7 @@ impl TomlDocument {
     /// 日付。ナイーブ。  
     pub fn get_naive_date_by_key(&self, key: &str) -> Option<NaiveDate> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         match format!("{}", literal_value).to_string().parse() {
@@ -585,7 +1514,7 @@ impl TomlDocument {
     /// 日時。ナイーブ。  
     pub fn get_naive_time_by_key(&self, key: &str) -> Option<NaiveTime> {
         if let Some(doc_elm) = self.get_val_by_key(key) {
-            if let Keyval(_ws1, keyval, _ws2, _comment) = doc_elm {
+            if let Keyval(_ws1, keyval, _ws2, _comment, _newline) = doc_elm {
                 if keyval.key.to_string() == key.to_string() {
                     if let Val::LiteralValue(literal_value) = &*keyval.val {
                         match format!("{}", literal_value).to_string().parse() {
@@ -599,8 +1528,122 @@ impl TomlDocument {
         None
     }
 
+    /// Lines with height.  
+    /// 縦幅を持つ行。  
+    pub fn elements(&self) -> &[Expression] {
+        &self.elements
+    }
+    /// Lines with height, to edit. `to_value()` and the key index are dropped.  
+    /// 編集用の、縦幅を持つ行。 `to_value()` とキー索引を捨てます。  
+    pub fn elements_mut(&mut self) -> &mut Vec<Expression> {
+        self.clear_cache();
+        &mut self.elements
+    }
     pub fn push_element(&mut self, m: &Expression) {
         self.elements.push(m.clone());
+        self.clear_cache();
+    }
+    /// Drops `to_value()` and the key index. Call it after each edit of `elements`.  
+    /// `to_value()` とキー索引を捨てます。 `elements` を編集するたびに呼んでください。  
+    pub(crate) fn clear_cache(&mut self) {
+        self.value = OnceLock::new();
+        self.index = OnceLock::new();
+    }
+    /// Value at the path. `Value::Absent` if nothing is there, or the path is broken.  
+    /// パスの位置の値。何も無いか、パスが壊れていれば `Value::Absent` 。  
+    ///
+    /// ```
+    /// // let ip = doc.path("servers.alpha.ip").as_str();
+    /// // let name = doc.path("a.\"b.c\"[0].name").as_str();
+    /// ```
+    pub fn path(&self, path: &str) -> &Value {
+        match Path::parse(path) {
+            Ok(path) => self.root().get_by_path(&path),
+            Err(_) => &ABSENT,
+        }
+    }
+    /// Every value that matches the query, with its path.  
+    /// クエリーに合う全ての値と、そのパス。  
+    ///
+    /// ```
+    /// // for (path, ip) in doc.query("servers.*.ip")? {
+    /// //     println!("{} = {:?}", path, ip.as_str());
+    /// // }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `` `a[?b ~ 1]` is not a query. ``  
+    /// Or the error of `to_value()`.  
+    pub fn query(&self, query: &str) -> Result<Vec<(Path, &Value)>, String> {
+        let query = Query::parse(query)?;
+        match self.cached_value() {
+            Ok(m) => Ok(query.select(m)),
+            Err(why) => Err(why.clone()),
+        }
+    }
+    /// Every value with its path, parent first. Array items too.  
+    /// Empty if `to_value()` is an error. The same for `keys()`, `tables()` and `entries()`.  
+    /// 全ての値と、そのパス。親が先です。配列の項目も。  
+    /// `to_value()` がエラーなら空です。 `keys()` 、 `tables()` 、 `entries()` も同じです。  
+    ///
+    /// ```
+    /// // for (path, value) in doc.walk() {
+    /// //     println!("{} is {}", path, value.type_name());
+    /// // }
+    /// ```
+    pub fn walk(&self) -> Vec<(Path, &Value)> {
+        self.root().walk()
+    }
+    /// Key-values that are not tables, such as `servers.alpha.ip` and `products[0].name`.  
+    /// An array of tables is not one. Its keys are.  
+    /// テーブルでないキー・バリュー。テーブルの配列は含まず、その中のキーを含みます。  
+    pub fn entries(&self) -> impl Iterator<Item = (Path, &Value)> {
+        self.walk().into_iter().filter(|(path, value)| {
+            matches!(path.segments().last(), Some(PathSegment::Key(_)))
+                && value.as_table().is_none()
+                && !value.is_array_of_tables()
+        })
+    }
+    /// Paths of `entries()`.  
+    /// `entries()` のパス。  
+    ///
+    /// ```
+    /// // let keys: Vec<String> = doc.keys().map(|m| m.to_string()).collect();
+    /// ```
+    pub fn keys(&self) -> impl Iterator<Item = Path> + '_ {
+        self.entries().map(|(path, _)| path)
+    }
+    /// Every table but the root, such as `servers.alpha` and `products[0]`. Inline tables too.  
+    /// ルート以外の全てのテーブル。インライン・テーブルも。  
+    pub fn tables(&self) -> impl Iterator<Item = (Path, &Table)> {
+        self.walk()
+            .into_iter()
+            .filter_map(|(path, value)| value.as_table().map(|m| (path, m)))
+    }
+    /// Cached `to_value()`. `Value::Absent` if it is an error.  
+    /// キャッシュした `to_value()` 。エラーなら `Value::Absent` 。  
+    fn root(&self) -> &Value {
+        match self.cached_value() {
+            Ok(m) => m,
+            Err(_) => &ABSENT,
+        }
+    }
+    fn cached_value(&self) -> &Result<Value, String> {
+        self.value.get_or_init(|| self.to_value())
+    }
+    fn key_index(&self) -> &KeyIndex {
+        self.index.get_or_init(|| KeyIndex::new(&self.elements))
+    }
+    /// Position of `left = right` by `left`, as written.  
+    /// 書かれたままの `left` による `left = right` の位置。  
+    fn position_by_key(&self, key: &str) -> Option<usize> {
+        self.key_index().get_by_key(key)
+    }
+    /// Position of `left = right` by the table header and `left`.  
+    /// テーブル・ヘッダーと `left` による `left = right` の位置。  
+    fn position_by_segments(&self, segments: &[String]) -> Option<usize> {
+        self.key_index().get_by_segments(segments)
     }
     pub fn to_debug_string(&self) -> String {
         format!("{:?}", self)
@@ -609,6 +1652,488 @@ impl TomlDocument {
         format!("{}", self)
     }
 }
+/// `segments` starts with the keys.  
+/// `segments` はキーで始まります。  
+fn starts_with_keys(segments: &[PathSegment], keys: &[String]) -> bool {
+    keys.len() <= segments.len()
+        && keys
+            .iter()
+            .zip(segments)
+            .all(|(key, m)| matches!(m, PathSegment::Key(k) if k == key))
+}
+/// `val` is written from `start`. Goes down into arrays and inline tables.  
+/// `val` は `start` から書かれています。配列とインライン・テーブルの中へ降ります。  
+fn get_span_in_val(val: &Val, start: usize, segments: &[PathSegment]) -> Option<(usize, usize)> {
+    match (segments.first(), val) {
+        (None, _) => Some((start, start + val.to_string().len())),
+        (Some(PathSegment::Index(index)), Val::Array(array)) => get_span_in_val(
+            &array.get(*index)?.to_val()?,
+            start + array.item_offset(*index)?,
+            &segments[1..],
+        ),
+        (Some(PathSegment::Key(_)), Val::InlineTable(inline_table)) => {
+            for (i, keyval) in inline_table.parts().0.iter().enumerate() {
+                let key = keyval.key.get_segments();
+                if starts_with_keys(segments, key) {
+                    return get_span_in_val(
+                        &keyval.val,
+                        start + inline_table.keyval_offset(i)? + keyval.val_offset(),
+                        &segments[key.len()..],
+                    );
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Replaces `val`, or the value under it. `false` if nothing is at the path.  
+/// `val` か、その下の値を置き換えます。パスに何も無ければ `false` 。  
+fn set_in_val(val: &mut Val, segments: &[PathSegment], value: &Value) -> Result<bool, String> {
+    match (segments.first(), val) {
+        (None, val) => {
+            *val = Val::parse(&value.to_toml_string_like(Some(val))?)?;
+            Ok(true)
+        }
+        (Some(PathSegment::Key(_)), Val::InlineTable(inline_table)) => {
+            set_in_inline_table(inline_table, segments, value)
+        }
+        (Some(PathSegment::Index(index)), Val::Array(array)) => {
+            set_in_array(array, *index, &segments[1..], value)
+        }
+        _ => Ok(false),
+    }
+}
+fn set_in_inline_table(
+    inline_table: &mut InlineTable,
+    segments: &[PathSegment],
+    value: &Value,
+) -> Result<bool, String> {
+    for keyval in inline_table.parts_mut().0 {
+        let len = keyval.key.get_segments().len();
+        if starts_with_keys(segments, keyval.key.get_segments())
+            && set_in_val(&mut keyval.val, &segments[len..], value)?
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+fn set_in_array(
+    array: &mut Array,
+    index: usize,
+    segments: &[PathSegment],
+    value: &Value,
+) -> Result<bool, String> {
+    let item = match array.iter_mut().nth(index) {
+        Some(m) => m,
+        None => return Ok(false),
+    };
+    match (segments.first(), item) {
+        (None, item) => {
+            let text = value.to_toml_string_like(item.to_val().as_ref())?;
+            *item = ItemValue::from_val(Val::parse(&text)?);
+            Ok(true)
+        }
+        (Some(PathSegment::Key(_)), ItemValue::InlineTable(inline_table)) => {
+            set_in_inline_table(inline_table, segments, value)
+        }
+        (Some(PathSegment::Index(index)), ItemValue::Array(array)) => {
+            set_in_array(array, *index, &segments[1..], value)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Puts new keys in the header or the key-value, as TOML text. The rest of the line stays.  
+/// ヘッダーかキー・バリューに、新しいキーをTOMLテキストとして入れます。行の残りはそのままです。  
+fn rewrite_key(elem: &mut Expression, keys: &[String]) -> Result<(), String> {
+    let dotted = to_dotted_key(keys);
+    match elem {
+        Expression::HeaderOfTable(m) => {
+            if let Some(Expression::HeaderOfTable(new)) =
+                Expression::parse_lines(&format!("[{}]\n", dotted))?.first()
+            {
+                m.tokens = new.tokens.clone();
+                m.key = new.key.clone();
+            }
+        }
+        Expression::HeaderOfArrayOfTable(m) => {
+            if let Some(Expression::HeaderOfArrayOfTable(new)) =
+                Expression::parse_lines(&format!("[[{}]]\n", dotted))?.first()
+            {
+                m.tokens = new.tokens.clone();
+                m.key = new.key.clone();
+            }
+        }
+        Keyval(_ws1, keyval, _ws2, _comment, _newline) => keyval.key = parse_key(keys)?,
+        Expression::EmptyLine(_ws, _comment, _newline) => {}
+    }
+    Ok(())
+}
+/// Key from names. Quoted if needed.  
+/// 名前からのキー。必要なら引用符で囲みます。  
+fn parse_key(keys: &[String]) -> Result<Box<Key>, String> {
+    match Expression::parse_lines(&format!("{} = 0\n", to_dotted_key(keys)))?.first() {
+        Some(Keyval(_ws1, keyval, _ws2, _comment, _newline)) => Ok(keyval.key.clone()),
+        _ => Err(format!("{} is not a key.", to_dotted_key(keys))),
+    }
+}
+/// Renames the key under `val`. `false` if nothing is at the path.  
+/// `val` の下のキーの名前を変えます。パスに何も無ければ `false` 。  
+fn rename_in_val(val: &mut Val, segments: &[PathSegment], new_key: &str) -> Result<bool, String> {
+    match (segments.first(), val) {
+        (Some(PathSegment::Index(index)), Val::Array(array)) => {
+            rename_in_array(array, *index, &segments[1..], new_key)
+        }
+        (Some(PathSegment::Key(_)), Val::InlineTable(inline_table)) => {
+            rename_in_inline_table(inline_table, segments, new_key)
+        }
+        _ => Ok(false),
+    }
+}
+fn rename_in_array(
+    array: &mut Array,
+    index: usize,
+    segments: &[PathSegment],
+    new_key: &str,
+) -> Result<bool, String> {
+    match (segments.first(), array.iter_mut().nth(index)) {
+        (Some(PathSegment::Index(i)), Some(ItemValue::Array(array))) => {
+            rename_in_array(array, *i, &segments[1..], new_key)
+        }
+        (Some(PathSegment::Key(_)), Some(ItemValue::InlineTable(inline_table))) => {
+            rename_in_inline_table(inline_table, segments, new_key)
+        }
+        _ => Ok(false),
+    }
+}
+fn rename_in_inline_table(
+    inline_table: &mut InlineTable,
+    segments: &[PathSegment],
+    new_key: &str,
+) -> Result<bool, String> {
+    // The key-values under the path, such as `b.c` and `b.d` for `b`.
+    // `b` に対する `b.c` や `b.d` のような、パスの下のキー・バリュー。
+    let len = segments.len();
+    let mut done = false;
+    for keyval in inline_table.parts_mut().0.iter_mut() {
+        let key = keyval.key.get_segments();
+        if len <= key.len() && starts_with_keys(segments, &key[..len]) {
+            let mut keys = key.to_vec();
+            keys[len - 1] = new_key.to_string();
+            keyval.key = parse_key(&keys)?;
+            done = true;
+        }
+    }
+    if done {
+        return Ok(true);
+    }
+    for keyval in inline_table.parts_mut().0 {
+        let len = keyval.key.get_segments().len();
+        if starts_with_keys(segments, keyval.key.get_segments())
+            && rename_in_val(&mut keyval.val, &segments[len..], new_key)?
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Removes the item under `val`. `false` if nothing is at the path.  
+/// `val` の下の項目を取り除きます。パスに何も無ければ `false` 。  
+fn remove_in_val(val: &mut Val, segments: &[PathSegment]) -> bool {
+    match (segments.first(), val) {
+        (Some(PathSegment::Index(index)), Val::Array(array)) => {
+            remove_in_array(array, *index, &segments[1..])
+        }
+        (Some(PathSegment::Key(_)), Val::InlineTable(inline_table)) => {
+            remove_in_inline_table(inline_table, segments)
+        }
+        _ => false,
+    }
+}
+fn remove_in_array(array: &mut Array, index: usize, segments: &[PathSegment]) -> bool {
+    match (segments.first(), array.iter_mut().nth(index)) {
+        (None, _) => array.remove(index).is_some(),
+        (Some(PathSegment::Index(i)), Some(ItemValue::Array(array))) => {
+            remove_in_array(array, *i, &segments[1..])
+        }
+        (Some(PathSegment::Key(_)), Some(ItemValue::InlineTable(inline_table))) => {
+            remove_in_inline_table(inline_table, segments)
+        }
+        _ => false,
+    }
+}
+fn remove_in_inline_table(inline_table: &mut InlineTable, segments: &[PathSegment]) -> bool {
+    // The key-values under the path, such as `b.c` and `b.d` for `b`.
+    // `b` に対する `b.c` や `b.d` のような、パスの下のキー・バリュー。
+    let under: Vec<usize> = inline_table
+        .parts()
+        .0
+        .iter()
+        .enumerate()
+        .filter(|(_, keyval)| {
+            let key = keyval.key.get_segments();
+            segments.len() <= key.len() && starts_with_keys(segments, &key[..segments.len()])
+        })
+        .map(|(i, _)| i)
+        .collect();
+    if !under.is_empty() {
+        for i in under.into_iter().rev() {
+            inline_table.remove(i);
+        }
+        return true;
+    }
+    for keyval in inline_table.parts_mut().0 {
+        let len = keyval.key.get_segments().len();
+        if starts_with_keys(segments, keyval.key.get_segments())
+            && remove_in_val(&mut keyval.val, &segments[len..])
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// The name right under `parent`, if `segments` is in it.  
+/// `segments` が `parent` の中にあれば、そのすぐ下の名前。  
+fn push_child_name(names: &mut Vec<String>, parent: &[PathSegment], segments: &[PathSegment]) {
+    if parent.len() < segments.len() && segments.starts_with(parent) {
+        if let PathSegment::Key(name) = &segments[parent.len()] {
+            if !names.contains(name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+}
+/// Same as `push_child_name`. Goes down into arrays and inline tables.  
+/// `push_child_name` と同じ。配列とインライン・テーブルの中へ降ります。  
+fn push_child_names(
+    names: &mut Vec<String>,
+    parent: &[PathSegment],
+    segments: &[PathSegment],
+    val: &Val,
+) {
+    push_child_name(names, parent, segments);
+    if !parent.starts_with(segments) && !segments.starts_with(parent) {
+        return;
+    }
+    match val {
+        Val::InlineTable(inline_table) => {
+            for (key, m) in inline_table.iter() {
+                let mut segments = segments.to_vec();
+                for key in key.get_segments() {
+                    segments.push(PathSegment::Key(key.to_string()));
+                }
+                push_child_names(names, parent, &segments, m);
+            }
+        }
+        Val::Array(array) => {
+            for (i, item) in array.iter().enumerate() {
+                if let Some(m) = item.to_val() {
+                    let mut segments = segments.to_vec();
+                    segments.push(PathSegment::Index(i));
+                    push_child_names(names, parent, &segments, &m);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `a.b.` for the table `[a.b]`. Empty for the root table.  
+/// テーブル `[a.b]` なら `a.b.` 。ルート・テーブルなら空。  
+/// How a table or an array was made. `to_value` finds redefinitions with it.  
+/// テーブルや配列の作られ方。 `to_value` はこれで再定義を見つけます。  
+#[derive(Clone, Copy, Debug)]
+enum Defined {
+    /// `a` of `[a.b]`.
+    Implicit,
+    /// `[a]`.
+    Header,
+    /// `a` of `a.b = 1`.
+    Dotted,
+    /// `a = { b = 1 }`.
+    Inline,
+    /// `a = [1, 2]`.
+    StaticArray,
+    /// `[[a]]`.
+    ArrayOfTables,
+}
+/// The parents of a table header. Inline tables and static arrays are closed.  
+/// テーブル・ヘッダーの親。インライン・テーブルと静的配列は閉じています。  
+fn define_parents(
+    defined: &mut HashMap<Vec<String>, Defined>,
+    table: &[String],
+) -> Result<(), String> {
+    for len in 1..table.len() {
+        match defined.get(&table[..len]) {
+            None => {
+                defined.insert(table[..len].to_vec(), Defined::Implicit);
+            }
+            Some(Defined::Inline) => return Err(closed_message(&table[..len])),
+            Some(Defined::StaticArray) => {
+                return Err(format!("{} is not a table.", to_dotted_key(&table[..len])))
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+/// The tables of a dotted key. They must not be made by anything else.  
+/// ドット区切りのキーのテーブル。他のもので作られていてはいけません。  
+///
+/// * `table` - Length of the table header.  
+///   テーブル・ヘッダーの長さ。  
+fn define_dotted(
+    defined: &mut HashMap<Vec<String>, Defined>,
+    segments: &[String],
+    table: usize,
+) -> Result<(), String> {
+    for len in table + 1..segments.len() {
+        match defined.get(&segments[..len]) {
+            None => {
+                defined.insert(segments[..len].to_vec(), Defined::Dotted);
+            }
+            Some(Defined::Dotted) => {}
+            Some(Defined::Inline) => return Err(closed_message(&segments[..len])),
+            Some(Defined::StaticArray) | Some(Defined::ArrayOfTables) => {
+                return Err(format!(
+                    "{} is not a table.",
+                    to_dotted_key(&segments[..len])
+                ))
+            }
+            Some(Defined::Header) | Some(Defined::Implicit) => {
+                return Err(format!(
+                    "{} is defined twice.",
+                    to_dotted_key(&segments[..len])
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+/// `a is an inline table. It can't be extended.`
+fn closed_message(segments: &[String]) -> String {
+    format!(
+        "{} is an inline table. It can't be extended.",
+        to_dotted_key(segments)
+    )
+}
+fn dotted(table: &[String]) -> String {
+    if table.is_empty() {
+        String::new()
+    } else {
+        format!("{}.", to_dotted_key(table))
+    }
+}
+
+/// `val` is written at `key`. Goes down into inline tables.  
+/// `val` は `key` に書かれています。インライン・テーブルの中へ降ります。  
+fn find_val<'a>(val: &'a Val, key: &[String], segments: &[String]) -> Option<&'a Val> {
+    if !segments.starts_with(key) {
+        return None;
+    }
+    let rest = &segments[key.len()..];
+    if rest.is_empty() {
+        return Some(val);
+    }
+    if let Val::InlineTable(inline_table) = val {
+        for (key, m) in inline_table.iter() {
+            if let Some(m) = find_val(m, key.get_segments(), rest) {
+                return Some(m);
+            }
+        }
+    }
+    None
+}
+
+/// The value under `val` at the path. Goes down into arrays and inline tables.  
+/// パスの位置の、 `val` の下の値。配列とインライン・テーブルの中へ降ります。  
+fn get_in_val(val: &Val, segments: &[PathSegment]) -> Option<Val> {
+    match (segments.first(), val) {
+        (None, _) => Some(val.clone()),
+        (Some(PathSegment::Index(index)), Val::Array(array)) => {
+            get_in_val(&array.get(*index)?.to_val()?, &segments[1..])
+        }
+        (Some(PathSegment::Key(_)), Val::InlineTable(inline_table)) => {
+            inline_table.iter().find_map(|(key, m)| {
+                let key = key.get_segments();
+                if starts_with_keys(segments, key) {
+                    get_in_val(m, &segments[key.len()..])
+                } else {
+                    None
+                }
+            })
+        }
+        _ => None,
+    }
+}
+/// A line by the path from a table, and its value. A header has no value.  
+/// テーブルからのパスによる行と、その値。ヘッダーには値がありません。  
+type Line<'a> = (&'a [PathSegment], Option<&'a Val>);
+/// Lines under a table, by the path from it. A header has no value.  
+/// Indexes make an array of inline tables, keys make an inline table.  
+/// テーブルの下の行。そこからのパス付き。ヘッダーには値がありません。  
+/// 添え字はインライン・テーブルの配列を、キーはインライン・テーブルを作ります。  
+fn gather_lines(lines: &[Line]) -> Val {
+    // The lines by the first segment, in the written order.
+    // 最初のセグメントごとの行。書かれた順。
+    let mut groups: Vec<(&PathSegment, Vec<Line>)> = Vec::new();
+    for (segments, val) in lines {
+        if let Some((first, rest)) = segments.split_first() {
+            match groups.iter_mut().find(|(m, _lines)| *m == first) {
+                Some((_first, group)) => group.push((rest, *val)),
+                None => groups.push((first, vec![(rest, *val)])),
+            }
+        }
+    }
+    if let Some((PathSegment::Index(_), _lines)) = groups.first() {
+        let mut array = Array::default();
+        for (i, (_index, group)) in groups.iter().enumerate() {
+            if 0 < i {
+                array.push_comma();
+            }
+            if let Val::InlineTable(m) = gather_lines(group) {
+                array.push_inline_table(&m);
+            }
+        }
+        return Val::Array(array);
+    }
+    let mut inline_table = InlineTable::default();
+    for (i, (first, group)) in groups.iter().enumerate() {
+        if let PathSegment::Key(key) = first {
+            if 0 < i {
+                inline_table.push_comma();
+            }
+            // The value written at the key, or the lines under it.
+            // キーに書かれた値か、その下の行。
+            let val = match group
+                .iter()
+                .find(|(rest, val)| rest.is_empty() && val.is_some())
+            {
+                Some((_rest, Some(val))) => (*val).clone(),
+                _ => gather_lines(group),
+            };
+            inline_table.push_keyval(&crate::model::layer225::Keyval::new(
+                &Key::from_segments(std::slice::from_ref(key)),
+                &val,
+            ));
+        }
+    }
+    Val::InlineTable(inline_table)
+}
+
+/// `doc["servers"]["alpha"]["ip"]`. Never panics, see `Value::Absent`.  
+/// パニックしません。 `Value::Absent` を見てください。  
+impl Index<&str> for TomlDocument {
+    type Output = Value;
+    fn index(&self, key: &str) -> &Value {
+        &self.root()[key]
+    }
+}
 impl fmt::Display for TomlDocument {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut buf = String::new();
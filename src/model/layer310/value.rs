@@ -0,0 +1,397 @@
+//! Value model.  
+//! 値モデル。  
+//!
+//! # Examples
+//!
+//! ```
+//! // let value = doc.to_value()?;
+//! // if let Value::Table(root) = value { ... }
+//! ```
+
+use crate::model::{
+    layer210::{
+        basic_string::{escape, head_newline},
+        key::to_dotted_key,
+        literal_string::to_raw,
+        LiteralValueType, StringStyle,
+    },
+    layer220::ItemValue,
+    layer225::{from_toml::join_path, InlineTable, Val},
+    layer310::{Path, PathSegment, Table, Value},
+};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Index;
+
+/// What indexing gives when nothing is there.  
+/// 何も無いときに添え字が返すもの。  
+pub(crate) static ABSENT: Value = Value::Absent;
+
+impl Value {
+    /// Name of the type. For messages.  
+    /// 型の名前。メッセージ用。  
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Integer(_) => LiteralValueType::Integer.name(),
+            Value::Float(_) => LiteralValueType::Float.name(),
+            Value::Boolean(_) => LiteralValueType::Boolean.name(),
+            Value::Datetime(_) => LiteralValueType::DateTime.name(),
+            Value::Array(_) => "array",
+            Value::Table(_) => "table",
+            Value::Absent => "absent",
+        }
+    }
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Value::Absent)
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Float(n) => Some(*n),
+            _ => None,
+        }
+    }
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+    pub fn as_datetime(&self) -> Option<&str> {
+        match self {
+            Value::Datetime(s) => Some(s),
+            _ => None,
+        }
+    }
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(m) => Some(m),
+            _ => None,
+        }
+    }
+    pub fn as_table(&self) -> Option<&Table> {
+        match self {
+            Value::Table(m) => Some(m),
+            _ => None,
+        }
+    }
+    /// Value at the path. `Value::Absent` if nothing is there.  
+    /// パスの位置の値。何も無ければ `Value::Absent` 。  
+    pub fn get_by_path(&self, path: &Path) -> &Value {
+        let mut m = self;
+        for segment in path.segments() {
+            m = match segment {
+                PathSegment::Key(key) => &m[key.as_str()],
+                PathSegment::Index(index) => &m[*index],
+            };
+        }
+        m
+    }
+    /// Everything under the value with its path, parent first. Array items too. Not the value itself.  
+    /// 値の下の全てと、そのパス。親が先です。配列の項目も。値自身は含みません。  
+    pub fn walk(&self) -> Vec<(Path, &Value)> {
+        let mut vec = Vec::new();
+        walk(self, &Path::default(), &mut vec);
+        vec
+    }
+    /// An array of tables, `[[a]]` or `[{ x = 1 }]`. Its keys are reached through the tables.  
+    /// テーブルの配列。そのキーにはテーブルを通って届きます。  
+    pub fn is_array_of_tables(&self) -> bool {
+        match self {
+            Value::Array(vec) => !vec.is_empty() && vec.iter().all(|m| m.as_table().is_some()),
+            _ => false,
+        }
+    }
+    /// Owned copy of a right value. Dotted keys in inline tables become nested tables.  
+    /// 右値の所有コピー。インライン・テーブルのドット付きキーは入れ子のテーブルになります。  
+    ///
+    /// # Errors
+    ///
+    /// `2021-13-99x is not a TOML value.`  
+    /// `is 9223372036854775808, out of the range of a TOML integer (64-bit signed).`  
+    pub fn from_val(val: &Val) -> Result<Value, String> {
+        match val {
+            Val::Array(array) => {
+                let mut vec = Vec::new();
+                for (i, item) in array.iter().enumerate() {
+                    match Value::from_item_value(item) {
+                        Ok(m) => vec.push(m),
+                        Err(why) => return Err(join_path(&format!("[{}]", i), &why)),
+                    }
+                }
+                Ok(Value::Array(vec))
+            }
+            Val::BasicString(m) => Ok(Value::String(m.value())),
+            Val::InlineTable(m) => Ok(Value::Table(Value::from_inline_table(m)?)),
+            Val::LiteralValue(m) => match m.get_type() {
+                Some(LiteralValueType::Boolean) => Ok(Value::Boolean(m.to_bool()?)),
+                Some(LiteralValueType::DateTime) => Ok(Value::Datetime(m.to_string())),
+                Some(LiteralValueType::Float) => Ok(Value::Float(m.to_float()?)),
+                Some(LiteralValueType::Integer) => match m.to_int() {
+                    Ok(n) => Ok(Value::Integer(n)),
+                    // The digits are right, but too many.
+                    // 数字は正しいが、多すぎます。
+                    Err(_) if m.to_int::<f64>().is_ok() => Err(out_of_range_message(m)),
+                    Err(why) => Err(why),
+                },
+                None => Err(format!("{} is not a TOML value.", m)),
+            },
+            Val::LiteralString(m) => Ok(Value::String(m.value())),
+        }
+    }
+    fn from_item_value(item: &ItemValue) -> Result<Value, String> {
+        match item.to_val() {
+            Some(val) => Value::from_val(&val),
+            None => Err(format!("{} is not a value.", item)),
+        }
+    }
+    /// TOML text of the value. Tables are inline.  
+    /// 値のTOMLテキスト。テーブルはインラインです。  
+    ///
+    /// ```
+    /// // assert_eq!(Value::from(vec![1, 2]).to_toml_string(), Ok("[1, 2]".to_string()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `has an absent value.`  
+    pub fn to_toml_string(&self) -> Result<String, String> {
+        self.to_toml_string_like(None)
+    }
+    /// Same as `to_toml_string`, in the style of the old value.  
+    /// `0x` stays hexadecimal, `1_000` keeps the separators, `'abc'` stays literal if it can.  
+    /// `to_toml_string` と同じですが、古い値の書き方に合わせます。  
+    pub(crate) fn to_toml_string_like(&self, like: Option<&Val>) -> Result<String, String> {
+        match self {
+            Value::String(m) => Ok(match like {
+                Some(Val::LiteralString(old)) => {
+                    let head = head_newline(&old.raw);
+                    literal_string(m, old.style, head)
+                        .unwrap_or_else(|| basic_string(m, old.style, head))
+                }
+                Some(Val::BasicString(old)) => basic_string(m, old.style, head_newline(&old.raw)),
+                _ => basic_string(m, StringStyle::SingleLine, ""),
+            }),
+            Value::Integer(m) => Ok(match like {
+                Some(Val::LiteralValue(old))
+                    if old.get_type() == Some(LiteralValueType::Integer) =>
+                {
+                    integer_like(*m, &old.to_string())
+                }
+                _ => m.to_string(),
+            }),
+            Value::Float(m) => Ok(if m.is_nan() {
+                "nan".to_string()
+            } else if m.is_infinite() {
+                if m.is_sign_negative() { "-inf" } else { "inf" }.to_string()
+            } else {
+                // `{:?}` keeps `.0`.
+                format!("{:?}", m)
+            }),
+            Value::Boolean(m) => Ok(m.to_string()),
+            Value::Datetime(m) => Ok(m.to_string()),
+            Value::Array(items) => {
+                let olds: Vec<Option<Val>> = match like {
+                    Some(Val::Array(old)) => old.iter().map(|m| m.to_val()).collect(),
+                    _ => Vec::new(),
+                };
+                let mut vec = Vec::new();
+                for (i, item) in items.iter().enumerate() {
+                    let old = olds.get(i).and_then(|m| m.as_ref());
+                    match item.to_toml_string_like(old) {
+                        Ok(m) => vec.push(m),
+                        Err(why) => return Err(join_path(&format!("[{}]", i), &why)),
+                    }
+                }
+                Ok(format!("[{}]", vec.join(", ")))
+            }
+            Value::Table(table) => {
+                if table.is_empty() {
+                    return Ok("{}".to_string());
+                }
+                let mut vec = Vec::new();
+                for (key, m) in table.iter() {
+                    let key = to_dotted_key(&[key.to_string()]);
+                    match m.to_toml_string() {
+                        Ok(m) => vec.push(format!("{} = {}", key, m)),
+                        Err(why) => return Err(join_path(&key, &why)),
+                    }
+                }
+                Ok(format!("{{ {} }}", vec.join(", ")))
+            }
+            Value::Absent => Err("has an absent value.".to_string()),
+        }
+    }
+    fn from_inline_table(inline_table: &InlineTable) -> Result<Table, String> {
+        let mut table = Table::default();
+        for (key, val) in inline_table.iter() {
+            table.insert_by_segments(key.get_segments(), Value::from_val(val)?)?;
+        }
+        Ok(table)
+    }
+}
+
+/// `"abc"` or `"""abc"""`, escaped. `head` is the newline after `"""`.  
+/// エスケープした `"abc"` か `"""abc"""` 。 `head` は `"""` の後の改行です。  
+fn basic_string(s: &str, style: StringStyle, head: &str) -> String {
+    let quotation = match style {
+        StringStyle::SingleLine => "\"",
+        StringStyle::MultiLine => "\"\"\"",
+    };
+    format!("{}{}{}", quotation, escape(s, style, head), quotation)
+}
+/// `'abc'` or `'''abc'''`. `None` if it can not be written without escapes.  
+/// `'abc'` か `'''abc'''` 。エスケープ無しで書けなければ `None` 。  
+fn literal_string(s: &str, style: StringStyle, head: &str) -> Option<String> {
+    let quotation = match style {
+        StringStyle::SingleLine => "'",
+        StringStyle::MultiLine => "'''",
+    };
+    to_raw(s, style, head).map(|raw| format!("{}{}{}", quotation, raw, quotation))
+}
+/// `is 18446744073709551615, out of the range of a TOML integer (64-bit signed).`
+fn out_of_range_message<T: fmt::Display>(n: T) -> String {
+    format!(
+        "is {}, out of the range of a TOML integer (64-bit signed).",
+        n
+    )
+}
+/// `n` written like `old`, such as `0xFF` or `1_000`.  
+/// `0xFF` や `1_000` のように、 `old` に合わせて書いた `n` 。  
+fn integer_like(n: i64, old: &str) -> String {
+    let prefix = old.get(..2).unwrap_or("");
+    let digits = old.get(2..).unwrap_or("");
+    match prefix {
+        // No sign after a prefix.
+        // 接頭辞の後に符号は付けられません。
+        "0x" if 0 <= n => {
+            if digits.chars().any(|ch| ch.is_ascii_uppercase()) {
+                format!("0x{:X}", n)
+            } else {
+                format!("0x{:x}", n)
+            }
+        }
+        "0o" if 0 <= n => format!("0o{:o}", n),
+        "0b" if 0 <= n => format!("0b{:b}", n),
+        _ if old.contains('_') && !old.starts_with("0x") => {
+            let digits: Vec<char> = n.unsigned_abs().to_string().chars().collect();
+            let groups: Vec<String> = digits
+                .rchunks(3)
+                .rev()
+                .map(|m| m.iter().collect())
+                .collect();
+            let text = groups.join("_");
+            if n < 0 {
+                format!("-{}", text)
+            } else {
+                text
+            }
+        }
+        _ => n.to_string(),
+    }
+}
+
+fn walk<'a>(value: &'a Value, path: &Path, vec: &mut Vec<(Path, &'a Value)>) {
+    match value {
+        Value::Table(table) => {
+            for (key, m) in table.iter() {
+                let mut path = path.clone();
+                path.push_key(key);
+                vec.push((path.clone(), m));
+                walk(m, &path, vec);
+            }
+        }
+        Value::Array(items) => {
+            for (i, m) in items.iter().enumerate() {
+                let mut path = path.clone();
+                path.push_index(i);
+                vec.push((path.clone(), m));
+                walk(m, &path, vec);
+            }
+        }
+        _ => {}
+    }
+}
+
+macro_rules! impl_from_for_value {
+    ($variant:ident, $($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(m: $t) -> Self {
+                    Value::$variant(m.into())
+                }
+            }
+        )*
+    };
+}
+impl_from_for_value!(Integer, i8, i16, i32, i64, u8, u16, u32);
+macro_rules! impl_try_from_for_value {
+    ($($t:ty),*) => {
+        $(
+            /// TOML integers are 64-bit signed.
+            /// TOML の整数は 64 ビット符号付きです。
+            impl TryFrom<$t> for Value {
+                type Error = String;
+                fn try_from(m: $t) -> Result<Self, String> {
+                    i64::try_from(m)
+                        .map(Value::Integer)
+                        .map_err(|_| out_of_range_message(m))
+                }
+            }
+        )*
+    };
+}
+impl_try_from_for_value!(i128, u64);
+impl_from_for_value!(Float, f64);
+/// `0.1f32` is `0.1`, not `0.10000000149011612`.  
+/// `0.1f32` は `0.10000000149011612` ではなく `0.1` です。  
+impl From<f32> for Value {
+    fn from(m: f32) -> Self {
+        // The shortest text of the `f32`, read as a `f64`.
+        // `f32` の最短のテキストを `f64` として読みます。
+        Value::Float(m.to_string().parse().unwrap_or_else(|_| m.into()))
+    }
+}
+impl_from_for_value!(Boolean, bool);
+impl_from_for_value!(String, String, &str);
+impl_from_for_value!(Table, Table);
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(m: Vec<T>) -> Self {
+        Value::Array(m.into_iter().map(|m| m.into()).collect())
+    }
+}
+
+/// `value["key"]`. `Value::Absent` unless it is a table with the key.  
+/// キーを持つテーブルでなければ `Value::Absent` 。  
+impl Index<&str> for Value {
+    type Output = Value;
+    fn index(&self, key: &str) -> &Value {
+        match self {
+            Value::Table(m) => &m[key],
+            _ => &ABSENT,
+        }
+    }
+}
+/// `value[0]`. `Value::Absent` unless it is an array with the index.  
+/// 添え字を持つ配列でなければ `Value::Absent` 。  
+impl Index<usize> for Value {
+    type Output = Value;
+    fn index(&self, index: usize) -> &Value {
+        match self {
+            Value::Array(m) => m.get(index).unwrap_or(&ABSENT),
+            _ => &ABSENT,
+        }
+    }
+}
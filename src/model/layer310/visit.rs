@@ -0,0 +1,309 @@
+//! Default recursion of the visitors.  
+//! ビジターの既定の再帰。  
+//!
+//! # Examples
+//!
+//! ```
+//! // impl VisitMut for Spacing {
+//! //     fn visit_keyval_mut(&mut self, m: &mut Keyval) {
+//! //         m.ws1 = space();
+//! //         m.ws2 = space();
+//! //         visit::walk_keyval_mut(self, m);
+//! //     }
+//! // }
+//! ```
+
+use crate::model::{
+    layer110::{token::tokens_stringify, Token, TokenType},
+    layer210::{Comment, Key, WsCommentNewline},
+    layer220::{Array, ItemValue},
+    layer225::{InlineTable, Keyval, Val},
+    layer230::{Expression, HeaderOfArrayOfTable, HeaderOfTable},
+    layer310::{TomlDocument, Visit, VisitMut},
+};
+
+/// Every expression.  
+pub fn walk_document<V: Visit + ?Sized>(v: &mut V, m: &TomlDocument) {
+    for elem in &m.elements {
+        v.visit_expression(elem);
+    }
+}
+/// Whitespace, the header or key-value, the comment and the newline.  
+pub fn walk_expression<V: Visit + ?Sized>(v: &mut V, m: &Expression) {
+    match m {
+        Expression::EmptyLine(ws, comment, newline) => {
+            v.visit_ws(ws);
+            if let Some(comment) = comment {
+                v.visit_comment(comment);
+            }
+            v.visit_newline(newline);
+        }
+        Expression::Keyval(ws1, keyval, ws2, comment, newline) => {
+            v.visit_ws(ws1);
+            v.visit_keyval(keyval);
+            v.visit_ws(ws2);
+            if let Some(comment) = comment {
+                v.visit_comment(comment);
+            }
+            v.visit_newline(newline);
+        }
+        Expression::HeaderOfTable(m) => v.visit_header_of_table(m),
+        Expression::HeaderOfArrayOfTable(m) => v.visit_header_of_array_of_table(m),
+    }
+}
+/// `[a.b]`. The key, and the whitespace, comment and newline around it.  
+pub fn walk_header_of_table<V: Visit + ?Sized>(v: &mut V, m: &HeaderOfTable) {
+    v.visit_ws(&m.ws1);
+    v.visit_key(&m.key);
+    v.visit_ws(&m.ws2);
+    if let Some(comment) = &m.comment {
+        v.visit_comment(comment);
+    }
+    v.visit_newline(&m.newline);
+}
+/// `[[a.b]]`. Same as `[a.b]`.  
+pub fn walk_header_of_array_of_table<V: Visit + ?Sized>(v: &mut V, m: &HeaderOfArrayOfTable) {
+    v.visit_ws(&m.ws1);
+    v.visit_key(&m.key);
+    v.visit_ws(&m.ws2);
+    if let Some(comment) = &m.comment {
+        v.visit_comment(comment);
+    }
+    v.visit_newline(&m.newline);
+}
+/// `a = 1`. The key, the whitespace around `=` and the value.  
+pub fn walk_keyval<V: Visit + ?Sized>(v: &mut V, m: &Keyval) {
+    v.visit_key(&m.key);
+    v.visit_ws(&m.ws1);
+    v.visit_ws(&m.ws2);
+    v.visit_val(&m.val);
+}
+/// Goes to the variant.  
+pub fn walk_val<V: Visit + ?Sized>(v: &mut V, m: &Val) {
+    match m {
+        Val::Array(m) => v.visit_array(m),
+        Val::BasicString(m) => v.visit_basic_string(m),
+        Val::InlineTable(m) => v.visit_inline_table(m),
+        Val::LiteralValue(m) => v.visit_literal_value(m),
+        Val::LiteralString(m) => v.visit_literal_string(m),
+    }
+}
+/// Goes to the variant.  
+pub fn walk_item_value<V: Visit + ?Sized>(v: &mut V, m: &ItemValue) {
+    match m {
+        ItemValue::Array(m) => v.visit_array(m),
+        ItemValue::BasicString(m) => v.visit_basic_string(m),
+        ItemValue::InlineTable(m) => v.visit_inline_table(m),
+        ItemValue::Keyval(m) => v.visit_keyval(m),
+        ItemValue::LiteralValue(m) => v.visit_literal_value(m),
+        ItemValue::LiteralString(m) => v.visit_literal_string(m),
+    }
+}
+/// Items, and the whitespace, comments and newlines between them.  
+pub fn walk_array<V: Visit + ?Sized>(v: &mut V, m: &Array) {
+    // `layout` is `[before 0, after 0, before 1, after 1, ..., after the trailing comma]`.
+    // `layout` は `[項目0の前, 項目0の後, 項目1の前, 項目1の後, ..., 末尾のカンマの後]` です。
+    let (items, layout) = m.parts();
+    for (i, item) in items.iter().enumerate() {
+        if let Some(m) = layout.get(2 * i) {
+            v.visit_ws_comment_newline(m);
+        }
+        v.visit_item_value(item);
+        if let Some(m) = layout.get(2 * i + 1) {
+            v.visit_ws_comment_newline(m);
+        }
+    }
+    if let Some(m) = layout.get(2 * items.len()) {
+        v.visit_ws_comment_newline(m);
+    }
+}
+/// Key-values, and the whitespace between them.  
+pub fn walk_inline_table<V: Visit + ?Sized>(v: &mut V, m: &InlineTable) {
+    let (items, layout) = m.parts();
+    for (i, item) in items.iter().enumerate() {
+        if let Some(m) = layout.get(2 * i) {
+            v.visit_ws(m);
+        }
+        v.visit_keyval(item);
+        if let Some(m) = layout.get(2 * i + 1) {
+            v.visit_ws(m);
+        }
+    }
+    if let Some(m) = layout.get(2 * items.len()) {
+        v.visit_ws(m);
+    }
+}
+/// Comments in it.  
+pub fn walk_ws_comment_newline<V: Visit + ?Sized>(v: &mut V, m: &WsCommentNewline) {
+    for comment in m.get_comments() {
+        v.visit_comment(&comment);
+    }
+}
+
+/// Every expression.  
+pub fn walk_document_mut<V: VisitMut + ?Sized>(v: &mut V, m: &mut TomlDocument) {
+    // `elements_mut()` drops the caches, so `doc["key"]` reads it again.
+    // `elements_mut()` はキャッシュを捨てるので、 `doc["key"]` は読み直します。
+    for elem in m.elements_mut() {
+        v.visit_expression_mut(elem);
+    }
+}
+/// Whitespace, the header or key-value, the comment and the newline.  
+pub fn walk_expression_mut<V: VisitMut + ?Sized>(v: &mut V, m: &mut Expression) {
+    match m {
+        Expression::EmptyLine(ws, comment, newline) => {
+            v.visit_ws_mut(ws);
+            if let Some(comment) = comment {
+                v.visit_comment_mut(comment);
+            }
+            v.visit_newline_mut(newline);
+        }
+        Expression::Keyval(ws1, keyval, ws2, comment, newline) => {
+            v.visit_ws_mut(ws1);
+            v.visit_keyval_mut(keyval);
+            v.visit_ws_mut(ws2);
+            if let Some(comment) = comment {
+                v.visit_comment_mut(comment);
+            }
+            v.visit_newline_mut(newline);
+        }
+        Expression::HeaderOfTable(m) => v.visit_header_of_table_mut(m),
+        Expression::HeaderOfArrayOfTable(m) => v.visit_header_of_array_of_table_mut(m),
+    }
+}
+/// `[a.b]`. The key, and the whitespace, comment and newline around it.  
+pub fn walk_header_of_table_mut<V: VisitMut + ?Sized>(v: &mut V, m: &mut HeaderOfTable) {
+    v.visit_ws_mut(&mut m.ws1);
+    walk_header_key_mut(v, &mut m.tokens, &mut m.key);
+    v.visit_ws_mut(&mut m.ws2);
+    if let Some(comment) = &mut m.comment {
+        v.visit_comment_mut(comment);
+    }
+    v.visit_newline_mut(&mut m.newline);
+}
+/// `[[a.b]]`. Same as `[a.b]`.  
+pub fn walk_header_of_array_of_table_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    m: &mut HeaderOfArrayOfTable,
+) {
+    v.visit_ws_mut(&mut m.ws1);
+    walk_header_key_mut(v, &mut m.tokens, &mut m.key);
+    v.visit_ws_mut(&mut m.ws2);
+    if let Some(comment) = &mut m.comment {
+        v.visit_comment_mut(comment);
+    }
+    v.visit_newline_mut(&mut m.newline);
+}
+/// The key of a header. If it is changed, the text between the brackets follows it.  
+/// ヘッダーのキー。変えられたら、角括弧の間のテキストも合わせます。  
+fn walk_header_key_mut<V: VisitMut + ?Sized>(v: &mut V, tokens: &mut Vec<Token>, key: &mut Key) {
+    let old = key.to_string();
+    v.visit_key_mut(key);
+    let new = key.to_string();
+    if new != old {
+        let text = tokens_stringify(tokens);
+        let start = text.len() - text.trim_start().len();
+        let end = text.trim_end().len().max(start);
+        *tokens = vec![Token::new(
+            &format!("{}{}{}", &text[..start], new, &text[end..]),
+            TokenType::Table,
+        )];
+    }
+}
+/// `a = 1`. The key, the whitespace around `=` and the value.  
+pub fn walk_keyval_mut<V: VisitMut + ?Sized>(v: &mut V, m: &mut Keyval) {
+    v.visit_key_mut(&mut m.key);
+    v.visit_ws_mut(&mut m.ws1);
+    v.visit_ws_mut(&mut m.ws2);
+    v.visit_val_mut(&mut m.val);
+}
+/// Goes to the variant.  
+pub fn walk_val_mut<V: VisitMut + ?Sized>(v: &mut V, m: &mut Val) {
+    match m {
+        Val::Array(m) => v.visit_array_mut(m),
+        Val::BasicString(m) => v.visit_basic_string_mut(m),
+        Val::InlineTable(m) => v.visit_inline_table_mut(m),
+        Val::LiteralValue(m) => v.visit_literal_value_mut(m),
+        Val::LiteralString(m) => v.visit_literal_string_mut(m),
+    }
+}
+/// Goes to the variant.  
+pub fn walk_item_value_mut<V: VisitMut + ?Sized>(v: &mut V, m: &mut ItemValue) {
+    match m {
+        ItemValue::Array(m) => v.visit_array_mut(m),
+        ItemValue::BasicString(m) => v.visit_basic_string_mut(m),
+        ItemValue::InlineTable(m) => v.visit_inline_table_mut(m),
+        ItemValue::Keyval(m) => v.visit_keyval_mut(m),
+        ItemValue::LiteralValue(m) => v.visit_literal_value_mut(m),
+        ItemValue::LiteralString(m) => v.visit_literal_string_mut(m),
+    }
+}
+/// Items, and the whitespace, comments and newlines between them.  
+pub fn walk_array_mut<V: VisitMut + ?Sized>(v: &mut V, m: &mut Array) {
+    // `layout` is `[before 0, after 0, before 1, after 1, ..., after the trailing comma]`.
+    // `layout` は `[項目0の前, 項目0の後, 項目1の前, 項目1の後, ..., 末尾のカンマの後]` です。
+    let (items, layout) = m.parts_mut();
+    for (i, item) in items.iter_mut().enumerate() {
+        if let Some(m) = layout.get_mut(2 * i) {
+            v.visit_ws_comment_newline_mut(m);
+        }
+        v.visit_item_value_mut(item);
+        if let Some(m) = layout.get_mut(2 * i + 1) {
+            v.visit_ws_comment_newline_mut(m);
+        }
+    }
+    if let Some(m) = layout.get_mut(2 * items.len()) {
+        v.visit_ws_comment_newline_mut(m);
+    }
+}
+/// Key-values, and the whitespace between them.  
+pub fn walk_inline_table_mut<V: VisitMut + ?Sized>(v: &mut V, m: &mut InlineTable) {
+    let (items, layout) = m.parts_mut();
+    for (i, item) in items.iter_mut().enumerate() {
+        if let Some(m) = layout.get_mut(2 * i) {
+            v.visit_ws_mut(m);
+        }
+        v.visit_keyval_mut(item);
+        if let Some(m) = layout.get_mut(2 * i + 1) {
+            v.visit_ws_mut(m);
+        }
+    }
+    if let Some(m) = layout.get_mut(2 * items.len()) {
+        v.visit_ws_mut(m);
+    }
+}
+/// Comments in it. An edited comment is written back into the tokens.  
+/// 含まれるコメント。編集したコメントはトークンへ書き戻します。  
+pub fn walk_ws_comment_newline_mut<V: VisitMut + ?Sized>(v: &mut V, m: &mut WsCommentNewline) {
+    let mut tokens = Vec::new();
+    let mut comment: Option<Comment> = None;
+    for token in m.tokens.drain(..) {
+        if let TokenType::Comment = token.type_ {
+            comment
+                .get_or_insert_with(Comment::default)
+                .push_token(&token);
+        } else {
+            if let Some(comment) = comment.take() {
+                visit_comment_tokens_mut(v, comment, &mut tokens);
+            }
+            tokens.push(token);
+        }
+    }
+    if let Some(comment) = comment {
+        visit_comment_tokens_mut(v, comment, &mut tokens);
+    }
+    m.tokens = tokens;
+}
+/// The tokens stay comments, so `get_comments` finds it again.  
+/// トークンはコメントのままなので、 `get_comments` は再び見つけます。  
+fn visit_comment_tokens_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    mut comment: Comment,
+    tokens: &mut Vec<Token>,
+) {
+    v.visit_comment_mut(&mut comment);
+    for mut token in comment.tokens {
+        token.type_ = TokenType::Comment;
+        tokens.push(token);
+    }
+}
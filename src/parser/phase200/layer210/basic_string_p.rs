@@ -12,11 +12,11 @@
 
 use crate::model::{
     layer110::{Token, TokenType},
-    layer210::BasicString,
+    layer210::{BasicString, StringStyle},
 };
-use crate::parser::phase200::error;
 use crate::parser::phase200::error_via;
 use crate::parser::phase200::layer210::{BasicStringP, EscapeSequenceP, PResult};
+use crate::parser::phase200::{control_character_message, error};
 use casual_logger::Table;
 use look_ahead_items::LookAheadItems;
 
@@ -35,6 +35,12 @@ pub enum State {
     MultiLineEnd1,
     MultiLineEnd2,
     MultiLineEscapeSequence,
+    // The first newline after `"""` is trimmed.
+    // `"""` の直後の改行は除去します。
+    MultiLineFirstNewline,
+    // Line ending backslash. Whitespace and newline follow.
+    // 行末のバックスラッシュ。空白と改行が続きます。
+    MultiLineLineEndingBackslash,
     // Trim start.
     // 行頭の空白の除去。
     MultiLineTrimStart,
@@ -55,22 +61,51 @@ impl BasicStringP {
             escape_sequence_p: None,
             buffer: Some(BasicString::default()),
             state: State::First,
+            quotes: 0,
         }
     }
     /// # Arguments
     ///
     /// * `look_ahead_items` - Tokens contains look ahead.  
-    ///             先読みを含むトークン。  
+    ///   先読みを含むトークン。  
     /// # Returns
     ///
     /// * `PResult` - Result.  
     ///               結果。
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         let chr0 = look_ahead_items.get(0).unwrap();
+        if let Some(m) = self.buffer.as_mut() {
+            m.push_raw_character(*chr0);
+
+            // Multi-line strings can contain newlines.
+            // 複数行文字列は改行を含められます。
+            let newline = match (chr0, look_ahead_items.get(1)) {
+                ('\n', _) | ('\r', Some('\n')) => m.style == StringStyle::MultiLine,
+                _ => false,
+            };
+            if !newline {
+                if let Some(message) = control_character_message(*chr0, "a basic string") {
+                    return error(
+                        self.log().str("message", &message),
+                        look_ahead_items,
+                        "basic_string_p.rs.74.",
+                    );
+                }
+            }
+        }
         match self.state {
             State::BeforeMultiLine => {
                 // print!("trace.8.");
-                self.state = State::MultiLine;
+                // Look-ahead.
+                // 先読み。
+                match look_ahead_items.get(1) {
+                    Some('\r') | Some('\n') => {
+                        self.state = State::MultiLineFirstNewline;
+                    }
+                    _ => {
+                        self.state = State::MultiLine;
+                    }
+                }
             }
             State::End => {
                 return error(&mut self.log(), look_ahead_items, "basic_string_p.rs.66.");
@@ -86,13 +121,13 @@ impl BasicStringP {
                                 '"' => {
                                     //print!("trace.7.");
                                     // Before triple double quoted string.
+                                    self.buffer.as_mut().unwrap().style = StringStyle::MultiLine;
                                     self.state = State::BeforeMultiLine;
                                 }
                                 _ => {
                                     // End of syntax. Empty string.
                                     // 構文の終わり。 空文字列。
-                                    self.state = State::End;
-                                    return PResult::End;
+                                    return self.end();
                                 }
                             }
                         } else {
@@ -114,7 +149,10 @@ impl BasicStringP {
                         {
                             PResult::End => {
                                 return error(
-                                    &mut self.log(),
+                                    self.log().str(
+                                        "message",
+                                        "A line ending backslash is only allowed in multi-line basic strings.",
+                                    ),
                                     &look_ahead_items,
                                     "basic_string_p.rs.108.",
                                 );
@@ -140,50 +178,7 @@ impl BasicStringP {
                 }
             }
             State::MultiLine => {
-                match chr0 {
-                    // "
-                    '"' => {
-                        // print!("trace.10.");
-                        if check_triple_double_quotation(look_ahead_items) {
-                            self.state = State::MultiLineEnd1;
-                        } else {
-                            let m = self.buffer.as_mut().unwrap();
-                            m.push_token(&Token::from_character(*chr0, TokenType::BasicString));
-                        }
-                    }
-                    // \
-                    '\\' => {
-                        self.escape_sequence_p = Some(EscapeSequenceP::default());
-                        match self
-                            .escape_sequence_p
-                            .as_mut()
-                            .unwrap()
-                            .parse(look_ahead_items)
-                        {
-                            PResult::End => {
-                                // 行末の \ だったなら。
-                                // println!("[trace200 行末の \\ だったなら。]");
-                                self.state = State::MultiLineTrimStart;
-                            }
-                            PResult::Err(mut table) => {
-                                return error_via(
-                                    &mut table,
-                                    &mut self.log(),
-                                    &look_ahead_items,
-                                    "basic_string_p.rs.139.",
-                                );
-                            }
-                            PResult::Ongoing => {
-                                self.state = State::MultiLineEscapeSequence;
-                            }
-                        }
-                    }
-                    _ => {
-                        // print!("trace.12.");
-                        let m = self.buffer.as_mut().unwrap();
-                        m.push_token(&Token::from_character(*chr0, TokenType::BasicString));
-                    }
-                }
+                return self.parse_multi_line(look_ahead_items);
             }
             State::MultiLineEnd1 => {
                 match chr0 {
@@ -202,8 +197,7 @@ impl BasicStringP {
                     '"' => {
                         // End of syntax.
                         // 構文の終わり。
-                        self.state = State::End;
-                        return PResult::End;
+                        return self.end();
                     }
                     _ => {
                         return error(&mut self.log(), &look_ahead_items, "basic_string_p.rs.136.");
@@ -229,32 +223,41 @@ impl BasicStringP {
                     PResult::Ongoing => {}
                 }
             }
+            State::MultiLineFirstNewline => {
+                // `\r` is followed by `\n`.
+                // `\r` の後ろには `\n` が続きます。
+                if *chr0 == '\n' {
+                    self.state = State::MultiLine;
+                }
+            }
+            State::MultiLineLineEndingBackslash => match chr0 {
+                '\t' | ' ' | '\r' => {} // Ignore it.
+                '\n' => {
+                    self.state = State::MultiLineTrimStart;
+                }
+                _ => {
+                    return error(
+                        self.log().str(
+                            "message",
+                            &format!(
+                                "Only whitespace can follow a line ending backslash, but found `{}`.",
+                                chr0.escape_debug()
+                            ),
+                        ),
+                        look_ahead_items,
+                        "basic_string_p.rs.291.",
+                    );
+                }
+            },
             State::MultiLineTrimStart => {
                 // println!("[trace307 MultiLineTrimStart]");
                 match chr0 {
-                    '\r' | '\n' => {
-                        // println!("[trace312 Newline]");
-                    } // Ignore it.
-                    '\t' | ' ' => {
-                        // println!("[trace308 WS]");
-                    } // Ignore it.
-                    // "
-                    '"' => {
-                        if check_triple_double_quotation(look_ahead_items) {
-                            // println!("[trace309 check_triple_double_quotation]");
-                            self.state = State::MultiLineEnd1;
-                        } else {
-                            // println!("[trace310 DoubleQuotation]");
-                            let m = self.buffer.as_mut().unwrap();
-                            m.push_token(&Token::from_character(*chr0, TokenType::BasicString));
-                            self.state = State::MultiLine; // (2020-10-18追加)
-                        }
-                    }
+                    // Whitespace and newlines are trimmed, even across blank lines.
+                    // 空白と改行は、空行をまたいでも除去します。
+                    '\t' | ' ' | '\r' | '\n' => {} // Ignore it.
                     _ => {
-                        // println!("[trace311 Otherwise={:?}]", chr0);
-                        let m = self.buffer.as_mut().unwrap();
-                        m.push_token(&Token::from_character(*chr0, TokenType::BasicString));
                         self.state = State::MultiLine;
+                        return self.parse_multi_line(look_ahead_items);
                     }
                 }
             }
@@ -264,8 +267,7 @@ impl BasicStringP {
                     '"' => {
                         // End of syntax.
                         // 構文の終わり。
-                        self.state = State::End;
-                        return PResult::End;
+                        return self.end();
                     }
                     // \
                     '\\' => {
@@ -278,7 +280,10 @@ impl BasicStringP {
                         {
                             PResult::End => {
                                 return error(
-                                    &mut self.log(),
+                                    self.log().str(
+                                        "message",
+                                        "A line ending backslash is only allowed in multi-line basic strings.",
+                                    ),
                                     &look_ahead_items,
                                     "basic_string_p.rs.252.",
                                 );
@@ -326,6 +331,103 @@ impl BasicStringP {
         PResult::Ongoing
     }
 
+    /// Multi-line basic string.  
+    /// 複数行の基本文字列。  
+    ///
+    /// # Arguments
+    ///
+    /// * `look_ahead_items` - Tokens contains look ahead.  
+    ///   先読みを含むトークン。  
+    fn parse_multi_line(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
+        let chr0 = look_ahead_items.get(0).unwrap();
+        if *chr0 != '"' {
+            self.quotes = 0;
+        }
+        match chr0 {
+            // "
+            '"' => {
+                // print!("trace.10.");
+                if check_triple_double_quotation(look_ahead_items) {
+                    self.state = State::MultiLineEnd1;
+                } else {
+                    // Up to two `"` before the closing `"""`.
+                    // 終わりの `"""` の前は２つの `"` まで。
+                    self.quotes += 1;
+                    if 2 < self.quotes {
+                        return error(
+                            self.log().str(
+                                "message",
+                                "More than five `\"` in a row in a multi-line basic string.",
+                            ),
+                            look_ahead_items,
+                            "basic_string_p.rs.352.",
+                        );
+                    }
+                    let m = self.buffer.as_mut().unwrap();
+                    m.push_token(&Token::from_character(*chr0, TokenType::BasicString));
+                }
+            }
+            // \
+            '\\' => {
+                // Look-ahead.
+                // 先読み。
+                if let Some('\t') | Some(' ') | Some('\r') | Some('\n') = look_ahead_items.get(1) {
+                    // Line ending backslash.
+                    // 行末のバックスラッシュ。
+                    self.state = State::MultiLineLineEndingBackslash;
+                    return PResult::Ongoing;
+                }
+                self.escape_sequence_p = Some(EscapeSequenceP::default());
+                match self
+                    .escape_sequence_p
+                    .as_mut()
+                    .unwrap()
+                    .parse(look_ahead_items)
+                {
+                    PResult::End => {
+                        // 行末の \ だったなら。
+                        // println!("[trace200 行末の \\ だったなら。]");
+                        self.state = State::MultiLineTrimStart;
+                    }
+                    PResult::Err(mut table) => {
+                        return error_via(
+                            &mut table,
+                            &mut self.log(),
+                            &look_ahead_items,
+                            "basic_string_p.rs.139.",
+                        );
+                    }
+                    PResult::Ongoing => {
+                        self.state = State::MultiLineEscapeSequence;
+                    }
+                }
+            }
+            _ => {
+                // print!("trace.12.");
+                let m = self.buffer.as_mut().unwrap();
+                m.push_token(&Token::from_character(*chr0, TokenType::BasicString));
+            }
+        }
+        PResult::Ongoing
+    }
+
+    /// Drop the quotation marks from the raw text and finish.  
+    /// 生テキストから引用符を除いて終了します。  
+    fn end(&mut self) -> PResult {
+        if let Some(m) = self.buffer.as_mut() {
+            // The opening quotation mark was consumed by the caller, so only the rest is here.
+            // 開始の引用符は呼び出し元が読んでいるので、残りだけがここにあります。
+            let (head, tail) = match m.style {
+                StringStyle::SingleLine => (0, 1),
+                StringStyle::MultiLine => (2, 3),
+            };
+            let chars: Vec<char> = m.raw.chars().collect();
+            m.raw = chars[head..chars.len() - tail].iter().collect();
+        }
+        self.state = State::End;
+        PResult::End
+    }
+
     /// Log.  
     /// ログ。  
     pub fn log(&self) -> Table {
@@ -343,27 +445,16 @@ impl BasicStringP {
 ///             先読みを含むトークン。  
 /// # Returns
 ///
-/// It's triple double quotation.  
-/// ３連一重引用符。  
+/// It's the closing triple double quotation. Up to two `"` before it belong to the string.  
+/// 終わりの３連二重引用符。その前の２つまでの `"` は文字列に含めます。  
 fn check_triple_double_quotation(look_ahead_items: &LookAheadItems<char>) -> bool {
-    if let Some(chr2_ahead) = &look_ahead_items.get(2) {
-        match chr2_ahead {
-            '"' => {
-                if let Some(chr1_ahead) = &look_ahead_items.get(1) {
-                    match chr1_ahead {
-                        '"' => {
-                            // Triple double quote.
-                            true
-                        }
-                        _ => false,
-                    }
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        }
-    } else {
-        false
+    match (
+        look_ahead_items.get(1),
+        look_ahead_items.get(2),
+        look_ahead_items.get(3),
+    ) {
+        (Some('"'), Some('"'), Some('"')) => false,
+        (Some('"'), Some('"'), _) => true,
+        _ => false,
     }
 }
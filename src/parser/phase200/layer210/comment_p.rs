@@ -6,6 +6,7 @@ use crate::model::{layer110::TokenType, layer210::Comment};
 use crate::parser::phase200::layer210::{non_eol_p::Judge as NonEolPJudge, NonEolP};
 use crate::parser::phase200::layer210::{CommentP, PResult};
 use crate::parser::phase200::Token;
+use crate::parser::phase200::{control_character_message, error};
 use casual_logger::Table;
 use look_ahead_items::LookAheadItems;
 
@@ -103,11 +104,31 @@ impl CommentP {
             }
             State::First | State::NonEol => {
                 // 次の１文字。
-                let chr1 = look_ahead_items.get(1).unwrap();
+                let chr1 = if let Some(chr1) = look_ahead_items.get(1) {
+                    chr1
+                } else {
+                    // End of file.
+                    // ファイルの終わり。
+                    self.state = State::End;
+                    return PResult::End;
+                };
                 if let None = NonEolP::judge(*chr1) {
+                    match (chr1, look_ahead_items.get(2)) {
+                        ('\n', _) | ('\r', Some('\n')) => {}
+                        _ => {
+                            if let Some(message) = control_character_message(*chr1, "a comment") {
+                                return error(
+                                    self.log().str("message", &message),
+                                    look_ahead_items,
+                                    "comment_p.rs.148.",
+                                );
+                            }
+                        }
+                    }
                     self.state = State::End;
                     return PResult::End;
                 }
+                self.state = State::NonEol;
             }
         }
 
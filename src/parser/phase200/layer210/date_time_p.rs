@@ -1,7 +1,7 @@
 //! Comment syntax parser.  
 //! コメント構文パーサー。  
 
-use crate::model::layer110::TokenType;
+use crate::model::layer110::{token::tokens_stringify, TokenType};
 use crate::parser::phase200::Token;
 use crate::parser::phase200::{
     error,
@@ -44,13 +44,29 @@ impl DateTimeP {
     /// * `PResult` - Result.  
     ///                             結果。
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
+        match self.parse_char(look_ahead_items) {
+            PResult::End => {
+                let text = tokens_stringify(&self.buffer);
+                match check(&text) {
+                    Ok(()) => PResult::End,
+                    Err(why) => error(
+                        self.log().str("message", &why),
+                        look_ahead_items,
+                        "date_time_p.rs.60.",
+                    ),
+                }
+            }
+            m => m,
+        }
+    }
+    fn parse_char(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         match self.state {
             State::End => {
                 return error(&mut self.log(), &look_ahead_items, "date_time_p.rs.50.");
             }
             State::FirstOfDate => {
                 let chr0 = look_ahead_items.get(0).unwrap();
-                let chr1 = look_ahead_items.get(1).unwrap();
+                let chr1 = look_ahead_items.get(1).unwrap_or(&'\n');
                 match chr0 {
                     '\r' | '\n' => {
                         // println!("[trace59.]");
@@ -101,6 +117,11 @@ impl DateTimeP {
                             '-' | '0'..='9' => {
                                 // println!("[trace86={}]", chr0.to_string().as_str());
                             }
+                            // `1979-05-27 07:32:00`. A space instead of `T`.
+                            // `T` の代わりの空白。
+                            ' ' if is_time_ahead(look_ahead_items) => {
+                                self.state = State::FirstOfTime;
+                            }
                             _ => {
                                 // println!("[trace89={}]", chr0.to_string().as_str());
                                 return PResult::End;
@@ -116,11 +137,19 @@ impl DateTimeP {
             }
             State::FirstOfTime => {
                 let chr0 = look_ahead_items.get(0).unwrap();
-                let chr1 = look_ahead_items.get(1).unwrap();
+                let chr1 = look_ahead_items.get(1).unwrap_or(&'\n');
                 match chr0 {
+                    // The newline is not a part of the value, so it is not taken.
+                    // 改行は値の一部ではないので、取りません。
                     '\r' | '\n' => {
-                        // println!("[trace114.]");
-                        return PResult::End;
+                        return error(
+                            self.log().str(
+                                "message",
+                                &format!("`{}` has no time.", tokens_stringify(&self.buffer)),
+                            ),
+                            look_ahead_items,
+                            "date_time_p.rs.130.",
+                        );
                     }
                     ':' | '0'..='9' => {
                         self.buffer
@@ -209,7 +238,7 @@ impl DateTimeP {
             }
             State::OffsetSign => {
                 let chr0 = look_ahead_items.get(0).unwrap();
-                let chr1 = look_ahead_items.get(1).unwrap();
+                let chr1 = look_ahead_items.get(1).unwrap_or(&'\n');
                 match chr0 {
                     ':' | '-' | '0'..='9' | '+' => {
                         self.buffer
@@ -251,7 +280,7 @@ impl DateTimeP {
             }
             State::FractionalSeconds => {
                 let chr0 = look_ahead_items.get(0).unwrap();
-                let chr1 = look_ahead_items.get(1).unwrap();
+                let chr1 = look_ahead_items.get(1).unwrap_or(&'\n');
                 match chr0 {
                     '.' | '0'..='9' => {
                         self.buffer
@@ -318,3 +347,102 @@ impl DateTimeP {
         t
     }
 }
+/// `nn` after the space. A value can't be followed by digits, so it is a time.  
+/// 空白の後の `nn` 。値の後に数字は続かないので、時刻です。  
+fn is_time_ahead(look_ahead_items: &LookAheadItems<char>) -> bool {
+    matches!(
+        (look_ahead_items.get(2), look_ahead_items.get(3)),
+        (Some('0'..='9'), Some('0'..='9'))
+    )
+}
+
+/// Checks the fields of `1979-05-27T07:32:00.999999-07:00`, `1979-05-27` or `07:32:00`.  
+/// `1979-05-27T07:32:00.999999-07:00` 、 `1979-05-27` 、 `07:32:00` の各欄を確かめます。  
+fn check(text: &str) -> Result<(), String> {
+    let not_date_time = || format!("`{}` is not a date-time.", text);
+    let out_of_range = |name: &str| format!("`{}` has {} out of range.", text, name);
+    let (date, time) = if text.len() < 5 || text.as_bytes()[4] != b'-' {
+        (None, Some(text))
+    } else if text.len() == 10 {
+        (Some(text), None)
+    } else {
+        match text.get(10..11) {
+            Some("T") | Some("t") | Some(" ") => (Some(&text[..10]), Some(&text[11..])),
+            _ => return Err(not_date_time()),
+        }
+    };
+    if let Some(date) = date {
+        let fields = digit_fields(date, '-', &[4, 2, 2]).ok_or_else(not_date_time)?;
+        let (year, month, day) = (fields[0], fields[1], fields[2]);
+        if !(1..=12).contains(&month) {
+            return Err(out_of_range("the month"));
+        }
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        if !(1..=days).contains(&day) {
+            return Err(out_of_range("the day"));
+        }
+    }
+    if let Some(time) = time {
+        let fields = time
+            .get(..8)
+            .and_then(|m| digit_fields(m, ':', &[2, 2, 2]))
+            .ok_or_else(not_date_time)?;
+        if 23 < fields[0] {
+            return Err(out_of_range("the hour"));
+        }
+        if 59 < fields[1] {
+            return Err(out_of_range("the minute"));
+        }
+        // 60 is a leap second.
+        // 60 はうるう秒です。
+        if 60 < fields[2] {
+            return Err(out_of_range("the second"));
+        }
+        let mut rest = &time[8..];
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let len = fraction
+                .find(|ch: char| !ch.is_ascii_digit())
+                .unwrap_or(fraction.len());
+            if len == 0 {
+                return Err(not_date_time());
+            }
+            rest = &fraction[len..];
+        }
+        match rest {
+            "" => {}
+            // An offset needs a date.
+            // オフセットには日付が要ります。
+            "Z" | "z" if date.is_some() => {}
+            _ if date.is_some() && (rest.starts_with('+') || rest.starts_with('-')) => {
+                let fields = digit_fields(&rest[1..], ':', &[2, 2]).ok_or_else(not_date_time)?;
+                if 23 < fields[0] || 59 < fields[1] {
+                    return Err(out_of_range("the offset"));
+                }
+            }
+            _ => return Err(not_date_time()),
+        }
+    }
+    Ok(())
+}
+/// `1979-05-27` split by `-` into fields of 4, 2 and 2 digits.  
+/// `1979-05-27` を `-` で 4、2、2 桁の欄に分けます。  
+fn digit_fields(text: &str, separator: char, widths: &[usize]) -> Option<Vec<u32>> {
+    let fields: Vec<&str> = text.split(separator).collect();
+    if fields.len() != widths.len() {
+        return None;
+    }
+    let mut vec = Vec::new();
+    for (field, width) in fields.iter().zip(widths) {
+        if field.len() != *width || !field.chars().all(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+        vec.push(field.parse().ok()?);
+    }
+    Some(vec)
+}
@@ -1,5 +1,11 @@
 //! Escape sequence parser.  
 //! エスケープ・シーケンス・パーサー。  
+//!
+//! # Examples
+//!
+//! ```
+//! // \b \t \n \f \r \" \\ \uXXXX \UXXXXXXXX
+//! ```
 
 use crate::model::layer110::token::tokens_stringify;
 use crate::model::layer110::{Token, TokenType};
@@ -55,7 +61,7 @@ impl EscapeSequenceP {
             State::End => {
                 return error(
                     &mut self.log(),
-                    &look_ahead_items,
+                    look_ahead_items,
                     "escape_sequence_p.rs.66.",
                 );
             }
@@ -64,7 +70,7 @@ impl EscapeSequenceP {
                 // 先読み。
                 if let Some(chr1_ahead) = look_ahead_items.get(1).as_ref() {
                     match chr1_ahead {
-                        'A'..='Z' | 'a'..='z' | '\\' | '"' => {
+                        'b' | 't' | 'n' | 'f' | 'r' | '"' | '\\' | 'u' | 'U' => {
                             // print!("[trace1 (IgnoreBackslash) ahead={:?}]", chr1_ahead);
                             self.state = State::EscapedCharacter;
                         }
@@ -76,16 +82,23 @@ impl EscapeSequenceP {
                         }
                         _ => {
                             return error(
-                                &mut self.log(),
-                                &look_ahead_items,
+                                self.log().str(
+                                    "message",
+                                    &format!(
+                                        "Invalid escape sequence `\\{}`. Only \\b, \\t, \\n, \\f, \\r, \\\", \\\\, \\uXXXX and \\UXXXXXXXX are allowed.",
+                                        chr1_ahead.escape_debug()
+                                    ),
+                                ),
+                                look_ahead_items,
                                 "escape_sequence_p.rs.136.",
                             );
                         }
                     }
                 } else {
                     return error(
-                        &mut self.log(),
-                        &look_ahead_items,
+                        self.log()
+                            .str("message", "The escape sequence is not terminated."),
+                        look_ahead_items,
                         "escape_sequence_p.rs.112.",
                     );
                 }
@@ -93,76 +106,39 @@ impl EscapeSequenceP {
             State::EscapedCharacter => {
                 // println!("[trace196={:?}]", chr0);
                 // Escaped.
-                match chr0 {
-                    // `"`
-                    'A'..='Z' | 'a'..='z' => {
-                        // TODO 汎用的に書けないか？
-                        // https://doc.rust-lang.org/reference/look_ahead_items.html
-                        let mut code = None;
-                        match chr0.to_string().as_str() {
-                            "n" => code = Some("\n"),
-                            "r" => code = Some("\r"),
-                            "t" => code = Some("\t"),
-                            "u" => {
-                                self.state = State::UnicodeDigits;
-                                self.string_buffer = String::new();
-                                self.positional_numeral_string_p = Some(
-                                    PositionalNumeralStringP::new("0x")
-                                        .set_expected_digits(4)
-                                        .clone(),
-                                );
-                            }
-                            "U" => {
-                                self.state = State::UnicodeDigits;
-                                self.string_buffer = String::new();
-                                self.positional_numeral_string_p = Some(
-                                    PositionalNumeralStringP::new("0x")
-                                        .set_expected_digits(8)
-                                        .clone(),
-                                );
-                            }
-                            _ => {
-                                return error(
-                                    &mut self.log(),
-                                    &look_ahead_items,
-                                    "escape_sequence_p.rs.206.",
-                                )
-                            }
-                        }
-                        if let Some(code) = code {
-                            self.buffer.push(Token::new(
-                                code,
-                                TokenType::EscapeSequence, // TODO EscapeSequence
-                            ));
-                            self.state = State::End;
-                            return PResult::End;
-                        }
-                    }
-                    '\\' => {
-                        self.buffer.push(Token::new(
-                            "\\",
-                            TokenType::EscapeSequence, // TODO EscapeSequence
-                        ));
-                        self.state = State::End;
-                        return PResult::End;
-                    }
-                    // "
-                    '"' => {
-                        self.buffer.push(Token::new(
-                            "\"",
-                            TokenType::EscapeSequence, // TODO EscapeSequence
-                        ));
-                        self.state = State::End;
-                        return PResult::End;
+                let code = match chr0 {
+                    'b' => '\u{0008}',
+                    't' => '\t',
+                    'n' => '\n',
+                    'f' => '\u{000C}',
+                    'r' => '\r',
+                    '"' => '"',
+                    '\\' => '\\',
+                    'u' | 'U' => {
+                        self.state = State::UnicodeDigits;
+                        self.string_buffer = String::new();
+                        self.positional_numeral_string_p = Some(
+                            PositionalNumeralStringP::new("0x")
+                                .set_expected_digits(if *chr0 == 'u' { 4 } else { 8 })
+                                .clone(),
+                        );
+                        return PResult::Ongoing;
                     }
                     _ => {
                         return error(
-                            &mut self.log(),
-                            &look_ahead_items,
+                            self.log().str(
+                                "message",
+                                &format!("Invalid escape sequence `\\{}`.", chr0.escape_debug()),
+                            ),
+                            look_ahead_items,
                             "escape_sequence_p.rs.212.",
                         );
                     }
-                }
+                };
+                self.buffer
+                    .push(Token::from_character(code, TokenType::EscapeSequence));
+                self.state = State::End;
+                return PResult::End;
             }
             State::UnicodeDigits => {
                 let p = self.positional_numeral_string_p.as_mut().unwrap();
@@ -172,14 +148,37 @@ impl EscapeSequenceP {
                         // 満ちたなら。
                         let string_buffer = tokens_stringify(&p.flush());
                         // println!("[trace157={}]", string_buffer);
-                        let hex = match u32::from_str_radix(&string_buffer, 16) {
+                        self.string_buffer = string_buffer;
+                        let code = match u32::from_str_radix(&self.string_buffer, 16) {
                             Ok(n) => n,
-                            Err(why) => panic!("{}", why),
+                            Err(why) => {
+                                return error(
+                                    self.log().str("message", &format!("{}", why)),
+                                    look_ahead_items,
+                                    "escape_sequence_p.rs.157.",
+                                );
+                            }
+                        };
+                        // Surrogate code points and values above U+10FFFF are not Unicode scalar values.
+                        // サロゲート、 U+10FFFF 超は Unicode スカラー値ではありません。
+                        let chr = match from_u32(code) {
+                            Some(chr) => chr,
+                            None => {
+                                return error(
+                                    self.log().str(
+                                        "message",
+                                        &format!(
+                                            "U+{:04X} is not a Unicode scalar value. Surrogates (U+D800 - U+DFFF) and values above U+10FFFF are not allowed.",
+                                            code
+                                        ),
+                                    ),
+                                    look_ahead_items,
+                                    "escape_sequence_p.rs.160.",
+                                );
+                            }
                         };
-                        self.buffer.push(Token::new(
-                            &from_u32(hex).unwrap().to_string(),
-                            TokenType::EscapeSequence, // TODO EscapeSequence
-                        ));
+                        self.buffer
+                            .push(Token::from_character(chr, TokenType::EscapeSequence));
                         self.state = State::End;
                         self.positional_numeral_string_p = None;
                         return PResult::End;
@@ -188,7 +187,7 @@ impl EscapeSequenceP {
                         return error_via(
                             &mut table,
                             &mut self.log(),
-                            &look_ahead_items,
+                            look_ahead_items,
                             "escape_sequence_p.rs.165.",
                         );
                     }
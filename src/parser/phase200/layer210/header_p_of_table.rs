@@ -5,9 +5,26 @@ use crate::model::{
     layer110::{Token, TokenType},
     layer230::HeaderOfTable,
 };
-use crate::parser::phase200::layer210::{HeaderPOfTable, PResult};
+use crate::parser::phase200::error;
+use crate::parser::phase200::error_via;
+use crate::parser::phase200::layer210::{HeaderPOfTable, KeyP, PResult};
+use casual_logger::Table;
 use look_ahead_items::LookAheadItems;
-// use casual_logger::Table;
+
+/// Syntax machine state.  
+/// 構文状態遷移。  
+///
+/// Example: `[ fruit.apple ]`.  
+#[derive(Debug, Clone)]
+pub enum State {
+    /// After the table name. Whitespace or `]`.
+    AfterKey,
+    /// After `[`.
+    BeforeKey,
+    End,
+    /// `fruit.apple`.
+    Key,
+}
 
 impl HeaderPOfTable {
     pub fn flush(&mut self) -> Option<HeaderOfTable> {
@@ -18,6 +35,8 @@ impl HeaderPOfTable {
     pub fn new() -> Self {
         HeaderPOfTable {
             buffer: Some(HeaderOfTable::default()),
+            key_p: None,
+            state: State::BeforeKey,
         }
     }
     /// # Arguments
@@ -30,30 +49,90 @@ impl HeaderPOfTable {
     ///                             結果。
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         let chr0 = look_ahead_items.get(0).unwrap();
-        match chr0 {
-            // `"`
-            '"' => {
-                // End of syntax.
-                // 構文の終わり。
-                return PResult::End;
+        match self.state {
+            State::AfterKey => match chr0 {
+                '\t' | ' ' => {
+                    let m = self.buffer.as_mut().unwrap();
+                    m.push_token(&Token::from_character(*chr0, TokenType::Table));
+                }
+                // `]`
+                ']' => {
+                    // End of syntax.
+                    // 構文の終わり。
+                    self.state = State::End;
+                    return PResult::End;
+                }
+                _ => {
+                    return error(
+                        &mut self.log(),
+                        look_ahead_items,
+                        "header_p_of_table.rs.66.",
+                    )
+                }
+            },
+            State::BeforeKey => match chr0 {
+                '\t' | ' ' => {
+                    let m = self.buffer.as_mut().unwrap();
+                    m.push_token(&Token::from_character(*chr0, TokenType::Table));
+                }
+                _ => {
+                    self.key_p = Some(KeyP::default());
+                    self.state = State::Key;
+                    return self.parse(look_ahead_items);
+                }
+            },
+            State::End => {
+                return error(
+                    &mut self.log(),
+                    look_ahead_items,
+                    "header_p_of_table.rs.80.",
+                );
             }
-            _ => {
+            State::Key => {
                 let m = self.buffer.as_mut().unwrap();
                 m.push_token(&Token::from_character(*chr0, TokenType::Table));
+                let p = self.key_p.as_mut().unwrap();
+                match p.parse(look_ahead_items) {
+                    PResult::End => {
+                        if let Some(child_m) = p.flush() {
+                            self.buffer.as_mut().unwrap().key = child_m;
+                            self.key_p = None;
+                            self.state = State::AfterKey;
+                        } else {
+                            return error(
+                                &mut self.log(),
+                                look_ahead_items,
+                                "header_p_of_table.rs.96.",
+                            );
+                        }
+                    }
+                    PResult::Err(mut table) => {
+                        return error_via(
+                            &mut table,
+                            &mut self.log(),
+                            look_ahead_items,
+                            "header_p_of_table.rs.105.",
+                        );
+                    }
+                    PResult::Ongoing => {}
+                }
             }
         }
         PResult::Ongoing
     }
 
-    /* TODO
-    /// Log.
-    /// ログ。
+    /// Log.  
+    /// ログ。  
     pub fn log(&self) -> Table {
-        let mut t = Table::default().clone();
+        let mut t = Table::default()
+            .str("state", &format!("{:?}", self.state))
+            .clone();
         if let Some(m) = &self.buffer {
             t.str("value", &m.to_string());
         }
+        if let Some(p) = &self.key_p {
+            t.sub_t("key_p", &p.log());
+        }
         t
     }
-    */
 }
@@ -1,19 +1,49 @@
 //! Key parser.  
 //! キー・パーサー。  
+//!
+//! # Examples
+//!
+//! ```
+//! // abc
+//! // site."google.com"
+//! // fruit . color
+//! ```
 
 use crate::model::{
     layer110::{Token, TokenType},
-    layer210::Key,
+    layer210::{Key, StringStyle},
 };
 use crate::parser::phase200::error;
-use crate::parser::phase200::layer210::{KeyP, PResult};
+use crate::parser::phase200::error_via;
+use crate::parser::phase200::layer210::{BasicStringP, KeyP, LiteralStringP, PResult};
 use casual_logger::Table as LogTable;
 use look_ahead_items::LookAheadItems;
 
+/// Syntax machine state.  
+/// 構文状態遷移。  
+#[derive(Debug, Clone)]
+pub enum State {
+    /// After a segment. Whitespace or `.`.
+    AfterSegment,
+    /// `abc`.
+    BareKey,
+    /// `"abc"`.
+    BasicString,
+    /// First, or after `.`.
+    BeforeSegment,
+    End,
+    /// `'abc'`.
+    LiteralString,
+}
+
 impl Default for KeyP {
     fn default() -> Self {
         KeyP {
             buffer: Some(Key::default()),
+            basic_string_p: None,
+            literal_string_p: None,
+            segment: String::new(),
+            state: State::BeforeSegment,
         }
     }
 }
@@ -36,33 +66,174 @@ impl KeyP {
     ///                             結果。
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         let chr0 = look_ahead_items.get(0).unwrap();
-        match chr0 {
-            'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' => {
-                let m = self.buffer.as_mut().unwrap();
-                m.push_token(&Token::from_character(*chr0, TokenType::Key));
-
-                // Look-ahead.
-                // 先読み。
-                if let Some(chr1_ahead) = look_ahead_items.get(1).as_ref() {
-                    match chr1_ahead {
-                        'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' => PResult::Ongoing,
-                        _ => PResult::End,
+        match self.state {
+            // `abc .`
+            State::AfterSegment => match chr0 {
+                '\t' | ' ' => {
+                    let m = self.buffer.as_mut().unwrap();
+                    m.push_token(&Token::from_character(*chr0, TokenType::Ws));
+                }
+                '.' => {
+                    let m = self.buffer.as_mut().unwrap();
+                    m.push_token(&Token::from_character(*chr0, TokenType::Key));
+                    self.state = State::BeforeSegment;
+                }
+                _ => return error(&mut self.log(), look_ahead_items, "key.rs.80."),
+            },
+            State::BareKey => {
+                return self.parse_bare_key(look_ahead_items);
+            }
+            // `"abc"`
+            State::BasicString => {
+                let p = self.basic_string_p.as_mut().unwrap();
+                match p.parse(look_ahead_items) {
+                    PResult::End => {
+                        if let Some(child_m) = p.flush() {
+                            self.basic_string_p = None;
+                            if child_m.style == StringStyle::MultiLine {
+                                return error(
+                                    self.log()
+                                        .str("message", "A key can't be a multi-line string."),
+                                    look_ahead_items,
+                                    "key.rs.97.",
+                                );
+                            }
+                            let m = self.buffer.as_mut().unwrap();
+                            m.push_token(&Token::new(&child_m.to_string(), TokenType::Key));
+                            m.push_segment(&child_m.value());
+                            return self.end_of_segment(look_ahead_items);
+                        } else {
+                            return error(&mut self.log(), look_ahead_items, "key.rs.105.");
+                        }
+                    }
+                    PResult::Err(mut table) => {
+                        return error_via(
+                            &mut table,
+                            &mut self.log(),
+                            look_ahead_items,
+                            "key.rs.113.",
+                        );
+                    }
+                    PResult::Ongoing => {}
+                }
+            }
+            // First, or `abc.`
+            State::BeforeSegment => match chr0 {
+                // `abc. def`
+                '\t' | ' ' => {
+                    let m = self.buffer.as_mut().unwrap();
+                    m.push_token(&Token::from_character(*chr0, TokenType::Ws));
+                }
+                'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' => {
+                    self.state = State::BareKey;
+                    return self.parse_bare_key(look_ahead_items);
+                }
+                '"' => {
+                    self.basic_string_p = Some(BasicStringP::new());
+                    self.state = State::BasicString;
+                }
+                '\'' => {
+                    self.literal_string_p = Some(LiteralStringP::new());
+                    self.state = State::LiteralString;
+                }
+                _ => {
+                    return error(
+                        self.log()
+                            .str("message", "A key name is expected here. It can't be empty."),
+                        look_ahead_items,
+                        "key.rs.38.",
+                    )
+                }
+            },
+            State::End => return error(&mut self.log(), look_ahead_items, "key.rs.146."),
+            // `'abc'`
+            State::LiteralString => {
+                let p = self.literal_string_p.as_mut().unwrap();
+                match p.parse(look_ahead_items) {
+                    PResult::End => {
+                        if let Some(child_m) = p.flush() {
+                            self.literal_string_p = None;
+                            if child_m.style == StringStyle::MultiLine {
+                                return error(
+                                    self.log()
+                                        .str("message", "A key can't be a multi-line string."),
+                                    look_ahead_items,
+                                    "key.rs.159.",
+                                );
+                            }
+                            let m = self.buffer.as_mut().unwrap();
+                            m.push_token(&Token::new(&child_m.to_string(), TokenType::Key));
+                            m.push_segment(&child_m.value());
+                            return self.end_of_segment(look_ahead_items);
+                        } else {
+                            return error(&mut self.log(), look_ahead_items, "key.rs.167.");
+                        }
                     }
-                } else {
-                    PResult::End
+                    PResult::Err(mut table) => {
+                        return error_via(
+                            &mut table,
+                            &mut self.log(),
+                            look_ahead_items,
+                            "key.rs.175.",
+                        );
+                    }
+                    PResult::Ongoing => {}
                 }
             }
-            _ => return error(&mut self.log(), &look_ahead_items, "key.rs.38."),
         }
+        PResult::Ongoing
+    }
+    /// `abc`.
+    fn parse_bare_key(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
+        let chr0 = look_ahead_items.get(0).unwrap();
+        let m = self.buffer.as_mut().unwrap();
+        m.push_token(&Token::from_character(*chr0, TokenType::Key));
+        self.segment.push(*chr0);
+
+        // Look-ahead.
+        // 先読み。
+        if let Some('A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_') = look_ahead_items.get(1) {
+            return PResult::Ongoing;
+        }
+        m.push_segment(&self.segment);
+        self.segment.clear();
+        self.end_of_segment(look_ahead_items)
+    }
+    /// The key goes on if `.` comes after the whitespace.  
+    /// 空白の後に `.` が来るなら、キーは続きます。  
+    fn end_of_segment(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
+        // Look-ahead.
+        // 先読み。
+        let mut i = 1;
+        while let Some(chr) = look_ahead_items.get(i) {
+            match chr {
+                '\t' | ' ' => i += 1,
+                '.' => {
+                    self.state = State::AfterSegment;
+                    return PResult::Ongoing;
+                }
+                _ => break,
+            }
+        }
+        self.state = State::End;
+        PResult::End
     }
 
     /// Log.  
     /// ログ。  
     pub fn log(&self) -> LogTable {
-        let mut t = LogTable::default().clone();
+        let mut t = LogTable::default()
+            .str("state", &format!("{:?}", self.state))
+            .clone();
         if let Some(key) = &self.buffer {
             t.str("buffer", &key.to_string());
         }
+        if let Some(p) = &self.basic_string_p {
+            t.sub_t("basic_string_p", &p.log());
+        }
+        if let Some(p) = &self.literal_string_p {
+            t.sub_t("literal_string_p", &p.log());
+        }
         t
     }
 }
@@ -1,9 +1,16 @@
-//! Non end-of-line parser.  
-//! 非行末パーサー。  
+//! Key value separator parser.  
+//! キー値区切り文字パーサー。  
+//!
+//! # Examples
+//!
+//! ```
+//! // key = val
+//! //    ^^^
+//! ```
 
 use crate::model::{layer110::TokenType, layer210::Ws};
 use crate::parser::phase200::error;
-use crate::parser::phase200::layer210::WsP;
+use crate::parser::phase200::layer210::WscharP;
 use crate::parser::phase200::layer210::{KeyvalSepP, PResult};
 use crate::parser::phase200::Token;
 use casual_logger::Table;
@@ -15,8 +22,9 @@ use look_ahead_items::LookAheadItems;
 pub enum State {
     End,
     First,
+    /// Before `=`.
     Ws1,
-    Equals,
+    /// After `=`.
     Ws2,
 }
 
@@ -35,12 +43,6 @@ impl Default for KeyvalSepP {
     }
 }
 impl KeyvalSepP {
-    pub fn is_end(&mut self) -> bool {
-        match self.state {
-            State::End => true,
-            _ => false,
-        }
-    }
     /// # Arguments
     ///
     /// * `token` - Token.  
@@ -50,7 +52,7 @@ impl KeyvalSepP {
     /// * `bool` - このパーサーの対象とするトークンになる.  
     ///                             結果。
     pub fn judge(chr: char) -> Option<Judge> {
-        if let Some(_judge) = WsP::judge(chr) {
+        if let Some(_judge) = WscharP::judge(chr) {
             return Some(Judge::Ws);
         }
         let unicode = chr as u32;
@@ -59,6 +61,14 @@ impl KeyvalSepP {
             _ => None,
         }
     }
+    /// Whitespace before and after `=`.  
+    /// `=` の前と後の空白。  
+    pub fn flush(&mut self) -> (Ws, Ws) {
+        let m = (self.ws1.clone(), self.ws2.clone());
+        self.ws1 = Ws::default();
+        self.ws2 = Ws::default();
+        m
+    }
     /// # Arguments
     ///
     /// * `look_ahead_items` - Tokens contains look ahead.  
@@ -68,97 +78,61 @@ impl KeyvalSepP {
     /// * `PResult` - Result.  
     ///                             結果。
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
+        let chr0 = look_ahead_items.get(0).unwrap();
         match self.state {
-            State::First => {
-                let chr0 = look_ahead_items.get(0).unwrap();
-                if let Some(judge) = Self::judge(*chr0) {
-                    match judge {
-                        Judge::Ws => {
-                            return self.parse_ws1(look_ahead_items);
-                        }
-                        Judge::Equals => {
-                            self.state = State::Ws2;
-                        }
-                    }
-                } else {
-                    return error(&mut self.log(), &look_ahead_items, "keyval_sep_p.rs.108.");
+            // Before `=`.
+            State::First | State::Ws1 => match Self::judge(*chr0) {
+                Some(Judge::Ws) => {
+                    self.ws1
+                        .push_token(&Token::from_character(*chr0, TokenType::Ws));
+                    self.state = State::Ws1;
                 }
-            }
-            State::Ws1 => {
-                return self.parse_ws1(look_ahead_items);
-            }
-            State::Equals => {
-                return self.parse_equals(look_ahead_items);
-            }
-            State::Ws2 => {
-                return self.parse_ws2(look_ahead_items);
-            }
-            State::End => {
-                return PResult::End;
-            }
-        }
-        PResult::Ongoing
-    }
-
-    fn parse_ws1(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
-        let chr0 = look_ahead_items.get(0).unwrap();
-        self.ws1
-            .push_token(&Token::from_character(*chr0, TokenType::Ws));
-
-        // 次のトークンを調べます。
-        let chr1 = look_ahead_items.get(1).unwrap();
-        if let Some(judge) = Self::judge(*chr1) {
-            match judge {
-                Judge::Ws => self.state = State::Ws1,
-                Judge::Equals => self.state = State::Equals,
-            }
-        } else {
-            return error(&mut self.log(), &look_ahead_items, "keyval_sep_p.rs.87.");
-        }
-        PResult::Ongoing
-    }
-
-    fn parse_equals(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
-        // 次の文字を調べます。
-        let chr1 = look_ahead_items.get(1).unwrap();
-        if let Some(judge) = Self::judge(*chr1) {
-            match judge {
-                Judge::Ws => {
-                    return self.parse_ws2(look_ahead_items);
+                Some(Judge::Equals) => {
+                    self.state = State::Ws2;
                 }
-                Judge::Equals => {
-                    return error(&mut self.log(), &look_ahead_items, "keyval_sep_p.rs.87.");
+                None => {
+                    return error(
+                        self.log().str("message", "Expected `=` after the key."),
+                        look_ahead_items,
+                        "keyval_sep_p.rs.108.",
+                    );
                 }
+            },
+            // After `=`.
+            State::Ws2 => match Self::judge(*chr0) {
+                Some(Judge::Ws) => {
+                    self.ws2
+                        .push_token(&Token::from_character(*chr0, TokenType::Ws));
+                }
+                Some(Judge::Equals) | None => {
+                    return error(&mut self.log(), look_ahead_items, "keyval_sep_p.rs.147.");
+                }
+            },
+            State::End => {
+                return error(&mut self.log(), look_ahead_items, "keyval_sep_p.rs.152.");
             }
-        } else {
-            return error(&mut self.log(), &look_ahead_items, "keyval_sep_p.rs.93.");
         }
-    }
 
-    fn parse_ws2(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
-        let chr0 = look_ahead_items.get(0).unwrap();
-        self.ws2
-            .push_token(&Token::from_character(*chr0, TokenType::Ws));
-
-        // 次のトークンを調べます。
-        let chr1 = look_ahead_items.get(1).unwrap();
-        if let Some(judge) = Self::judge(*chr1) {
-            match judge {
-                Judge::Ws => self.state = State::Ws2,
-                Judge::Equals => {
-                    return error(&mut self.log(), &look_ahead_items, "keyval_sep_p.rs.147.");
+        // After `=`, it ends before the value.
+        // `=` の後なら、値の前で終わります。
+        if let State::Ws2 = self.state {
+            match look_ahead_items.get(1) {
+                Some(chr1) if Self::judge(*chr1).is_some() => {}
+                _ => {
+                    self.state = State::End;
+                    return PResult::End;
                 }
             }
-            PResult::Ongoing
-        } else {
-            PResult::End
         }
+        PResult::Ongoing
     }
 
     /// Log.  
     /// ログ。  
     pub fn log(&self) -> Table {
-        let mut t = Table::default().clone();
+        let mut t = Table::default()
+            .str("state", &format!("{:?}", self.state))
+            .clone();
         t.str("ws1", &self.ws1.to_string());
         t.str("ws2", &self.ws2.to_string());
         t
@@ -3,10 +3,10 @@
 
 use crate::model::{
     layer110::{Token, TokenType},
-    layer210::LiteralString,
+    layer210::{LiteralString, StringStyle},
 };
-use crate::parser::phase200::error;
 use crate::parser::phase200::layer210::{LiteralStringP, PResult};
+use crate::parser::phase200::{control_character_message, error};
 use casual_logger::Table;
 use look_ahead_items::LookAheadItems;
 
@@ -36,6 +36,7 @@ impl LiteralStringP {
         LiteralStringP {
             buffer: Some(LiteralString::default()),
             state: State::First,
+            quotes: 0,
         }
     }
     /// # Arguments
@@ -48,6 +49,25 @@ impl LiteralStringP {
     ///               結果。
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         let chr0 = look_ahead_items.get(0).unwrap();
+        if let Some(m) = self.buffer.as_mut() {
+            m.push_raw_character(*chr0);
+
+            // Multi-line strings can contain newlines.
+            // 複数行文字列は改行を含められます。
+            let newline = match (chr0, look_ahead_items.get(1)) {
+                ('\n', _) | ('\r', Some('\n')) => m.style == StringStyle::MultiLine,
+                _ => false,
+            };
+            if !newline {
+                if let Some(message) = control_character_message(*chr0, "a literal string") {
+                    return error(
+                        self.log().str("message", &message),
+                        look_ahead_items,
+                        "literal_string_p.rs.58.",
+                    );
+                }
+            }
+        }
         match self.state {
             State::BeforeMultiLine1 => {
                 // Skip 3rd single quotation.
@@ -71,8 +91,11 @@ impl LiteralStringP {
                 }
             }
             State::BeforeMultiLine2 => {
-                // Skip first end-of-line.
-                self.state = State::MultiLine;
+                // Skip first end-of-line. `\r` is followed by `\n`.
+                // 最初の改行を飛ばします。 `\r` の後ろには `\n` が続きます。
+                if *chr0 == '\n' {
+                    self.state = State::MultiLine;
+                }
             }
             State::End => {
                 return error(
@@ -91,13 +114,13 @@ impl LiteralStringP {
                             match chr1_ahead {
                                 '\'' => {
                                     // Before triple sinble quoted string.
+                                    self.buffer.as_mut().unwrap().style = StringStyle::MultiLine;
                                     self.state = State::BeforeMultiLine1;
                                 }
                                 _ => {
                                     // End of syntax. Empty string.
                                     // 構文の終わり。 空文字列。
-                                    self.state = State::End;
-                                    return PResult::End;
+                                    return self.end();
                                 }
                             }
                         } else {
@@ -122,11 +145,25 @@ impl LiteralStringP {
                         if check_triple_single_quotation(look_ahead_items) {
                             self.state = State::MultiLineEnd1;
                         } else {
+                            // Up to two `'` before the closing `'''`.
+                            // 終わりの `'''` の前は２つの `'` まで。
+                            self.quotes += 1;
+                            if 2 < self.quotes {
+                                return error(
+                                    self.log().str(
+                                        "message",
+                                        "More than five `'` in a row in a multi-line literal string.",
+                                    ),
+                                    look_ahead_items,
+                                    "literal_string_p.rs.150.",
+                                );
+                            }
                             let m = self.buffer.as_mut().unwrap();
                             m.push_token(&Token::from_character(*chr0, TokenType::LiteralString));
                         }
                     }
                     _ => {
+                        self.quotes = 0;
                         let m = self.buffer.as_mut().unwrap();
                         m.push_token(&Token::from_character(*chr0, TokenType::LiteralString));
                     }
@@ -153,8 +190,7 @@ impl LiteralStringP {
                     '\'' => {
                         // End of syntax.
                         // 構文の終わり。
-                        self.state = State::End;
-                        return PResult::End;
+                        return self.end();
                     }
                     _ => {
                         return error(
@@ -171,8 +207,7 @@ impl LiteralStringP {
                     '\'' => {
                         // End of syntax.
                         // 構文の終わり。
-                        self.state = State::End;
-                        return PResult::End;
+                        return self.end();
                     }
                     _ => {
                         let m = self.buffer.as_mut().unwrap();
@@ -185,6 +220,23 @@ impl LiteralStringP {
         PResult::Ongoing
     }
 
+    /// Drop the quotation marks from the raw text and finish.  
+    /// 生テキストから引用符を除いて終了します。  
+    fn end(&mut self) -> PResult {
+        if let Some(m) = self.buffer.as_mut() {
+            // The opening quotation mark was consumed by the caller, so only the rest is here.
+            // 開始の引用符は呼び出し元が読んでいるので、残りだけがここにあります。
+            let (head, tail) = match m.style {
+                StringStyle::SingleLine => (0, 1),
+                StringStyle::MultiLine => (2, 3),
+            };
+            let chars: Vec<char> = m.raw.chars().collect();
+            m.raw = chars[head..chars.len() - tail].iter().collect();
+        }
+        self.state = State::End;
+        PResult::End
+    }
+
     /// Log.  
     /// ログ。  
     pub fn log(&self) -> Table {
@@ -202,27 +254,16 @@ impl LiteralStringP {
 ///             先読みを含むトークン。  
 /// # Returns
 ///
-/// It's triple single quotation.  
-/// ３連一重引用符。  
+/// It's the closing triple single quotation. Up to two `'` before it belong to the string.  
+/// 終わりの３連一重引用符。その前の２つまでの `'` は文字列に含めます。  
 fn check_triple_single_quotation(look_ahead_items: &LookAheadItems<char>) -> bool {
-    if let Some(chr2_ahead) = &look_ahead_items.get(2) {
-        match chr2_ahead {
-            '\'' => {
-                if let Some(chr1_ahead) = &look_ahead_items.get(1) {
-                    match chr1_ahead {
-                        '\'' => {
-                            // Triple single quote.
-                            true
-                        }
-                        _ => false,
-                    }
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        }
-    } else {
-        false
+    match (
+        look_ahead_items.get(1),
+        look_ahead_items.get(2),
+        look_ahead_items.get(3),
+    ) {
+        (Some('\''), Some('\''), Some('\'')) => false,
+        (Some('\''), Some('\''), _) => true,
+        _ => false,
     }
 }
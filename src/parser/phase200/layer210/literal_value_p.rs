@@ -113,12 +113,8 @@ impl LiteralValueP {
                 }
                 if is_date {
                     // `??n?-`.
-                    if let Some(chr4_ahead) = look_ahead_items.get(4).as_ref() {
-                        if let Some(ch4) = chr4_ahead.to_string().chars().nth(0) {
-                            if ch4 != '-' {
-                                is_date = false;
-                            }
-                        }
+                    if look_ahead_items.get(4) != Some(&'-') {
+                        is_date = false;
                     }
                     // `??nn-`.
                     if is_date {
@@ -21,6 +21,8 @@ use crate::model::{
 use crate::parser::phase200::layer210::{
     basic_string_p::State as BasicStringState, comment_p::State as CommentState,
     date_time_p::State as DateTimeState, escape_sequence_p::State as EscapeSequenceState,
+    header_p_of_array_of_table::State as HeaderOfArrayOfTableState,
+    header_p_of_table::State as HeaderOfTableState, key_p::State as KeyState,
     keyval_sep_p::State as KeyvalSepPState, literal_string_p::State as LiteralStringState,
     literal_value_p::State as LiteralValueState, ws_p::State as WsPState,
     wschar_p::State as WscharState,
@@ -37,6 +39,9 @@ pub struct BasicStringP {
     buffer: Option<BasicString>,
     state: BasicStringState,
     escape_sequence_p: Option<EscapeSequenceP>,
+    // `"` in a row kept in a multi-line string.
+    // 複数行文字列に入れた、連続する `"` の数。
+    quotes: usize,
 }
 
 /// Comment parser.  
@@ -78,6 +83,8 @@ pub struct EscapeSequenceP {
 #[derive(Clone)]
 pub struct HeaderPOfArrayOfTable {
     buffer: Option<HeaderOfArrayOfTable>,
+    key_p: Option<KeyP>,
+    state: HeaderOfArrayOfTableState,
 }
 
 /// Header of table syntax parser.  
@@ -87,6 +94,8 @@ pub struct HeaderPOfArrayOfTable {
 #[derive(Clone)]
 pub struct HeaderPOfTable {
     buffer: Option<HeaderOfTable>,
+    key_p: Option<KeyP>,
+    state: HeaderOfTableState,
 }
 
 /// Non ascii parser.  
@@ -127,10 +136,16 @@ pub struct KeyvalSepP {
 /// Key parser.  
 /// キー・パーサー。  
 ///
-/// Example: `abc`.  
+/// Example: `abc`, `site."google.com"`.  
 #[derive(Clone)]
 pub struct KeyP {
     buffer: Option<Key>,
+    basic_string_p: Option<BasicStringP>,
+    literal_string_p: Option<LiteralStringP>,
+    /// Characters of the bare key segment.  
+    /// 裸のキー・セグメントの文字。  
+    segment: String,
+    state: KeyState,
 }
 
 /// Result of syntax parser.  
@@ -152,6 +167,9 @@ pub enum PResult {
 pub struct LiteralStringP {
     buffer: Option<LiteralString>,
     state: LiteralStringState,
+    // `'` in a row kept in a multi-line string.
+    // 複数行文字列に入れた、連続する `'` の数。
+    quotes: usize,
 }
 
 /// Literal value syntax parser.  
@@ -27,7 +27,7 @@ impl NonEolP {
         let unicode = chr as u32;
         match unicode {
             0x09 => Some(Judge::HorizontalTab(NonEol::new(chr))),
-            0x20..=0x7F => Some(Judge::Ascii(NonEol::new(chr))),
+            0x20..=0x7E => Some(Judge::Ascii(NonEol::new(chr))),
             _ => None,
         }
     }
@@ -36,62 +36,90 @@ impl PositionalNumeralStringP {
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         let token0 = look_ahead_items.get(0).unwrap();
 
-        match token0 {
-            'A'..='Z' | 'a'..='z' | '0'..='9' | '_' => {
-                let s = token0.to_string();
-                let expected_len = 1;
-                if s.len() != expected_len {
-                    // 1文字を想定しているので、そうでなかったらエラー。
-                    panic!("s.len()={} s=|{}|", s.len(), s);
-                }
-
-                self.string_buffer.push_str(&s);
-
-                // 次がHexの文字以外か？
-                let finished = if let Some(chr1_ahead) = look_ahead_items.get(1).as_ref() {
-                    match chr1_ahead {
-                        'A'..='Z' | 'a'..='z' | '0'..='9' | '_' => {
-                            // 続行。
-                            false
-                        }
-                        _ => true,
-                    }
-                } else {
-                    true
-                };
+        if !self.is_digit(*token0) {
+            return error(
+                self.log().str(
+                    "message",
+                    &format!(
+                        "`{}` is not a digit of `{}` number.",
+                        token0.escape_debug(),
+                        self.prefix
+                    ),
+                ),
+                &look_ahead_items,
+                "hex_string_p.rs.179.",
+            );
+        }
+        self.string_buffer.push(*token0);
 
-                // Filled.
-                // 満ちたなら。
-                if finished
-                    || (self.expected_digits != 0
-                        && self.expected_digits <= self.string_buffer.len())
-                {
-                    /*
-                    println!(
-                        // "[trace56={} self.expected_digits={} self.string_buffer.len()={} look_ahead_items.one_ahead={}]",
-                        self.string_buffer,
-                        self.expected_digits,
-                        self.string_buffer.len(),
-                        if let Some(chr1_ahead) = look_ahead_items.one_ahead.as_ref() {chr1_ahead.to_string()}else{"".to_string()}
-                    );
-                    // */
-                    self.buffer.push(Token::new(
-                        &self.string_buffer,
-                        TokenType::SPPositionalNumeralString,
-                    ));
-                    return PResult::End;
+        // 次が桁の文字以外か？
+        let finished = if let Some(chr1_ahead) = look_ahead_items.get(1).as_ref() {
+            match chr1_ahead {
+                'A'..='Z' | 'a'..='z' | '0'..='9' | '_' => {
+                    // 続行。
+                    false
                 }
-
-                // １文字ずつだから、オーバーフローしないはず。
+                _ => true,
             }
-            _ => {
-                return error(&mut self.log(), &look_ahead_items, "hex_string_p.rs.179.");
+        } else {
+            true
+        };
+
+        // Filled.
+        // 満ちたなら。
+        if self.expected_digits != 0 && self.expected_digits <= self.string_buffer.len() {
+            self.buffer.push(Token::new(
+                &self.string_buffer,
+                TokenType::SPPositionalNumeralString,
+            ));
+            return PResult::End;
+        }
+
+        if finished {
+            if self.expected_digits != 0 {
+                // 桁数が足りません。
+                return error(
+                    self.log().str(
+                        "message",
+                        &format!(
+                            "Expected {} digits, but found {} digits `{}`.",
+                            self.expected_digits,
+                            self.string_buffer.len(),
+                            self.string_buffer
+                        ),
+                    ),
+                    &look_ahead_items,
+                    "hex_string_p.rs.96.",
+                );
             }
+            self.buffer.push(Token::new(
+                &self.string_buffer,
+                TokenType::SPPositionalNumeralString,
+            ));
+            return PResult::End;
         }
 
+        // １文字ずつだから、オーバーフローしないはず。
         PResult::Ongoing
     }
 
+    /// It is a digit of this positional numeral system.  
+    /// この進数の桁の文字か？  
+    ///
+    /// Separators `_` are only allowed when the number of digits is not fixed.  
+    /// 桁数が決まっているときは、区切り `_` は使えません。  
+    fn is_digit(&self, chr: char) -> bool {
+        match chr {
+            '_' => self.expected_digits == 0,
+            _ => match self.prefix.as_str() {
+                "0b" => chr.is_digit(2),
+                "0o" => chr.is_digit(8),
+                "0x" => chr.is_ascii_hexdigit(),
+                _ => chr.is_ascii_digit(),
+            },
+        }
+    }
+
     /// Log.  
     /// ログ。  
     pub fn log(&self) -> Table {
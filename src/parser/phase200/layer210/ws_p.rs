@@ -18,11 +18,6 @@ pub enum State {
     Wschar,
 }
 
-pub enum Judge {
-    Wschar,
-    HorizontalTabAndAscii,
-}
-
 impl Default for WsP {
     fn default() -> Self {
         WsP {
@@ -38,24 +33,6 @@ impl WsP {
     }
     /// # Arguments
     ///
-    /// * `token` - Token.  
-    ///             トークン。  
-    /// # Returns
-    ///
-    /// * `bool` - このパーサーの対象とするトークンになる.  
-    ///                             結果。
-    pub fn judge(chr: char) -> Option<Judge> {
-        if let Some(_judge) = WscharP::judge(chr) {
-            return Some(Judge::Wschar);
-        }
-        let unicode = chr as u32;
-        match unicode {
-            0x09 | 0x20..=0x7F => Some(Judge::HorizontalTabAndAscii),
-            _ => None,
-        }
-    }
-    /// # Arguments
-    ///
     /// * `look_ahead_items` - Tokens contains look ahead.  
     ///             先読みを含むトークン。  
     /// # Returns
@@ -73,10 +50,13 @@ impl WsP {
                 self.ws
                     .push_token(&Token::from_character(*chr0, TokenType::Ws));
 
-                // TODO 次の文字をチェックすべきか、次のトークンをチェックすべきか？
-                let chr1 = look_ahead_items.get(1).unwrap();
-                if let None = Self::judge(*chr1) {
-                    return PResult::End;
+                // Look-ahead.
+                // 先読み。
+                match look_ahead_items.get(1) {
+                    Some('\t') | Some(' ') => {}
+                    _ => {
+                        return PResult::End;
+                    }
                 }
             }
             State::Wschar => {
@@ -5,14 +5,25 @@
 //!
 //! ```
 //! // [ 1, 2, 3 ]
+//!
+//! // [ { x = 1, y = 2 }, true, 1979-05-27, 3.14, "a" ]
+//!
+//! // [
+//! //   "a", # Comment.
+//! //   "b",
+//! // ]
 //! ```
 
-use crate::model::{layer210::LiteralValue, layer220::Array};
+use crate::model::{
+    layer110::{Token, TokenType},
+    layer220::Array,
+};
 use crate::parser::phase200::error;
 use crate::parser::phase200::error_via;
 use crate::parser::phase200::{
-    layer210::{BasicStringP, LiteralStringP, PResult},
+    layer210::{BasicStringP, CommentP, LiteralStringP, LiteralValueP, PResult},
     layer220::ArrayP,
+    layer225::InlineTableP,
 };
 use casual_logger::Table;
 use look_ahead_items::LookAheadItems;
@@ -23,35 +34,36 @@ use look_ahead_items::LookAheadItems;
 /// Example: `[ 'a', 'b', 'c' ]`.  
 #[derive(Clone, Debug)]
 pub enum State {
-    /// After `[array]`.
-    AfterArray,
-    /// After `[],`.
-    AfterCommaBehindArray,
-    /// After `[ "a",`.
-    AfterCommaBefindString,
-    /// After `[ true,`.
-    AfterCommaBehindLiteralValue,
-    /// After " or '.
-    AfterString,
-    /// After `[`.
-    First,
-    /// `[ true` , か ] を待ちます。
-    LiteralValue,
+    /// After an item. Waiting for `,` or `]`.
+    AfterItem,
     /// After `[`.
     Array,
+    /// After `[` or `,`. Waiting for an item or `]`.
+    BeforeItem,
+    /// `# comment` after an item.
+    CommentAfterItem,
+    /// `# comment` before an item.
+    CommentBeforeItem,
     DoubleQuotedString,
     End,
+    /// `{ x = 1, y = 2 }`.
+    InlineTable,
     LiteralString,
+    /// `true`, `1979-05-27`, `3.14`.
+    LiteralValue,
 }
 
 impl Default for ArrayP {
     fn default() -> Self {
         ArrayP {
-            buffer: None,
+            buffer: Some(Array::default()),
             array_p: None,
             basic_string_p: None,
+            comment_p: None,
+            inline_table_p: None,
             literal_string_p: None,
-            state: State::First,
+            literal_value_p: None,
+            state: State::BeforeItem,
         }
     }
 }
@@ -72,94 +84,24 @@ impl ArrayP {
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         let chr0 = look_ahead_items.get(0).unwrap();
         match self.state {
-            // After `]`.
-            State::AfterArray => {
+            // After an item.
+            State::AfterItem => {
                 match chr0 {
-                    '\t' | ' ' => {} // Ignore it.
+                    '\t' | ' ' | '\r' | '\n' | '#' => {
+                        return self
+                            .parse_ws_comment_newline(look_ahead_items, State::CommentAfterItem);
+                    }
                     // ,
                     ',' => {
-                        self.state = State::AfterCommaBehindArray;
+                        self.buffer.as_mut().unwrap().push_comma();
+                        self.state = State::BeforeItem;
                     }
                     // ]
                     ']' => {
                         self.state = State::End;
                         return PResult::End;
                     }
-                    _ => return error(&mut self.log(), &look_ahead_items, "array.rs.93."),
-                }
-            }
-            // After `[],`.
-            State::AfterCommaBehindArray => {
-                match chr0 {
-                    // [
-                    '[' => {
-                        self.array_p = Some(Box::new(ArrayP::default()));
-                        self.state = State::Array;
-                    }
-                    '\t' | ' ' => {} // Ignore it.
-                    // ]
-                    ']' => {
-                        self.state = State::End;
-                        return PResult::End;
-                    }
-                    _ => return error(&mut self.log(), &look_ahead_items, "array.rs.130."),
-                }
-            }
-            // ", ` の次。
-            State::AfterCommaBefindString => {
-                match chr0 {
-                    // "
-                    '"' => {
-                        self.basic_string_p = Some(Box::new(BasicStringP::new()));
-                        self.state = State::DoubleQuotedString;
-                    }
-                    // '
-                    '\'' => {
-                        self.literal_string_p = Some(Box::new(LiteralStringP::new()));
-                        self.state = State::LiteralString;
-                    }
-                    '\t' | ' ' => {} // Ignore it.
-                    // ]
-                    ']' => {
-                        self.state = State::End;
-                        return PResult::End;
-                    }
-                    _ => return error(&mut self.log(), &look_ahead_items, "array.rs.176."),
-                }
-            }
-            // After `literal,`.
-            State::AfterCommaBehindLiteralValue => {
-                match chr0 {
-                    'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' => {
-                        // TODO 数字なら正しいが、リテラル文字列だと間違い。キー・バリューかもしれない。
-                        if let None = self.buffer {
-                            self.buffer = Some(Array::default());
-                        }
-                        let m = self.buffer.as_mut().unwrap();
-                        m.push_literal_string(&LiteralValue::from_character(*chr0));
-                        self.state = State::LiteralValue;
-                    }
-                    '\t' | ' ' => {} // Ignore it.
-                    // `]`.
-                    ']' => {
-                        self.state = State::End;
-                        return PResult::End;
-                    }
-                    _ => return error(&mut self.log(), &look_ahead_items, "array.rs.218."),
-                }
-            }
-            // After " or '.
-            State::AfterString => {
-                match chr0 {
-                    '\t' | ' ' => {} // Ignore it.
-                    ',' => {
-                        self.state = State::AfterCommaBefindString;
-                    }
-                    ']' => {
-                        self.state = State::End;
-                        return PResult::End;
-                    }
-                    _ => return error(&mut self.log(), &look_ahead_items, "array.rs.245."),
+                    _ => return error(&mut self.log(), look_ahead_items, "array.rs.93."),
                 }
             }
             // `[array]`.
@@ -168,51 +110,56 @@ impl ArrayP {
                 match p.parse(look_ahead_items) {
                     PResult::End => {
                         if let Some(child_m) = p.flush() {
-                            if let None = self.buffer {
-                                self.buffer = Some(Array::default());
-                            }
                             let m = self.buffer.as_mut().unwrap();
                             m.push_array(&child_m);
                         } else {
-                            // Empty array.
+                            return error(&mut self.log(), look_ahead_items, "array.rs.272.");
                         }
                         self.array_p = None;
-                        self.state = State::AfterArray;
+                        self.state = State::AfterItem;
                     }
                     PResult::Err(mut table) => {
                         return error_via(
                             &mut table,
                             &mut self.log(),
-                            &look_ahead_items,
+                            look_ahead_items,
                             "array.rs.283.",
                         );
                     }
                     PResult::Ongoing => {}
                 }
             }
-            // After `[`.
-            State::First => {
+            // After `[` or `,`.
+            State::BeforeItem => {
                 match chr0 {
+                    '\t' | ' ' | '\r' | '\n' | '#' => {
+                        return self
+                            .parse_ws_comment_newline(look_ahead_items, State::CommentBeforeItem);
+                    }
                     // "
                     '"' => {
                         self.basic_string_p = Some(Box::new(BasicStringP::new()));
                         self.state = State::DoubleQuotedString;
                     }
-                    'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' => {
-                        // TODO 数字なら正しいが、リテラル文字列だと間違い。キー・バリューかもしれない。
-                        if let None = self.buffer {
-                            self.buffer = Some(Array::default());
-                        }
-                        let m = self.buffer.as_mut().unwrap();
-                        m.push_literal_string(&LiteralValue::from_character(*chr0));
+                    // `true`, `1979-05-27`, `3.14`. Each item has its own type.
+                    'A'..='Z' | 'a'..='z' | '0'..='9' | '+' | '-' | '_' => {
+                        self.literal_value_p = Some(LiteralValueP::default());
                         self.state = State::LiteralValue;
+                        // The first character is a part of the value.
+                        // 最初の文字は値の一部です。
+                        return self.parse(look_ahead_items);
+                    }
+                    // `{`. Recursive.
+                    '{' => {
+                        self.inline_table_p = Some(Box::new(InlineTableP::default()));
+                        self.state = State::InlineTable;
                     }
                     // `[`. Recursive.
                     '[' => {
                         self.array_p = Some(Box::new(ArrayP::default()));
                         self.state = State::Array;
                     }
-                    // `]`. Empty array.
+                    // `]`. Empty array, or after the trailing comma.
                     ']' => {
                         self.state = State::End;
                         return PResult::End;
@@ -222,42 +169,31 @@ impl ArrayP {
                         self.literal_string_p = Some(Box::new(LiteralStringP::new()));
                         self.state = State::LiteralString;
                     }
-                    '\t' | ' ' => {} // Ignore it.
-                    _ => return error(&mut self.log(), &look_ahead_items, "array.rs.358."),
+                    _ => return error(&mut self.log(), look_ahead_items, "array.rs.358."),
                 }
             }
-            State::LiteralValue => match chr0 {
-                ',' => {
-                    self.state = State::AfterCommaBehindLiteralValue;
-                }
-                ']' => {
-                    self.state = State::End;
-                    return PResult::End;
-                }
-                _ => return error(&mut self.log(), &look_ahead_items, "array.rs.383."),
-            },
+            State::CommentAfterItem | State::CommentBeforeItem => {
+                return self.parse_comment(look_ahead_items);
+            }
             // "dog".
             State::DoubleQuotedString => {
                 let p = self.basic_string_p.as_mut().unwrap();
-                match p.parse(&look_ahead_items) {
+                match p.parse(look_ahead_items) {
                     PResult::End => {
                         if let Some(child_m) = p.flush() {
-                            if let None = self.buffer {
-                                self.buffer = Some(Array::default());
-                            }
                             let m = self.buffer.as_mut().unwrap();
                             m.push_double_quote_string(&child_m);
                             self.basic_string_p = None;
-                            self.state = State::AfterString;
+                            self.state = State::AfterItem;
                         } else {
-                            return error(&mut self.log(), &look_ahead_items, "array.rs.439.");
+                            return error(&mut self.log(), look_ahead_items, "array.rs.439.");
                         }
                     }
                     PResult::Err(mut table) => {
                         return error_via(
                             &mut table,
                             &mut self.log(),
-                            &look_ahead_items,
+                            look_ahead_items,
                             "array.rs.448.",
                         );
                     }
@@ -265,36 +201,168 @@ impl ArrayP {
                 }
             }
             State::End => {
-                return error(&mut self.log(), &look_ahead_items, "array.rs.466.");
+                return error(&mut self.log(), look_ahead_items, "array.rs.466.");
+            }
+            // `{ x = 1, y = 2 }`.
+            State::InlineTable => {
+                let p = self.inline_table_p.as_mut().unwrap();
+                match p.parse(look_ahead_items) {
+                    PResult::End => {
+                        if let Some(child_m) = p.flush() {
+                            let m = self.buffer.as_mut().unwrap();
+                            m.push_inline_table(&child_m);
+                            self.inline_table_p = None;
+                            self.state = State::AfterItem;
+                        } else {
+                            return error(&mut self.log(), look_ahead_items, "array.rs.477.");
+                        }
+                    }
+                    PResult::Err(mut table) => {
+                        return error_via(
+                            &mut table,
+                            &mut self.log(),
+                            look_ahead_items,
+                            "array.rs.485.",
+                        );
+                    }
+                    PResult::Ongoing => {}
+                }
             }
             // `'C:\temp'`.
             State::LiteralString => {
                 let p = self.literal_string_p.as_mut().unwrap();
-                match p.parse(&look_ahead_items) {
+                match p.parse(look_ahead_items) {
                     PResult::End => {
                         if let Some(child_m) = p.flush() {
-                            if let None = self.buffer {
-                                self.buffer = Some(Array::default());
-                            }
                             let m = self.buffer.as_mut().unwrap();
                             m.push_single_quote_string(&child_m);
                             self.literal_string_p = None;
-                            self.state = State::AfterString;
+                            self.state = State::AfterItem;
                         } else {
-                            return error(&mut self.log(), &look_ahead_items, "array.rs.493.");
+                            return error(&mut self.log(), look_ahead_items, "array.rs.493.");
                         }
                     }
                     PResult::Err(mut table) => {
                         return error_via(
                             &mut table,
                             &mut self.log(),
-                            &look_ahead_items,
+                            look_ahead_items,
                             "array.rs.502.",
                         );
                     }
                     PResult::Ongoing => {}
                 }
             }
+            // `true`, `1979-05-27`, `3.14`.
+            State::LiteralValue => {
+                let p = self.literal_value_p.as_mut().unwrap();
+                match p.parse(look_ahead_items) {
+                    PResult::End => {
+                        if let Some(child_m) = p.flush() {
+                            let m = self.buffer.as_mut().unwrap();
+                            m.push_literal_string(&child_m);
+                            self.literal_value_p = None;
+                            self.state = State::AfterItem;
+                        } else {
+                            return error(&mut self.log(), look_ahead_items, "array.rs.522.");
+                        }
+                    }
+                    PResult::Err(mut table) => {
+                        return error_via(
+                            &mut table,
+                            &mut self.log(),
+                            look_ahead_items,
+                            "array.rs.530.",
+                        );
+                    }
+                    PResult::Ongoing => {}
+                }
+            }
+        }
+        PResult::Ongoing
+    }
+    /// Whitespace, comment or newline. Arrays can span lines.  
+    /// 空白、コメント、改行。配列は複数行にまたがれます。  
+    ///
+    /// # Arguments
+    ///
+    /// * `look_ahead_items` - Tokens contains look ahead.  
+    ///   先読みを含むトークン。  
+    /// * `comment_state` - State if it's a comment.  
+    ///   コメントだったときの状態。  
+    fn parse_ws_comment_newline(
+        &mut self,
+        look_ahead_items: &LookAheadItems<char>,
+        comment_state: State,
+    ) -> PResult {
+        let chr0 = look_ahead_items.get(0).unwrap();
+        let token = match chr0 {
+            '\t' | ' ' => Token::from_character(*chr0, TokenType::Ws),
+            '\n' => Token::from_character(*chr0, TokenType::Newline),
+            '\r' => {
+                if let Some('\n') = look_ahead_items.get(1) {
+                    Token::from_character(*chr0, TokenType::Newline)
+                } else {
+                    return error(
+                        self.log().str(
+                            "message",
+                            "Control character U+000D is not allowed outside of `\\r\\n`.",
+                        ),
+                        look_ahead_items,
+                        "array.rs.545.",
+                    );
+                }
+            }
+            // `#`
+            _ => {
+                self.comment_p = Some(CommentP::new());
+                self.state = comment_state;
+                return self.parse_comment(look_ahead_items);
+            }
+        };
+        self.buffer
+            .as_mut()
+            .unwrap()
+            .push_ws_comment_newline_token(&token);
+        PResult::Ongoing
+    }
+    /// `# comment` up to the end of line.  
+    /// 行末までの `# comment` 。  
+    ///
+    /// # Arguments
+    ///
+    /// * `look_ahead_items` - Tokens contains look ahead.  
+    ///   先読みを含むトークン。  
+    fn parse_comment(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
+        let chr0 = look_ahead_items.get(0).unwrap();
+        let p = self.comment_p.as_mut().unwrap();
+        if let Some(judge) = p.judge1(*chr0) {
+            p.commit1(&judge);
+            match p.forward1(look_ahead_items) {
+                PResult::End => {
+                    let comment = p.get_product();
+                    let m = self.buffer.as_mut().unwrap();
+                    for token in &comment.tokens {
+                        m.push_ws_comment_newline_token(token);
+                    }
+                    self.comment_p = None;
+                    self.state = match self.state {
+                        State::CommentAfterItem => State::AfterItem,
+                        _ => State::BeforeItem,
+                    };
+                }
+                PResult::Err(mut table) => {
+                    return error_via(
+                        &mut table,
+                        &mut self.log(),
+                        look_ahead_items,
+                        "array.rs.588.",
+                    );
+                }
+                PResult::Ongoing => {}
+            }
+        } else {
+            return error(&mut self.log(), look_ahead_items, "array.rs.594.");
         }
         PResult::Ongoing
     }
@@ -312,9 +380,18 @@ impl ArrayP {
         if let Some(p) = &self.literal_string_p {
             t.sub_t("literal_string_p", &p.log());
         }
+        if let Some(p) = &self.literal_value_p {
+            t.sub_t("literal_value_p", &p.log());
+        }
+        if let Some(p) = &self.inline_table_p {
+            t.sub_t("inline_table_p", &p.log());
+        }
         if let Some(p) = &self.array_p {
             t.sub_t("array_p", &p.log());
         }
+        if let Some(p) = &self.comment_p {
+            t.sub_t("comment_p", &p.log());
+        }
 
         t
     }
@@ -2,8 +2,9 @@ pub mod array_p;
 
 use crate::model::layer220::Array;
 use crate::parser::phase200::{
-    layer210::{BasicStringP, LiteralStringP},
+    layer210::{BasicStringP, CommentP, LiteralStringP, LiteralValueP},
     layer220::array_p::State as ArrayState,
+    layer225::InlineTableP,
 };
 
 /*
@@ -21,12 +22,15 @@ pub fn usize_to_i128(num: usize) -> i128 {
 /// 配列パーサー。  
 ///
 /// Example: `[ 'a', 'b', 'c' ]`.  
-#[derive(Clone)]
 pub struct ArrayP {
     buffer: Option<Array>,
     /// Recursive.
     array_p: Option<Box<ArrayP>>,
     basic_string_p: Option<Box<BasicStringP>>,
+    comment_p: Option<CommentP>,
+    /// Recursive.
+    inline_table_p: Option<Box<InlineTableP>>,
     literal_string_p: Option<Box<LiteralStringP>>,
+    literal_value_p: Option<LiteralValueP>,
     state: ArrayState,
 }
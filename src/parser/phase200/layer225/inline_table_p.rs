@@ -1,7 +1,18 @@
 //! Inline table syntax parser.  
 //! インライン・テーブル構文パーサー。  
+//!
+//! # Examples
+//!
+//! ```
+//! // { x = 1, y = 2 }
+//! // { b = { c = [1, { d = 2 }] } }
+//! // { type.name = "pug" }
+//! ```
 
-use crate::model::layer225::InlineTable;
+use crate::model::{
+    layer110::{Token, TokenType},
+    layer225::{InlineTable, Keyval},
+};
 use crate::parser::phase200::error;
 use crate::parser::phase200::error_via;
 use crate::parser::phase200::{
@@ -21,6 +32,8 @@ pub enum State {
     First,
     Keyval,
     AfterKeyval,
+    /// After `,`. No trailing comma.
+    BeforeKeyval,
 }
 
 impl Default for InlineTableP {
@@ -29,6 +42,7 @@ impl Default for InlineTableP {
             state: State::First,
             buffer: Some(InlineTable::default()),
             keyval_p: None,
+            keys: Vec::new(),
         }
     }
 }
@@ -49,12 +63,12 @@ impl InlineTableP {
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         let chr0 = look_ahead_items.get(0).unwrap();
         match self.state {
-            // After `{`.
-            State::First => {
+            // After `{` or `,`.
+            State::First | State::BeforeKeyval => {
                 match chr0 {
-                    '\t' | ' ' => {} // Ignore it.
-                    // `apple.banana`
-                    'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' => {
+                    '\t' | ' ' => self.push_ws(chr0),
+                    // `apple.banana`, `"apple"`, `'apple'`.
+                    'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '"' | '\'' => {
                         self.keyval_p = Some(Box::new(KeyvalP::new()));
                         self.state = State::Keyval;
                         match self.keyval_p.as_mut().unwrap().parse(look_ahead_items) {
@@ -78,9 +92,20 @@ impl InlineTableP {
                         }
                     }
                     '}' => {
+                        if let State::BeforeKeyval = self.state {
+                            return error(
+                                self.log().str(
+                                    "message",
+                                    "A trailing comma is not allowed in an inline table.",
+                                ),
+                                look_ahead_items,
+                                "inline_table.rs.89.",
+                            );
+                        }
                         // Empty inline-table.
                         return PResult::End;
                     }
+                    '\r' | '\n' => return self.newline_error(look_ahead_items),
                     _ => return error(&mut self.log(), &look_ahead_items, "inline_table.rs.63."),
                 }
             }
@@ -90,6 +115,14 @@ impl InlineTableP {
                 match p.parse(look_ahead_items) {
                     PResult::End => {
                         if let Some(child_m) = p.flush() {
+                            if let Some(message) = self.key_conflict_message(&child_m) {
+                                return error(
+                                    self.log().str("message", &message),
+                                    look_ahead_items,
+                                    "inline_table.rs.109.",
+                                );
+                            }
+                            self.keys.push(child_m.key.get_segments().to_vec());
                             self.buffer.as_mut().unwrap().push_keyval(&child_m);
                             self.keyval_p = None;
                             self.state = State::AfterKeyval;
@@ -114,20 +147,59 @@ impl InlineTableP {
             }
             // After `banana = 3`.
             State::AfterKeyval => match chr0 {
-                '\t' | ' ' => {} // Ignore it.
+                '\t' | ' ' => self.push_ws(chr0),
                 // `,`
                 ',' => {
-                    self.state = State::First;
+                    self.buffer.as_mut().unwrap().push_comma();
+                    self.state = State::BeforeKeyval;
                 }
                 // `}`
                 '}' => {
                     return PResult::End;
                 }
+                '\r' | '\n' => return self.newline_error(look_ahead_items),
                 _ => return error(&mut self.log(), &look_ahead_items, "inline_table.rs.96."),
             },
         }
         PResult::Ongoing
     }
+    fn push_ws(&mut self, chr: &char) {
+        self.buffer
+            .as_mut()
+            .unwrap()
+            .push_ws_token(&Token::from_character(*chr, TokenType::Ws));
+    }
+    fn newline_error(&self, look_ahead_items: &LookAheadItems<char>) -> PResult {
+        error(
+            self.log()
+                .str("message", "An inline table must be on a single line."),
+            look_ahead_items,
+            "inline_table.rs.146.",
+        )
+    }
+    /// An inline table is self-contained. A key can't be defined twice, and a value can't be extended by a dotted key.  
+    /// インライン・テーブルは自己完結しています。キーは２回定義できず、値をドット付きキーで拡張することもできません。  
+    fn key_conflict_message(&self, keyval: &Keyval) -> Option<String> {
+        let key = keyval.key.get_segments();
+        for defined in &self.keys {
+            if defined.as_slice() == key {
+                return Some(format!("Duplicate key `{}`.", key.join(".")));
+            } else if key.starts_with(defined) {
+                return Some(format!(
+                    "`{}` is already defined, so `{}` can't extend it.",
+                    defined.join("."),
+                    key.join(".")
+                ));
+            } else if defined.starts_with(key) {
+                return Some(format!(
+                    "`{}` is already defined as a table by `{}`.",
+                    key.join("."),
+                    defined.join(".")
+                ));
+            }
+        }
+        None
+    }
 
     /// Log.  
     /// ログ。  
@@ -5,13 +5,14 @@
 //!
 //! ```
 //! // key = val
+//! // site."google.com" = val
 //! ```
 
 use crate::model::layer225::Keyval;
 use crate::parser::phase200::error;
 use crate::parser::phase200::error_via;
 use crate::parser::phase200::{
-    layer210::{KeyP, PResult},
+    layer210::{KeyP, KeyvalSepP, PResult},
     layer225::{KeyvalP, ValP},
 };
 use casual_logger::Table as LogTable;
@@ -21,12 +22,11 @@ use look_ahead_items::LookAheadItems;
 /// 構文状態遷移。  
 #[derive(Debug)]
 pub enum State {
-    AfterEquals,
-    // After key.
-    // キーの後。
-    BeforeEqual,
     End,
     First,
+    Key,
+    /// ` = `.
+    KeyvalSep,
     Val,
 }
 
@@ -34,8 +34,10 @@ impl KeyvalP {
     pub fn new() -> Self {
         KeyvalP {
             key_buffer: None,
+            sep_buffer: None,
             val_buffer: None,
             key_p: Some(KeyP::default()),
+            keyval_sep_p: None,
             val_p: None,
             state: State::First,
         }
@@ -44,7 +46,12 @@ impl KeyvalP {
     pub fn flush(&mut self) -> Option<Keyval> {
         let m = if let Some(key) = &self.key_buffer {
             if let Some(val) = &self.val_buffer {
-                Some(Keyval::new(&key, &val))
+                let mut m = Keyval::new(&key, &val);
+                if let Some((ws1, ws2)) = &self.sep_buffer {
+                    m.ws1 = ws1.clone();
+                    m.ws2 = ws2.clone();
+                }
+                Some(m)
             } else {
                 panic!("keyval_p.rs.53.")
             }
@@ -52,6 +59,7 @@ impl KeyvalP {
             panic!("keyval_p.rs.56.")
         };
         self.key_buffer = None;
+        self.sep_buffer = None;
         self.val_buffer = None;
         m
     }
@@ -67,53 +75,60 @@ impl KeyvalP {
     pub fn parse(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         let chr0 = look_ahead_items.get(0).unwrap();
         match self.state {
-            // After `=`.
-            State::AfterEquals => {
-                self.val_p = Some(ValP::default());
-                self.state = State::Val;
-            }
-            // After key.
-            State::BeforeEqual => {
+            State::First => {
                 match chr0 {
                     '\t' | ' ' => {} //Ignored it.
-                    // `=`.
-                    '=' => {
-                        self.state = State::AfterEquals;
+                    'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '"' | '\'' => {
+                        self.state = State::Key;
+                        return self.parse(look_ahead_items);
                     }
                     _ => return error(&mut self.log(), &look_ahead_items, "keyval.rs.65."),
                 }
             }
-            State::First => {
-                match chr0 {
-                    '\t' | ' ' => {} //Ignored it.
-                    'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' => {
-                        let p = self.key_p.as_mut().unwrap();
-                        match p.parse(&look_ahead_items) {
-                            PResult::End => {
-                                if let Some(child_m) = p.flush() {
-                                    self.key_buffer = Some(child_m);
-                                    self.key_p = None;
-                                    self.state = State::BeforeEqual;
-                                } else {
-                                    return error(
-                                        &mut self.log(),
-                                        &look_ahead_items,
-                                        "keyval.rs.84.",
-                                    );
-                                }
-                            }
-                            PResult::Err(mut table) => {
-                                return error_via(
-                                    &mut table,
-                                    &mut self.log(),
-                                    &look_ahead_items,
-                                    "keyval.rs.84.",
-                                );
-                            }
-                            PResult::Ongoing => {}
+            // `abc`, `site."google.com"`.
+            State::Key => {
+                let p = self.key_p.as_mut().unwrap();
+                match p.parse(&look_ahead_items) {
+                    PResult::End => {
+                        if let Some(child_m) = p.flush() {
+                            self.key_buffer = Some(child_m);
+                            self.key_p = None;
+                            self.keyval_sep_p = Some(KeyvalSepP::default());
+                            self.state = State::KeyvalSep;
+                        } else {
+                            return error(&mut self.log(), &look_ahead_items, "keyval.rs.84.");
                         }
                     }
-                    _ => return error(&mut self.log(), &look_ahead_items, "keyval.rs.65."),
+                    PResult::Err(mut table) => {
+                        return error_via(
+                            &mut table,
+                            &mut self.log(),
+                            &look_ahead_items,
+                            "keyval.rs.84.",
+                        );
+                    }
+                    PResult::Ongoing => {}
+                }
+            }
+            // ` = `.
+            State::KeyvalSep => {
+                let p = self.keyval_sep_p.as_mut().unwrap();
+                match p.parse(look_ahead_items) {
+                    PResult::End => {
+                        self.sep_buffer = Some(p.flush());
+                        self.keyval_sep_p = None;
+                        self.val_p = Some(ValP::default());
+                        self.state = State::Val;
+                    }
+                    PResult::Err(mut table) => {
+                        return error_via(
+                            &mut table,
+                            &mut self.log(),
+                            &look_ahead_items,
+                            "keyval.rs.113.",
+                        );
+                    }
+                    PResult::Ongoing => {}
                 }
             }
             // After `=`.
@@ -155,9 +170,15 @@ impl KeyvalP {
         if let Some(m) = &self.key_buffer {
             t.str("key_buffer", &m.to_string());
         }
+        if let Some(p) = &self.key_p {
+            t.sub_t("key_p", &p.log());
+        }
         if let Some(m) = &self.val_buffer {
             t.str("val_buffer", &m.to_string());
         }
+        if let Some(p) = &self.keyval_sep_p {
+            t.sub_t("keyval_sep_p", &p.log());
+        }
         if let Some(p) = &self.val_p {
             t.sub_t("val_p", &p.log());
         }
@@ -3,11 +3,11 @@ pub mod keyval_p;
 pub mod val_p;
 
 use crate::model::{
-    layer210::Key,
+    layer210::{Key, Ws},
     layer225::{InlineTable, Val},
 };
 use crate::parser::phase200::{
-    layer210::{BasicStringP, KeyP, LiteralStringP, LiteralValueP},
+    layer210::{BasicStringP, KeyP, KeyvalSepP, LiteralStringP, LiteralValueP},
     layer220::ArrayP,
     layer225::{
         inline_table_p::State as InlineTableState, keyval_p::State as KeyvalState,
@@ -23,6 +23,9 @@ pub struct InlineTableP {
     state: InlineTableState,
     buffer: Option<InlineTable>,
     keyval_p: Option<Box<KeyvalP>>,
+    /// Keys defined in this table. It is self-contained.  
+    /// このテーブルで定義したキー。自己完結しています。  
+    keys: Vec<Vec<String>>,
 }
 
 /// Key value syntax parser.  
@@ -31,8 +34,12 @@ pub struct InlineTableP {
 /// `key = value`.  
 pub struct KeyvalP {
     key_buffer: Option<Key>,
+    /// Whitespace before and after `=`.  
+    /// `=` の前と後の空白。  
+    sep_buffer: Option<(Ws, Ws)>,
     val_buffer: Option<Val>,
     key_p: Option<KeyP>,
+    keyval_sep_p: Option<KeyvalSepP>,
     val_p: Option<ValP>,
     state: KeyvalState,
 }
@@ -1,9 +1,11 @@
 //! Broad-line syntax parser.  
 //! `縦幅のある行` 構文パーサー。  
 
-use crate::model::layer210::Comment;
-use crate::model::layer210::Ws;
-use crate::model::layer230::Expression;
+use crate::model::{
+    layer110::{Token, TokenType},
+    layer210::{Comment, Newline, Ws},
+    layer230::Expression,
+};
 use crate::parser::phase200::error;
 use crate::parser::phase200::error_via;
 use crate::parser::phase200::layer230::WsP;
@@ -17,17 +19,20 @@ use look_ahead_items::LookAheadItems;
 
 /// Line syntax machine state.  
 /// 行構文状態遷移。  
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum State {
     AfterArrayOfTable,
     AfterLeftSquareBracket,
     AfterTable,
     End,
+    /// End of line. `\r\n` or `\n`.
+    Eol,
     /// `[[name]]`
     HeaderOfArrayOfTable,
-    Finished,
     /// `[name]`
     Table,
+    /// `# comment` after the header.
+    HeaderComment,
     First,
     /// Whitespace 1.
     Ws1,
@@ -63,7 +68,7 @@ impl ExpressionP {
     /// # Arguments
     ///
     /// * `look_ahead_items` - Tokens contains look ahead.  
-    ///             先読みを含むトークン。  
+    ///   先読みを含むトークン。  
     /// # Returns
     ///
     /// * `PResult` - Result.  
@@ -72,10 +77,36 @@ impl ExpressionP {
         let chr0 = look_ahead_items.get(0).unwrap();
 
         match self.state {
-            State::AfterArrayOfTable => {
-                // TODO 後ろにコメントがあるかも。
-                return error(&mut self.log(), &look_ahead_items, "expression.rs.66.");
-            }
+            State::AfterArrayOfTable | State::AfterTable => match chr0 {
+                '\t' | ' ' => {
+                    if let Some((ws2, _comment)) = self.header_trailer() {
+                        ws2.push_token(&Token::from_character(*chr0, TokenType::Ws));
+                    }
+                    return self.end_of_expression(self.state.clone(), look_ahead_items);
+                }
+                '\r' | '\n' => {
+                    return self.parse_newline(look_ahead_items);
+                }
+                // `#`
+                '#' => {
+                    self.comment_p = Some(CommentP::new());
+                    self.state = State::HeaderComment;
+                    return self.parse(look_ahead_items);
+                }
+                _ => {
+                    return error(
+                        self.log().str(
+                            "message",
+                            &format!(
+                                "Expected end of line after the header, but found `{}`.",
+                                chr0.escape_debug()
+                            ),
+                        ),
+                        look_ahead_items,
+                        "expression.rs.66.",
+                    );
+                }
+            },
             State::AfterLeftSquareBracket => match chr0 {
                 '[' => {
                     self.header_p_of_array_of_table = Some(HeaderPOfArrayOfTable::new());
@@ -87,22 +118,51 @@ impl ExpressionP {
                     return self.parse_header_of_table(look_ahead_items);
                 }
             },
-            State::AfterTable => {
-                // TODO 後ろにコメントがあるかも。
-                return error(&mut self.log(), &look_ahead_items, "expression.rs.106.");
-            }
             State::End => {
                 return error(&mut self.log(), &look_ahead_items, "expression.rs.98.");
             }
+            State::Eol => {
+                return self.parse_newline(look_ahead_items);
+            }
+            State::HeaderComment => {
+                let p = self.comment_p.as_mut().unwrap();
+                if let Some(judge) = p.judge1(*chr0) {
+                    p.commit1(&judge);
+                    match p.forward1(look_ahead_items) {
+                        PResult::End => {
+                            let comment = self.comment_p.as_mut().unwrap().get_product();
+                            if let Some((_ws2, m)) = self.header_trailer() {
+                                *m = Some(comment);
+                            }
+                            self.comment_p = None;
+                            return self.end_of_expression(State::Eol, look_ahead_items);
+                        }
+                        PResult::Err(mut table) => {
+                            return error_via(
+                                &mut table,
+                                &mut self.log(),
+                                look_ahead_items,
+                                "expression.rs.141.",
+                            );
+                        }
+                        PResult::Ongoing => {}
+                    }
+                } else {
+                    return error(&mut self.log(), look_ahead_items, "expression.rs.145.");
+                }
+            }
             State::HeaderOfArrayOfTable => {
                 let p = self.header_p_of_array_of_table.as_mut().unwrap();
                 match p.parse(&look_ahead_items) {
                     PResult::End => {
-                        if let Some(m) = p.flush() {
+                        if let Some(mut m) = p.flush() {
+                            if let Some(ws_p_1) = self.ws_p_1.as_mut() {
+                                m.ws1 = ws_p_1.get_ws();
+                            }
                             self.buffer = Some(Expression::from_header_of_array_of_table(&m));
                             self.header_p_of_array_of_table = None;
-                            self.state = State::AfterArrayOfTable;
-                            return PResult::End;
+                            return self
+                                .end_of_expression(State::AfterArrayOfTable, look_ahead_items);
                         } else {
                             return error(&mut self.log(), &look_ahead_items, "expression.rs.123.");
                         }
@@ -118,9 +178,6 @@ impl ExpressionP {
                     PResult::Ongoing => {}
                 }
             }
-            State::Finished => {
-                return error(&mut self.log(), &look_ahead_items, "expression.rs.205.");
-            }
             State::Table => {
                 return self.parse_header_of_table(look_ahead_items);
             }
@@ -137,15 +194,15 @@ impl ExpressionP {
                         } else {
                             None
                         },
+                        Newline::default(),
                     ));
                     self.ws_p_1 = None;
                     self.comment_p = None;
                     self.keyval_p = None;
-                    self.state = State::Finished;
-                    return PResult::End;
+                    return self.parse_newline(look_ahead_items);
                 }
-                // `abc`
-                'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' => {
+                // `abc`, `"abc"`, `'abc'`
+                'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '"' | '\'' => {
                     self.keyval_p = Some(KeyvalP::new());
                     match self.keyval_p.as_mut().unwrap().parse(&look_ahead_items) {
                         PResult::End => {
@@ -185,11 +242,11 @@ impl ExpressionP {
                                     } else {
                                         None
                                     },
+                                    Newline::default(),
                                 ));
                                 self.ws_p_1 = None;
                                 self.comment_p = None;
-                                self.state = State::End;
-                                return PResult::End;
+                                return self.end_of_expression(State::Eol, look_ahead_items);
                             }
                             PResult::Err(mut table) => {
                                 return error_via(
@@ -213,7 +270,19 @@ impl ExpressionP {
                     }
                     match self.ws_p_1.as_mut().unwrap().parse(&look_ahead_items) {
                         PResult::End => {
-                            return error(&mut self.log(), &look_ahead_items, "expression.rs.197.");
+                            if look_ahead_items.get(1).is_none() {
+                                // End of file.
+                                // ファイルの終わり。
+                                self.buffer = Some(Expression::EmptyLine(
+                                    self.ws_p_1.as_mut().unwrap().get_ws(),
+                                    None,
+                                    Newline::default(),
+                                ));
+                                self.ws_p_1 = None;
+                                self.state = State::End;
+                                return PResult::End;
+                            }
+                            self.state = State::Ws1;
                         }
                         PResult::Err(mut table) => {
                             return error_via(
@@ -248,11 +317,11 @@ impl ExpressionP {
                                 } else {
                                     None
                                 },
+                                Newline::default(),
                             ));
                             self.ws_p_1 = None;
                             self.comment_p = None;
-                            self.state = State::End;
-                            return PResult::End;
+                            return self.end_of_expression(State::Eol, look_ahead_items);
                         }
                         PResult::Err(mut table) => {
                             return error_via(
@@ -271,49 +340,22 @@ impl ExpressionP {
             State::Ws1Keyval => {
                 let p = self.keyval_p.as_mut().unwrap();
                 match p.parse(&look_ahead_items) {
-                    PResult::End => {
-                        let chr1_ahead = look_ahead_items.get(1).unwrap();
-
-                        match chr1_ahead {
-                            '\r' | '\n' => {
-                                if let Some(keyval) = p.flush() {
-                                    self.buffer = Some(Expression::from_keyval(
-                                        &if let Some(ws_p_1) = self.ws_p_1.as_mut() {
-                                            ws_p_1.get_ws()
-                                        } else {
-                                            Ws::default()
-                                        },
-                                        &keyval,
-                                        &Ws::default(),
-                                        &Comment::default(),
-                                    ));
-                                    self.keyval_p = None;
-                                } else {
-                                    return error(
-                                        &mut self.log(),
-                                        &look_ahead_items,
-                                        "expression.rs.222.",
-                                    );
-                                }
-                                return PResult::End;
-                            }
-                            '#' => {
-                                self.comment_p = Some(CommentP::new());
-                                self.state = State::Ws1KeyvalWs2Comment;
-                            }
-                            '\t' | ' ' => {
-                                self.ws_p_2 = Some(WsP::default());
-                                self.state = State::Ws1KeyvalWs2;
-                            }
-                            _ => {
-                                return error(
-                                    &mut self.log(),
-                                    &look_ahead_items,
-                                    "expression.rs.222.",
-                                );
-                            }
+                    PResult::End => match look_ahead_items.get(1) {
+                        None | Some('\r') | Some('\n') => {
+                            return self.end_of_keyval(look_ahead_items);
                         }
-                    } // Ignored it.
+                        Some('#') => {
+                            self.comment_p = Some(CommentP::new());
+                            self.state = State::Ws1KeyvalWs2Comment;
+                        }
+                        Some('\t') | Some(' ') => {
+                            self.ws_p_2 = Some(WsP::default());
+                            self.state = State::Ws1KeyvalWs2;
+                        }
+                        _ => {
+                            return error(&mut self.log(), &look_ahead_items, "expression.rs.222.");
+                        }
+                    },
                     PResult::Err(mut table) => {
                         return error_via(
                             &mut table,
@@ -325,29 +367,32 @@ impl ExpressionP {
                     PResult::Ongoing => {}
                 }
             }
-            State::Ws1KeyvalWs2 => match chr0 {
-                '\t' | ' ' => {
-                    let chr1_ahead = look_ahead_items.get(1).unwrap();
-
-                    match chr1_ahead {
-                        '\r' | '\n' => {
-                            return PResult::End;
+            State::Ws1KeyvalWs2 => {
+                let p = self.ws_p_2.as_mut().unwrap();
+                match p.parse(&look_ahead_items) {
+                    PResult::End => match look_ahead_items.get(1) {
+                        None | Some('\r') | Some('\n') => {
+                            return self.end_of_keyval(look_ahead_items);
                         }
-                        '#' => {
+                        Some('#') => {
                             self.comment_p = Some(CommentP::new());
                             self.state = State::Ws1KeyvalWs2Comment;
                         }
                         _ => {
                             return error(&mut self.log(), &look_ahead_items, "expression.rs.222.");
                         }
+                    },
+                    PResult::Err(mut table) => {
+                        return error_via(
+                            &mut table,
+                            &mut self.log(),
+                            &look_ahead_items,
+                            "expression.rs.84.",
+                        );
                     }
-                } // Ignore it.
-                // `,`
-                '\r' | '\n' => return PResult::End,
-                _ => {
-                    return error(&mut self.log(), &look_ahead_items, "expression.rs.84.");
+                    PResult::Ongoing => {}
                 }
-            },
+            }
             State::Ws1KeyvalWs2Comment => {
                 let p = self.comment_p.as_mut().unwrap();
                 let judge = p.judge1(*chr0);
@@ -372,11 +417,11 @@ impl ExpressionP {
                                 } else {
                                     None
                                 },
+                                Newline::default(),
                             ));
                             self.ws_p_1 = None;
                             self.comment_p = None;
-                            self.state = State::End;
-                            return PResult::End;
+                            return self.end_of_expression(State::Eol, look_ahead_items);
                         }
                         PResult::Err(mut table) => {
                             return error_via(
@@ -402,16 +447,18 @@ impl ExpressionP {
     /// # Arguments
     ///
     /// * `look_ahead_items` - Tokens contains look ahead.  
-    ///             先読みを含むトークン。  
+    ///   先読みを含むトークン。  
     fn parse_header_of_table(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
         let p = self.header_p_of_table.as_mut().unwrap();
         match p.parse(&look_ahead_items) {
             PResult::End => {
-                if let Some(m) = p.flush() {
+                if let Some(mut m) = p.flush() {
+                    if let Some(ws_p_1) = self.ws_p_1.as_mut() {
+                        m.ws1 = ws_p_1.get_ws();
+                    }
                     self.buffer = Some(Expression::from_header_of_table(&m));
                     self.header_p_of_table = None;
-                    self.state = State::AfterTable;
-                    return PResult::End;
+                    return self.end_of_expression(State::AfterTable, look_ahead_items);
                 } else {
                     return error(&mut self.log(), &look_ahead_items, "expression.rs.269.");
                 }
@@ -427,6 +474,111 @@ impl ExpressionP {
             PResult::Ongoing => PResult::Ongoing,
         }
     }
+    /// Key value without comment.  
+    /// コメントの無いキー値。  
+    ///
+    /// # Arguments
+    ///
+    /// * `look_ahead_items` - Tokens contains look ahead.  
+    ///   先読みを含むトークン。  
+    fn end_of_keyval(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
+        if let Some(keyval) = self.keyval_p.as_mut().unwrap().flush() {
+            self.buffer = Some(Expression::Keyval(
+                if let Some(ws_p_1) = self.ws_p_1.as_mut() {
+                    ws_p_1.get_ws()
+                } else {
+                    Ws::default()
+                },
+                keyval,
+                if let Some(ws_p_2) = self.ws_p_2.as_mut() {
+                    ws_p_2.get_ws()
+                } else {
+                    Ws::default()
+                },
+                None,
+                Newline::default(),
+            ));
+            self.keyval_p = None;
+        } else {
+            return error(&mut self.log(), &look_ahead_items, "expression.rs.222.");
+        }
+        self.end_of_expression(State::Eol, look_ahead_items)
+    }
+    /// The expression ends with the end of line, or the end of file.  
+    /// 式は行末か、ファイルの終わりで終わります。  
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - State that reads the rest of the line.  
+    ///   行の残りを読む状態。  
+    /// * `look_ahead_items` - Tokens contains look ahead.  
+    ///   先読みを含むトークン。  
+    fn end_of_expression(
+        &mut self,
+        state: State,
+        look_ahead_items: &LookAheadItems<char>,
+    ) -> PResult {
+        if look_ahead_items.get(1).is_none() {
+            // End of file.
+            // ファイルの終わり。
+            self.state = State::End;
+            return PResult::End;
+        }
+        self.state = state;
+        PResult::Ongoing
+    }
+    /// Whitespace and comment after the header.  
+    /// ヘッダーの後の空白とコメント。  
+    fn header_trailer(&mut self) -> Option<(&mut Ws, &mut Option<Comment>)> {
+        match self.buffer.as_mut() {
+            Some(Expression::HeaderOfArrayOfTable(m)) => Some((&mut m.ws2, &mut m.comment)),
+            Some(Expression::HeaderOfTable(m)) => Some((&mut m.ws2, &mut m.comment)),
+            _ => None,
+        }
+    }
+    /// `\r\n` or `\n`.  
+    ///
+    /// # Arguments
+    ///
+    /// * `look_ahead_items` - Tokens contains look ahead.  
+    ///   先読みを含むトークン。  
+    fn parse_newline(&mut self, look_ahead_items: &LookAheadItems<char>) -> PResult {
+        let chr0 = look_ahead_items.get(0).unwrap();
+        if let '\r' | '\n' = chr0 {
+            if let Some(m) = self.buffer.as_mut() {
+                m.push_newline_token(&Token::from_character(*chr0, TokenType::Newline));
+            }
+        }
+        match chr0 {
+            '\n' => {
+                self.state = State::End;
+                PResult::End
+            }
+            '\r' => {
+                if let Some('\n') = look_ahead_items.get(1) {
+                    self.state = State::Eol;
+                    PResult::Ongoing
+                } else {
+                    error(
+                        self.log().str(
+                            "message",
+                            "Control character U+000D is not allowed outside of `\\r\\n`.",
+                        ),
+                        look_ahead_items,
+                        "expression.rs.474.",
+                    )
+                }
+            }
+            _ => error(
+                self.log().str(
+                    "message",
+                    &format!("Expected end of line, but found `{}`.", chr0.escape_debug()),
+                ),
+                look_ahead_items,
+                "expression.rs.484.",
+            ),
+        }
+    }
     /// Log.  
     /// ログ。  
     pub fn log(&self) -> Table {
@@ -439,6 +591,12 @@ impl ExpressionP {
         if let Some(p) = &self.keyval_p {
             t.sub_t("keyval_p", &p.log());
         }
+        if let Some(p) = &self.header_p_of_array_of_table {
+            t.sub_t("header_p_of_array_of_table", &p.log());
+        }
+        if let Some(p) = &self.header_p_of_table {
+            t.sub_t("header_p_of_table", &p.log());
+        }
         t
     }
 }
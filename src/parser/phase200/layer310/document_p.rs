@@ -1,9 +1,11 @@
 //! Document syntax parser.  
 //! ドキュメント構文解析器。  
 
-use crate::model::layer310::TomlDocument;
+use crate::model::{
+    layer210::key::to_dotted_key, layer225::Val, layer230::Expression, layer310::TomlDocument,
+};
 use crate::parser::phase200::{
-    error_via,
+    error, error_via,
     {layer210::PResult, layer230::ExpressionP, layer310::DocumentP},
 };
 use casual_logger::Table;
@@ -11,7 +13,11 @@ use look_ahead_items::ItemsBuilder;
 
 impl Default for DocumentP {
     fn default() -> Self {
-        DocumentP { expression_p: None }
+        DocumentP {
+            expression_p: None,
+            table_key: Vec::new(),
+            inline_table_keys: Vec::new(),
+        }
     }
 }
 impl DocumentP {
@@ -20,8 +26,10 @@ impl DocumentP {
     /// * `PResult` - Result.  
     ///                             結果。
     pub fn scan_line(&mut self, char_vec: &Vec<char>, doc: &mut TomlDocument) -> PResult {
+        // The current character and 4 ahead. A date needs `nnnn-`.
+        // 現在の文字と、４つ先まで。日付には `nnnn-` が要ります。
         let items = ItemsBuilder::default()
-            .set_look_ahead_size(4)
+            .set_look_ahead_size(5)
             .read(char_vec)
             .build();
 
@@ -29,7 +37,7 @@ impl DocumentP {
         //             先読みを含むトークン。
         // (Current character, 1 ahead character, 2 ahead character)
         // （現在のトークン, １つ先のトークン，２つ先のトークン）
-        for look_ahead_characters in items {
+        for (i, look_ahead_characters) in items.enumerate() {
             if let None = self.expression_p {
                 self.expression_p = Some(ExpressionP::default());
             }
@@ -37,6 +45,17 @@ impl DocumentP {
             match p.parse(&look_ahead_characters) {
                 PResult::End => {
                     if let Some(m) = p.flush() {
+                        if let Some(message) = self.closed_table_message(&m) {
+                            let result = error(
+                                self.log()
+                                    .usize("column_number", i + 1)
+                                    .str("message", &message),
+                                &look_ahead_characters,
+                                "document.rs.47.",
+                            );
+                            self.expression_p = None;
+                            return result;
+                        }
                         doc.push_element(&m);
                         self.expression_p = None;
                     } else {
@@ -47,12 +66,16 @@ impl DocumentP {
                     }
                 }
                 PResult::Err(mut table) => {
-                    return error_via(
+                    let result = error_via(
                         &mut table,
-                        &mut self.log(),
+                        self.log().usize("column_number", i + 1),
                         &look_ahead_characters,
                         "document.rs.92.",
                     );
+                    // The next line starts a new expression.
+                    // 次の行は新しい式から始めます。
+                    self.expression_p = None;
+                    return result;
                 }
                 PResult::Ongoing => {}
             }
@@ -61,6 +84,62 @@ impl DocumentP {
         PResult::Ongoing
     }
 
+    /// Inline tables are closed. Keys and sub-tables can't be added to them later.  
+    /// インライン・テーブルは閉じています。後からキーやサブ・テーブルを追加できません。  
+    fn closed_table_message(&mut self, m: &Expression) -> Option<String> {
+        let (key, is_header) = match m {
+            Expression::EmptyLine(_ws, _comment, _newline) => return None,
+            Expression::HeaderOfArrayOfTable(m) => (m.key.get_segments().to_vec(), true),
+            Expression::HeaderOfTable(m) => (m.key.get_segments().to_vec(), true),
+            Expression::Keyval(_ws1, keyval, _ws2, _comment, _newline) => {
+                let mut key = self.table_key.clone();
+                key.extend(keyval.key.get_segments().iter().cloned());
+                (key, false)
+            }
+        };
+        for inline_table_key in &self.inline_table_keys {
+            let message = if is_header && key == *inline_table_key {
+                format!(
+                    "`{}` is already defined as an inline table.",
+                    to_dotted_key(&key)
+                )
+            } else if key.starts_with(inline_table_key) && inline_table_key.len() < key.len() {
+                format!(
+                    "`{}` is an inline table. It is closed, so `{}` can't extend it.",
+                    to_dotted_key(inline_table_key),
+                    to_dotted_key(&key)
+                )
+            } else {
+                continue;
+            };
+            // The keys under a rejected header are in it, so they are rejected too.
+            // 拒否したヘッダーの下のキーはその中にあるので、それらも拒否します。
+            if is_header {
+                self.table_key = key;
+            }
+            return Some(message);
+        }
+        match m {
+            Expression::HeaderOfArrayOfTable(_) => {
+                // A new table in the array. Its inline tables are new too.
+                // 配列の中の新しいテーブル。そのインライン・テーブルも新しいものです。
+                self.inline_table_keys
+                    .retain(|m| !(m.len() > key.len() && m.starts_with(&key)));
+                self.table_key = key;
+            }
+            Expression::HeaderOfTable(_) => {
+                self.table_key = key;
+            }
+            Expression::Keyval(_ws1, keyval, _ws2, _comment, _newline) => {
+                if let Val::InlineTable(_) = *keyval.val {
+                    self.inline_table_keys.push(key);
+                }
+            }
+            Expression::EmptyLine(_ws, _comment, _newline) => {}
+        }
+        None
+    }
+
     /// Log.  
     /// ログ。  
     pub fn log(&self) -> Table {
@@ -6,4 +6,10 @@ use crate::parser::phase200::layer230::ExpressionP;
 /// ドキュメント構文解析器。  
 pub struct DocumentP {
     pub expression_p: Option<ExpressionP>,
+    /// Name of the current table. Empty for the root table.  
+    /// 現在のテーブル名。ルート・テーブルなら空。  
+    table_key: Vec<String>,
+    /// Full names of the inline tables. They are closed to extension.  
+    /// インライン・テーブルのフル・ネーム。拡張できません。  
+    inline_table_keys: Vec<Vec<String>>,
 }
@@ -51,3 +51,27 @@ fn error_via(
 
     PResult::Err(escalated_table1.sub_t(&random_name(), this_table).clone())
 }
+
+/// Control characters are not allowed in strings and comments.  
+/// 制御文字は文字列やコメントに置けません。  
+///
+/// # Arguments
+///
+/// * `chr` - Character.  
+///   文字。  
+/// * `place` - Where the character is. Example: `a comment`.  
+///   文字のある場所。  
+///
+/// # Returns
+///
+/// * `Option<String>` - Message, if it's a forbidden control character.  
+///   禁止された制御文字ならメッセージ。  
+fn control_character_message(chr: char, place: &str) -> Option<String> {
+    match chr as u32 {
+        0x00..=0x08 | 0x0A..=0x1F | 0x7F => Some(format!(
+            "Control character U+{:04X} is not allowed in {}.",
+            chr as u32, place
+        )),
+        _ => None,
+    }
+}